@@ -1,28 +1,19 @@
 use std::env;
 use std::fs::{File, OpenOptions};
 use std::io;
-use std::mem;
-use std::ptr::{copy_nonoverlapping, write_bytes};
+use std::ptr::copy_nonoverlapping;
 use std::time::SystemTime;
 use sword::mem::NockStack;
-use sword::noun::{DirectAtom, IndirectAtom};
-use sword::serialization::{cue, jam};
+use sword::noun::DirectAtom;
+use sword::serialization::{cue_bytes, cue_fast_bytes, jam};
+use sword::unifying_equality::unifying_equality;
 
 fn main() -> io::Result<()> {
     let filename = env::args().nth(1).expect("Must provide input filename");
     let output_filename = format!("{}.out", filename);
     let f = File::open(filename)?;
-    let in_len = f.metadata()?.len();
     let mut stack = NockStack::new(1 << 10 << 10 << 10, 0);
-    let jammed_input = unsafe {
-        let in_map = memmap2::Mmap::map(&f)?;
-        let word_len = (in_len + 7) >> 3;
-        let (mut atom, dest) = IndirectAtom::new_raw_mut(&mut stack, word_len as usize);
-        write_bytes(dest.add(word_len as usize - 1), 0, 8);
-        copy_nonoverlapping(in_map.as_ptr(), dest as *mut u8, in_len as usize);
-        mem::drop(in_map);
-        atom.normalize_as_atom()
-    };
+    let in_map = unsafe { memmap2::Mmap::map(&f)? };
 
     let now = SystemTime::now();
 
@@ -33,7 +24,7 @@ fn main() -> io::Result<()> {
             break;
         };
         i += 1;
-        input = cue(&mut stack, jammed_input).unwrap();
+        input = cue_bytes(&mut stack, &in_map).unwrap();
     }
 
     match now.elapsed() {
@@ -43,6 +34,18 @@ fn main() -> io::Result<()> {
         Err(_) => println!("NO TIME FOR YOU!"),
     };
 
+    let fast = SystemTime::now();
+
+    let mut fast_input = cue_fast_bytes(&mut stack, &in_map).unwrap();
+
+    match fast.elapsed() {
+        Ok(elapse) => {
+            println!("Fast cue: {}", elapse.as_secs_f64());
+        }
+        Err(_) => println!("NO TIME FOR YOU!"),
+    };
+    assert!(unsafe { unifying_equality(&mut stack, &mut fast_input, &mut input) });
+
     let nuw = SystemTime::now();
 
     let jammed_output = jam(&mut stack, input);
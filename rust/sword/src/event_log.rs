@@ -0,0 +1,421 @@
+/** Event log: an append-only file of numbered, jammed events
+ *
+ * The file is a short header followed by one record per event:
+ *
+ * | bytes | contents                                        |
+ * |-------|-------------------------------------------------|
+ * | 8     | magic, "swrdelog" (file header)                 |
+ * | 4     | format version, little-endian (file header)     |
+ * | 8     | event number, little-endian                     |
+ * | 4     | mug of the event noun, little-endian            |
+ * | 4     | byte length of the jammed event, little-endian  |
+ * | ...   | jam of the event                                |
+ *
+ * Events are numbered from 1 with no gaps. Appends are buffered and only made durable by
+ * [EventLog::commit], which appending does by itself every [EventLog::set_batch] events, so that
+ * a burst of events costs one fsync rather than one each. A record torn by a crash mid-write, or
+ * a zeroed tail left by a file extended but never written, is dropped when the log is next opened.
+ */
+use crate::interpreter::Error;
+use crate::mem::NockStack;
+use crate::mug::mug_u32;
+use crate::noun::{IndirectAtom, Noun, D};
+use crate::serialization::{cue, jam_to_vec};
+use std::convert::TryInto;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+crate::gdb!();
+
+const MAGIC: &[u8; 8] = b"swrdelog";
+const VERSION: u32 = 1;
+const FILE_HEADER_SIZE: u64 = 12;
+const RECORD_HEADER_SIZE: usize = 16;
+
+/** Events appended between fsyncs, by default */
+pub const DEFAULT_BATCH: usize = 64;
+
+#[derive(Debug, Error)]
+pub enum LogError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("not an event log")]
+    Magic,
+    #[error("unsupported event log version {0}")]
+    Version(u32),
+    #[error("expected event {expected}, found {found}")]
+    Sequence { expected: u64, found: u64 },
+    #[error("could not cue event {0}")]
+    Cue(u64),
+    #[error("mug mismatch in event {0}")]
+    Mug(u64),
+    #[error("event is too large to log")]
+    TooLarge,
+}
+
+pub struct EventLog {
+    path: PathBuf,
+    output: BufWriter<File>,
+    last: u64,
+    /** Number of the last event made durable */
+    committed: u64,
+    pending: usize,
+    batch: usize,
+}
+
+struct Record {
+    eve: u64,
+    mug: u32,
+    size: usize,
+}
+
+impl Record {
+    fn is_zero(&self) -> bool {
+        self.eve == 0 && self.mug == 0 && self.size == 0
+    }
+}
+
+impl EventLog {
+    /** Open the event log at `path`, creating it if it does not exist */
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<EventLog, LogError> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        if file.metadata()?.len() == 0 {
+            let mut header = [0u8; FILE_HEADER_SIZE as usize];
+            header[0..8].copy_from_slice(MAGIC);
+            header[8..12].copy_from_slice(&VERSION.to_le_bytes());
+            file.write_all(&header)?;
+            file.sync_all()?;
+        }
+
+        // Find the last event, and the end of the last complete record
+        file.seek(SeekFrom::Start(0))?;
+        let mut input = BufReader::new(file.try_clone()?);
+        read_file_header(&mut input)?;
+        let len = file.metadata()?.len();
+        let mut last = 0;
+        let mut end = FILE_HEADER_SIZE;
+        while let Some(record) = read_record_header(&mut input)? {
+            // A record running past the end of the file, or zeroes from here to the end, are the
+            // tail of a write a crash cut short
+            let body_end = end + (RECORD_HEADER_SIZE + record.size) as u64;
+            if body_end > len || (record.is_zero() && rest_is_zero(&mut input)?) {
+                break;
+            }
+            if record.eve != last + 1 {
+                return Err(LogError::Sequence {
+                    expected: last + 1,
+                    found: record.eve,
+                });
+            }
+            input.seek(SeekFrom::Start(body_end))?;
+            last = record.eve;
+            end = body_end;
+        }
+
+        // Drop a record torn by a crash
+        file.set_len(end)?;
+        file.seek(SeekFrom::Start(end))?;
+
+        Ok(EventLog {
+            path,
+            output: BufWriter::new(file),
+            last,
+            committed: last,
+            pending: 0,
+            batch: DEFAULT_BATCH,
+        })
+    }
+
+    /** Number of the last event in the log, or 0 if it is empty */
+    pub fn last(&self) -> u64 {
+        self.last
+    }
+
+    /** Commit after every `batch` appends (at least 1) */
+    pub fn set_batch(&mut self, batch: usize) {
+        self.batch = batch.max(1);
+    }
+
+    /** Append an event, returning its number */
+    pub fn append(&mut self, stack: &mut NockStack, event: Noun) -> Result<u64, LogError> {
+        let mug = mug_u32(stack, event);
+        let bytes = jam_to_vec(stack, event);
+        let size = bytes.len();
+        if size > u32::MAX as usize {
+            return Err(LogError::TooLarge);
+        }
+
+        let eve = self.last + 1;
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        header[0..8].copy_from_slice(&eve.to_le_bytes());
+        header[8..12].copy_from_slice(&mug.to_le_bytes());
+        header[12..16].copy_from_slice(&(size as u32).to_le_bytes());
+        self.output.write_all(&header)?;
+        self.output.write_all(&bytes)?;
+        self.last = eve;
+
+        self.pending += 1;
+        if self.pending >= self.batch {
+            self.commit()?;
+        }
+        Ok(eve)
+    }
+
+    /** Make every appended event durable */
+    pub fn commit(&mut self) -> io::Result<()> {
+        if self.pending > 0 {
+            self.output.flush()?;
+            self.output.get_ref().sync_data()?;
+            self.pending = 0;
+            self.committed = self.last;
+        }
+        Ok(())
+    }
+
+    /** Read the committed events from number `from` onwards
+     *
+     * Events appended since the last commit are not read, even once written out of the buffer.
+     */
+    pub fn events(&self, from: u64) -> Result<LogReader, LogError> {
+        let mut input = BufReader::new(File::open(&self.path)?);
+        read_file_header(&mut input)?;
+        Ok(LogReader {
+            input,
+            from,
+            next: 1,
+            last: self.committed,
+        })
+    }
+}
+
+impl Drop for EventLog {
+    fn drop(&mut self) {
+        let _ = self.commit();
+    }
+}
+
+/** Reads events out of an [EventLog], onto a NockStack */
+pub struct LogReader {
+    input: BufReader<File>,
+    from: u64,
+    next: u64,
+    /** Number of the last committed event when the reader was made */
+    last: u64,
+}
+
+impl LogReader {
+    /** The next event and its number, checked against its mug. None at the end of the log. */
+    pub fn next(&mut self, stack: &mut NockStack) -> Option<Result<(u64, Noun), LogError>> {
+        self.read(stack).transpose()
+    }
+
+    fn read(&mut self, stack: &mut NockStack) -> Result<Option<(u64, Noun)>, LogError> {
+        loop {
+            if self.next > self.last {
+                return Ok(None);
+            }
+            let record = match read_record_header(&mut self.input)? {
+                Some(record) => record,
+                None => return Ok(None),
+            };
+            if record.eve != self.next {
+                return Err(LogError::Sequence {
+                    expected: self.next,
+                    found: record.eve,
+                });
+            }
+            self.next += 1;
+
+            if record.eve < self.from {
+                self.input.seek_relative(record.size as i64)?;
+                continue;
+            }
+
+            let event = if record.size == 0 {
+                D(0)
+            } else {
+                let atom = unsafe {
+                    let (mut atom, body) = IndirectAtom::new_raw_mut_bytes(stack, record.size);
+                    match self.input.read_exact(body) {
+                        // A torn record is the end of the log
+                        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                        res => res?,
+                    }
+                    atom.normalize_as_atom()
+                };
+                cue(stack, atom).map_err(|_: Error| LogError::Cue(record.eve))?
+            };
+            if mug_u32(stack, event) != record.mug {
+                return Err(LogError::Mug(record.eve));
+            }
+            return Ok(Some((record.eve, event)));
+        }
+    }
+}
+
+fn read_file_header<R: Read>(input: &mut R) -> Result<(), LogError> {
+    let mut header = [0u8; FILE_HEADER_SIZE as usize];
+    input.read_exact(&mut header)?;
+    if &header[0..8] != MAGIC {
+        return Err(LogError::Magic);
+    }
+    let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+    if version != VERSION {
+        return Err(LogError::Version(version));
+    }
+    Ok(())
+}
+
+/** Read the header of the next record, or None at the end of the log (even mid-header) */
+fn read_record_header<R: Read>(input: &mut R) -> Result<Option<Record>, LogError> {
+    let mut header = [0u8; RECORD_HEADER_SIZE];
+    match input.read_exact(&mut header) {
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        res => res?,
+    }
+    Ok(Some(Record {
+        eve: u64::from_le_bytes(header[0..8].try_into().unwrap()),
+        mug: u32::from_le_bytes(header[8..12].try_into().unwrap()),
+        size: u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize,
+    }))
+}
+
+/** Whether the rest of the input is all zeroes */
+fn rest_is_zero<R: Read>(input: &mut R) -> io::Result<bool> {
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        if buffer[..read].iter().any(|&byte| byte != 0) {
+            return Ok(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::{assert_noun_eq, init_context};
+    use crate::noun::T;
+    use std::fs;
+
+    #[test]
+    fn test_event_log_round_trip() {
+        let path = std::env::temp_dir().join(format!("sword-event-log-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut c = init_context();
+        let stack = &mut c.stack;
+
+        let mut log = EventLog::open(&path).unwrap();
+        log.set_batch(2);
+        for n in 0..3 {
+            let event = T(stack, &[D(n), D(n + 1)]);
+            assert_eq!(log.append(stack, event).unwrap(), n + 1);
+        }
+        drop(log);
+
+        // Tear the last record, as if we crashed while writing it
+        let len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 1)
+            .unwrap();
+
+        let log = EventLog::open(&path).unwrap();
+        assert_eq!(log.last(), 2);
+        let mut events = log.events(2).unwrap();
+        let (eve, event) = events.next(stack).unwrap().unwrap();
+        assert_eq!(eve, 2);
+        let expected = T(stack, &[D(1), D(2)]);
+        assert_noun_eq(stack, event, expected);
+        assert!(events.next(stack).is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_event_log_torn_tails() {
+        let path = std::env::temp_dir().join(format!("sword-event-tail-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut c = init_context();
+        let stack = &mut c.stack;
+
+        let mut log = EventLog::open(&path).unwrap();
+        for n in 0..2 {
+            log.append(stack, D(n + 10)).unwrap();
+        }
+        drop(log);
+        let len = fs::metadata(&path).unwrap().len();
+
+        let tails: [&[u8]; 3] = [
+            // Half a record header
+            &[3, 0, 0, 0, 0, 0],
+            // A zeroed header and more, as from a file extended but never written
+            &[0; 40],
+            // A whole header whose body never made it
+            &[3, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 100, 0, 0, 0, 0x80],
+        ];
+        for tail in tails {
+            let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+            file.write_all(tail).unwrap();
+            drop(file);
+
+            let log = EventLog::open(&path).unwrap();
+            assert_eq!(log.last(), 2);
+            assert_eq!(fs::metadata(&path).unwrap().len(), len);
+        }
+
+        // Zeroes followed by anything else aren't a torn tail
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0; 20]).unwrap();
+        file.write_all(&[1]).unwrap();
+        drop(file);
+        assert!(matches!(
+            EventLog::open(&path),
+            Err(LogError::Sequence {
+                expected: 3,
+                found: 0
+            })
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_event_log_reads_only_committed() {
+        let path = std::env::temp_dir().join(format!("sword-event-commit-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut c = init_context();
+        let stack = &mut c.stack;
+
+        let mut log = EventLog::open(&path).unwrap();
+        log.append(stack, D(10)).unwrap();
+        log.commit().unwrap();
+        log.append(stack, D(11)).unwrap();
+        // Written out, but not committed
+        log.output.flush().unwrap();
+
+        let mut events = log.events(1).unwrap();
+        assert_eq!(events.next(stack).unwrap().unwrap().0, 1);
+        assert!(events.next(stack).is_none());
+
+        log.commit().unwrap();
+        let mut events = log.events(2).unwrap();
+        assert_eq!(events.next(stack).unwrap().unwrap().0, 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,21 +1,36 @@
-use crate::hamt::Hamt;
 use crate::jets::cold::Cold;
-use crate::jets::hot::Hot;
+use crate::jets::hot::{Hot, HotEntry, URBIT_HOT_STATE};
 use crate::jets::warm::Warm;
-use crate::jets::{cold, JetErr};
-use crate::mem::{NockStack, Preserve};
-use crate::noun::{Atom, Cell, IndirectAtom, Noun, Slots, D, T};
-use crate::trace::{write_nock_trace, TraceInfo, TraceStack};
+use crate::jets::{cold, JetErr, JetTestMode};
+use crate::mem::{AllocationError, NewStackError, NockStack, Preserve};
+use crate::memo::{MemoCache, DEFAULT_CAPACITY};
+use crate::mug::met3_usize;
+use crate::noun::{tape, Atom, Cell, IndirectAtom, Noun, Slots, D, T};
+use crate::profile::{Profile, ProfileNode, ROOT};
+use crate::trace::{
+    create_trace_file_at, path_to_cord, trace_name, write_metadata, write_nock_trace,
+    TraceCategory, TraceConfig, TraceInfo, TraceStack,
+};
 use crate::unifying_equality::unifying_equality;
 use crate::{assert_acyclic, assert_no_forwarding_pointers, assert_no_junior_pointers, flog, noun};
-use assert_no_alloc::{assert_no_alloc, ensure_alloc_counters};
+use assert_no_alloc::{assert_no_alloc, ensure_alloc_counters, permit_alloc};
 use bitvec::prelude::{BitSlice, Lsb0};
 use either::*;
+use signal_hook::consts::SIGINT;
+use signal_hook::SigId;
+use std::collections::BTreeSet;
+use std::io::Write;
 use std::ops::DerefMut;
+use std::panic::{self, catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Once};
+use std::thread::{self, JoinHandle};
 use std::time::Instant;
 use sword_macros::tas;
+use thiserror::Error;
 
 crate::gdb!();
 
@@ -262,6 +277,26 @@ pub trait Slogger {
     fn flog(&mut self, stack: &mut NockStack, cord: Noun);
 }
 
+/** The answer to a scry from a [ScryHandler] */
+pub enum ScryResult {
+    /** The value bound at the path */
+    Found(Noun),
+    /** Nothing is known at the path yet; the scry blocks (Error::ScryBlocked) */
+    NotFound,
+    /** The path can never be bound; the scry crashes */
+    Crash,
+}
+
+/** A scry namespace implemented in Rust
+ *
+ * Register one with [Context::scry_handler] to get a noun which can go anywhere a scry gate can:
+ * on `context.scry_stack`, or as the scry argument to +mink.
+ */
+pub trait ScryHandler {
+    /** Resolve a Nock 12 with reference `reff` at `path` */
+    fn scry(&mut self, stack: &mut NockStack, reff: Noun, path: Noun) -> ScryResult;
+}
+
 impl<T: Slogger + DerefMut + Unpin + Sized> Slogger for Pin<&mut T>
 where
     T::Target: Slogger + DerefMut + Unpin + Sized,
@@ -277,10 +312,28 @@ where
     }
 }
 
+/** Slogger which prints to stderr, for embedders without a king to talk to */
+pub struct StderrSlogger;
+
+impl Slogger for StderrSlogger {
+    fn slog(&mut self, _stack: &mut NockStack, pri: u64, tank: Noun) {
+        eprintln!("\rslog [{}]: {}", pri, tank);
+    }
+
+    fn flog(&mut self, _stack: &mut NockStack, cord: Noun) {
+        if let Ok(atom) = cord.as_atom() {
+            let bytes = atom.as_bytes();
+            let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+            let _ = std::io::stderr().write_all(&bytes[..len]);
+            eprintln!();
+        }
+    }
+}
+
 pub struct ContextSnapshot {
     cold: Cold,
     warm: Warm,
-    cache: Hamt<Noun>,
+    cache: MemoCache,
 }
 
 pub struct Context {
@@ -289,9 +342,36 @@ pub struct Context {
     pub cold: Cold,
     pub warm: Warm,
     pub hot: Hot,
-    pub cache: Hamt<Noun>,
+    pub cache: MemoCache,
     pub scry_stack: Noun,
     pub trace_info: Option<TraceInfo>,
+    /** Calls, time, and allocation per jet path and %spot, if profiling */
+    pub profile: Option<Profile>,
+    /** When set, interpretation stops with %intr at the next safe point */
+    pub interrupt: Arc<AtomicBool>,
+    /** Steps left before interpretation stops with %fuel, or None for no limit */
+    pub fuel: Option<u64>,
+    /** Jets to check against the raw Nock of their formulas */
+    pub test_jets: JetTestMode,
+    /** When set, collects the paths of the jets which run (see replay::Replay::verify) */
+    pub fired_jets: Option<BTreeSet<String>>,
+    /** Native scry handlers, indexed by the atoms standing in for them on the scry stack */
+    scry_handlers: Vec<Box<dyn ScryHandler>>,
+    /** Slot holding the mean stack of the innermost running interpret call, for %hela */
+    mean_slot: *const Noun,
+    /** Mean stacks of the enclosing interpret calls, for %hela */
+    mean_road: *const MeanRoad,
+}
+
+/** An interpret call which has called out to a nested one (e.g. through +mink or a scry)
+ *
+ * Each interpret call keeps one of these on the Rust stack while it runs, so that %hela can walk
+ * the mean stacks of every enclosing call down to the root.
+ */
+struct MeanRoad {
+    /** Slot holding the enclosing call's mean stack (null at the root) */
+    mean: *const Noun,
+    outer: *const MeanRoad,
 }
 
 impl Context {
@@ -303,10 +383,11 @@ impl Context {
         }
     }
 
+    /** Go back to the state saved by [Context::save], e.g. after a crash */
     pub fn restore(&mut self, saved: &ContextSnapshot) {
         self.cold = saved.cold;
         self.warm = saved.warm;
-        self.cache = saved.cache;
+        self.cache.rewind(saved.cache);
     }
 
     /**
@@ -331,6 +412,294 @@ impl Context {
         self.stack.frame_pop();
         ret
     }
+
+    /** Charge fuel for one step. Returns false if the budget is spent. */
+    fn burn(&mut self, steps: u64) -> bool {
+        match self.fuel.as_mut() {
+            Some(fuel) if *fuel < steps => {
+                *fuel = 0;
+                false
+            }
+            Some(fuel) => {
+                *fuel -= steps;
+                true
+            }
+            None => true,
+        }
+    }
+
+    /** Charge fuel for running a jet: one step, plus one per cell and atom word of the sample */
+    fn burn_jet(&mut self, core: Noun) -> bool {
+        let limit = match self.fuel {
+            Some(fuel) => fuel,
+            None => return true,
+        };
+        let mut cost = 1;
+        if let Ok(sample) = core.slot(6) {
+            let stack = &mut self.stack;
+            stack.frame_push(0);
+            unsafe {
+                *(stack.push()) = sample;
+                // Stop counting once we're over budget, so the walk is bounded by the fuel too
+                while !stack.stack_is_empty() && cost <= limit {
+                    let noun: Noun = *(stack.top());
+                    stack.pop::<Noun>();
+                    match noun.as_either_atom_cell() {
+                        Left(atom) => cost += atom.size() as u64,
+                        Right(cell) => {
+                            cost += 1;
+                            *(stack.push()) = cell.tail();
+                            *(stack.push()) = cell.head();
+                        }
+                    }
+                }
+                stack.frame_pop();
+            }
+        }
+        self.burn(cost)
+    }
+
+    /** Register a native scry handler, returning the noun which stands for it on the scry stack
+     *
+     * Scry gates are always cells, so handlers are represented by their index as an atom.
+     */
+    pub fn scry_handler<H: ScryHandler + 'static>(&mut self, handler: H) -> Noun {
+        self.scry_handlers.push(Box::new(handler));
+        D(self.scry_handlers.len() as u64 - 1)
+    }
+
+    /** Note that the jet at `path` is about to run, if collecting fired jets
+     *
+     * `path` is a cold state path, or the name of a %sham jet.
+     */
+    pub(crate) fn note_jet(&mut self, path: Noun) {
+        if self.fired_jets.is_none() {
+            return;
+        }
+        let cord = match path.as_atom() {
+            Ok(name) => name,
+            Err(_) => path_to_cord(&mut self.stack, path),
+        };
+        let bytes = &cord.as_bytes()[0..met3_usize(cord)];
+        if let Some(ref mut fired) = self.fired_jets {
+            permit_alloc(|| fired.insert(String::from_utf8_lossy(bytes).into_owned()));
+        }
+    }
+
+    /** Whether to push trace entries of this category, for the trace file or the profile */
+    fn traces(&self, category: TraceCategory) -> bool {
+        self.profile.is_some()
+            || self
+                .trace_info
+                .as_ref()
+                .is_some_and(|info| info.config.includes(category))
+    }
+
+    /** Check for and clear a pending interrupt */
+    fn interrupted(&self) -> bool {
+        self.interrupt.load(Ordering::Relaxed) && self.interrupt.swap(false, Ordering::Relaxed)
+    }
+}
+
+/** Interrupt the interpreter whenever the process receives SIGINT */
+pub fn interrupt_on_sigint(interrupt: &Arc<AtomicBool>) -> std::io::Result<SigId> {
+    signal_hook::flag::register(SIGINT, interrupt.clone())
+}
+
+/** Interrupt the interpreter once a wall-clock deadline passes
+ *
+ * The watchdog runs on its own thread until it fires or the returned [Deadline] is dropped.
+ */
+pub fn interrupt_at(interrupt: &Arc<AtomicBool>, deadline: Instant) -> Deadline {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let interrupt = interrupt.clone();
+        let cancel = cancel.clone();
+        thread::spawn(move || loop {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                interrupt.store(true, Ordering::Relaxed);
+                break;
+            }
+            thread::park_timeout(deadline - now);
+        })
+    };
+    Deadline {
+        cancel,
+        thread: Some(thread),
+    }
+}
+
+/** A pending [interrupt_at] watchdog, cancelled on drop */
+pub struct Deadline {
+    cancel: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Deadline {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+/** Default NockStack size for a built Context, in 64-bit words (1GiB) */
+pub const DEFAULT_STACK_SIZE: usize = 1 << 27;
+
+#[derive(Debug, Error)]
+pub enum ContextError {
+    #[error("Failed to create stack: {0}")]
+    Stack(#[from] NewStackError),
+    #[error("Failed to create trace file: {0}")]
+    Trace(#[from] std::io::Error),
+}
+
+/** Builder for a ready-to-use [Context]
+ *
+ * Defaults to a [DEFAULT_STACK_SIZE] stack, a [StderrSlogger], the [URBIT_HOT_STATE] jet table,
+ * and no tracing.
+ */
+pub struct ContextBuilder<'a> {
+    stack_size: usize,
+    slogger: Option<Pin<Box<dyn Slogger + Unpin>>>,
+    hot_state: &'a [HotEntry],
+    trace_sink: Option<TraceSink>,
+    trace_config: TraceConfig,
+    profile: bool,
+    test_jets: JetTestMode,
+    cache_capacity: usize,
+}
+
+/** Where a [ContextBuilder] should write its trace */
+enum TraceSink {
+    Path(PathBuf),
+    Writer(Box<dyn Write>),
+}
+
+impl<'a> ContextBuilder<'a> {
+    pub fn new() -> Self {
+        ContextBuilder {
+            stack_size: DEFAULT_STACK_SIZE,
+            slogger: None,
+            hot_state: URBIT_HOT_STATE,
+            trace_sink: None,
+            trace_config: TraceConfig::default(),
+            profile: false,
+            cache_capacity: DEFAULT_CAPACITY,
+            test_jets: JetTestMode::Off,
+        }
+    }
+
+    /** Size of the NockStack, in 64-bit words */
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = stack_size;
+        self
+    }
+
+    /** Destination for %slog and %flog output */
+    pub fn slogger<S: Slogger + Unpin + 'static>(mut self, slogger: S) -> Self {
+        self.slogger = Some(Box::pin(slogger));
+        self
+    }
+
+    /** Table of jets to match against registered cores */
+    pub fn hot_state(mut self, hot_state: &'a [HotEntry]) -> Self {
+        self.hot_state = hot_state;
+        self
+    }
+
+    /** Write a JSON trace of Nock execution to this file */
+    pub fn trace_path(mut self, trace_path: PathBuf) -> Self {
+        self.trace_sink = Some(TraceSink::Path(trace_path));
+        self
+    }
+
+    /** Write a JSON trace of Nock execution to this writer */
+    pub fn trace_writer<W: Write + 'static>(mut self, writer: W) -> Self {
+        self.trace_sink = Some(TraceSink::Writer(Box::new(writer)));
+        self
+    }
+
+    /** What to include in the trace, if there is one */
+    pub fn trace_config(mut self, trace_config: TraceConfig) -> Self {
+        self.trace_config = trace_config;
+        self
+    }
+
+    /** Bound the memo cache at this many entries (see [MemoCache]), or usize::MAX for no bound */
+    pub fn cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache_capacity = capacity;
+        self
+    }
+
+    /** Profile calls into jet paths and %spot hints (see [Profile]) */
+    pub fn profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /** Check these jets against the raw Nock of their formulas, slogging any mismatch */
+    pub fn test_jets(mut self, test_jets: JetTestMode) -> Self {
+        self.test_jets = test_jets;
+        self
+    }
+
+    pub fn build(self) -> result::Result<Context, ContextError> {
+        let trace_info = match self.trace_sink {
+            Some(sink) => {
+                let mut info = match sink {
+                    TraceSink::Path(path) => create_trace_file_at(path)?,
+                    TraceSink::Writer(writer) => TraceInfo::new(writer, self.trace_config),
+                };
+                info.config = self.trace_config;
+                write_metadata(&mut info)?;
+                Some(info)
+            }
+            None => None,
+        };
+
+        let (mut stack, _) = NockStack::new_(self.stack_size, 0)?;
+        let mut cold = Cold::new(&mut stack);
+        let hot = Hot::init(&mut stack, self.hot_state);
+        let warm = Warm::init(&mut stack, &mut cold, &hot);
+        let cache = MemoCache::new(&mut stack, self.cache_capacity);
+        let slogger = self.slogger.unwrap_or_else(|| Box::pin(StderrSlogger));
+
+        Ok(Context {
+            stack,
+            slogger,
+            cold,
+            warm,
+            hot,
+            cache,
+            scry_stack: D(0),
+            trace_info,
+            profile: if self.profile {
+                Some(Profile::new())
+            } else {
+                None
+            },
+            interrupt: Arc::new(AtomicBool::new(false)),
+            fuel: None,
+            test_jets: self.test_jets,
+            fired_jets: None,
+            scry_handlers: Vec::new(),
+            mean_slot: std::ptr::null(),
+            mean_road: std::ptr::null(),
+        })
+    }
+}
+
+impl Default for ContextBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -339,6 +708,7 @@ pub enum Mote {
     Fail = tas!(b"fail") as isize,
     Intr = tas!(b"intr") as isize,
     Meme = tas!(b"meme") as isize,
+    Fuel = tas!(b"fuel") as isize,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -385,6 +755,9 @@ pub type Result = result::Result<Noun, Error>;
 
 const BAIL_EXIT: Result = Err(Error::Deterministic(Mote::Exit, D(0)));
 const BAIL_FAIL: Result = Err(Error::NonDeterministic(Mote::Fail, D(0)));
+const BAIL_MEME: Result = Err(Error::NonDeterministic(Mote::Meme, D(0)));
+const BAIL_INTR: Result = Err(Error::NonDeterministic(Mote::Intr, D(0)));
+const BAIL_FUEL: Result = Err(Error::Deterministic(Mote::Fuel, D(0)));
 
 #[allow(unused_variables)]
 fn debug_assertions(stack: &mut NockStack, noun: Noun) {
@@ -393,12 +766,53 @@ fn debug_assertions(stack: &mut NockStack, noun: Noun) {
     assert_no_junior_pointers!(stack, noun);
 }
 
+thread_local! {
+    /// How many calls on this thread are ready to catch an [AllocationError] unwind
+    static CATCHING_ALLOC: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/** Keep the panic hook quiet about an [AllocationError] caught by [catch_alloc].
+ *
+ * Running out of stack is expected and turned into %meme, but the default hook would print a
+ * message and backtrace to stderr for every one. Other panics go to the hook installed before.
+ */
+fn quiet_alloc_panics() {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let expected = info.payload().is::<AllocationError>()
+                && CATCHING_ALLOC.with(|catching| catching.get() > 0);
+            if !expected {
+                hook(info);
+            }
+        }));
+    });
+}
+
+/** Catch an unwind from `f`, as the places which turn running out of stack into %meme do */
+fn catch_alloc<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    CATCHING_ALLOC.with(|catching| catching.set(catching.get() + 1));
+    let res = catch_unwind(AssertUnwindSafe(f));
+    CATCHING_ALLOC.with(|catching| catching.set(catching.get() - 1));
+    res
+}
+
 /** Interpret nock */
 pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Result {
+    quiet_alloc_panics();
     let orig_subject = subject; // for debugging
     let snapshot = context.save();
     let virtual_frame: *const u64 = context.stack.get_frame_pointer();
     let mut res: Noun = D(0);
+    let road = MeanRoad {
+        mean: context.mean_slot,
+        outer: context.mean_road,
+    };
+    let profile_node = context
+        .profile
+        .as_ref()
+        .map_or(ROOT, |profile| profile.current());
 
     // Setup stack for Nock computation
     unsafe {
@@ -406,6 +820,8 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
 
         // Bottom of mean stack
         *(context.stack.local_noun_pointer(0)) = D(0);
+        context.mean_slot = context.stack.local_noun_pointer(0);
+        context.mean_road = &road;
         // Bottom of trace stack
         *(context.stack.local_noun_pointer(1) as *mut *const TraceStack) = std::ptr::null();
 
@@ -423,12 +839,19 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
     // ```
     //
     // (See https://docs.rs/assert_no_alloc/latest/assert_no_alloc/#advanced-use)
+    //
+    // Running out of stack space unwinds with an AllocationError, which we catch and turn into a
+    // %meme bail. The stack is then cleaned up by exit() as for any other error.
     let nock = assert_no_alloc(|| {
         ensure_alloc_counters(|| {
-            unsafe {
+            catch_alloc(|| unsafe {
                 push_formula(&mut context.stack, formula, true)?;
 
                 loop {
+                    if !context.burn(1) {
+                        break BAIL_FUEL;
+                    }
+
                     let work: NockWork = *context.stack.top();
                     match work {
                         NockWork::Done => {
@@ -463,6 +886,7 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                             stack.preserve(&mut context.warm);
                             stack.preserve(&mut res);
                             stack.frame_pop();
+                            context.mean_slot = stack.local_noun_pointer(0);
 
                             debug_assertions(stack, orig_subject);
                             debug_assertions(stack, res);
@@ -511,6 +935,10 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                                 push_formula(&mut context.stack, vale.formula, false)?;
                             }
                             Todo2::ComputeResult => {
+                                if context.interrupted() {
+                                    break BAIL_INTR;
+                                }
+
                                 let stack = &mut context.stack;
                                 if vale.tail {
                                     stack.pop::<NockWork>();
@@ -526,6 +954,7 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                                     debug_assertions(stack, res);
 
                                     mean_frame_push(stack, 0);
+                                    context.mean_slot = stack.local_noun_pointer(0);
                                     *stack.push() = NockWork::Ret;
                                     push_formula(stack, res, true)?;
                                 }
@@ -673,13 +1102,48 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                                     push_formula(&mut context.stack, kale.core, false)?;
                                 }
                                 Todo9::ComputeResult => {
+                                    if context.interrupted() {
+                                        break BAIL_INTR;
+                                    }
+
                                     if let Ok(mut formula) = res.slot_atom(kale.axis) {
                                         if !cfg!(feature = "sham_hints") {
-                                            if let Some((jet, _path)) = context.warm.find_jet(
+                                            if let Some((jet, path)) = context.warm.find_jet(
                                                 &mut context.stack, &mut res, &mut formula,
                                             ) {
-                                                match jet(context, res) {
+                                                if !context.burn_jet(res) {
+                                                    break BAIL_FUEL;
+                                                }
+                                                context.note_jet(path);
+                                                let profiled = open_jet(context, path);
+                                                let jet_res = jet(context, res);
+                                                if let Some((parent, start, allocated)) = profiled {
+                                                    // A punted jet is profiled again as the Nock
+                                                    // which replaces it
+                                                    if let Some(ref mut profile) = context.profile {
+                                                        profile.leave(
+                                                            &context.stack, parent, start,
+                                                            allocated,
+                                                        );
+                                                    }
+                                                }
+                                                match jet_res {
                                                     Ok(jet_res) => {
+                                                        if context.interrupted() {
+                                                            break BAIL_INTR;
+                                                        }
+                                                        let jet_res =
+                                                            if context.test_jets.tests_path(path) {
+                                                                match test_jet(
+                                                                    context, path, res, formula,
+                                                                    jet_res,
+                                                                ) {
+                                                                    Ok(nock_res) => nock_res,
+                                                                    Err(err) => break Err(err),
+                                                                }
+                                                            } else {
+                                                                jet_res
+                                                            };
                                                         res = jet_res;
                                                         context.stack.pop::<NockWork>();
                                                         continue;
@@ -699,16 +1163,17 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                                             // We could trace on 2 as well, but 2 only comes from Hoon via
                                             // '.*', so we can assume it's never directly used to invoke
                                             // jetted code.
-                                            if context.trace_info.is_some() {
-                                                if let Some(path) =
-                                                    context.cold.matches(stack, &mut res)
+                                            if context.traces(TraceCategory::Jet) {
+                                                if let Some(path) = context
+                                                    .cold
+                                                    .matches(&mut context.stack, &mut res)
                                                 {
-                                                    append_trace(stack, path);
+                                                    append_trace(context, path, TraceCategory::Jet);
                                                 };
                                             };
 
                                             subject = res;
-                                            push_formula(stack, formula, true)?;
+                                            push_formula(&mut context.stack, formula, true)?;
                                         } else {
                                             kale.todo = Todo9::RestoreSubject;
                                             kale.core = subject;
@@ -720,17 +1185,19 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
 
                                             subject = res;
                                             mean_frame_push(stack, 0);
+                                            context.mean_slot = stack.local_noun_pointer(0);
                                             *stack.push() = NockWork::Ret;
                                             push_formula(stack, formula, true)?;
 
                                             // We could trace on 2 as well, but 2 only comes from Hoon via
                                             // '.*', so we can assume it's never directly used to invoke
                                             // jetted code.
-                                            if context.trace_info.is_some() {
-                                                if let Some(path) =
-                                                    context.cold.matches(stack, &mut res)
+                                            if context.traces(TraceCategory::Jet) {
+                                                if let Some(path) = context
+                                                    .cold
+                                                    .matches(&mut context.stack, &mut res)
                                                 {
-                                                    append_trace(stack, path);
+                                                    append_trace(context, path, TraceCategory::Jet);
                                                 };
                                             };
                                         }
@@ -889,67 +1356,78 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                                     scry.path = res;
                                     let scry_stack = context.scry_stack;
                                     let scry_handler = cell.head();
-                                    let scry_gate = scry_handler.as_cell()?;
-                                    let payload = T(&mut context.stack, &[scry.reff, res]);
-                                    let scry_core = T(
-                                        &mut context.stack,
-                                        &[
-                                            scry_gate.head(),
-                                            payload,
-                                            scry_gate.tail().as_cell()?.tail(),
-                                        ],
-                                    );
-                                    let scry_form =
-                                        T(&mut context.stack, &[D(9), D(2), D(1), scry_core]);
-
                                     context.scry_stack = cell.tail();
-                                    // Alternately, we could use scry_core as the subject and [9 2 0 1] as
-                                    // the formula. It's unclear if performance will be better with a purely
-                                    // static formula.
-                                    match interpret(context, D(0), scry_form) {
-                                        Ok(noun) => match noun.as_either_atom_cell() {
-                                            Left(atom) => {
-                                                if atom.as_noun().raw_equals(D(0)) {
-                                                    break Err(Error::ScryBlocked(scry.path));
-                                                } else {
-                                                    break Err(Error::ScryCrashed(D(0)));
-                                                }
-                                            }
-                                            Right(cell) => {
-                                                match cell.tail().as_either_atom_cell() {
-                                                    Left(_) => {
-                                                        let stack = &mut context.stack;
-                                                        let hunk = T(
-                                                            stack,
-                                                            &[
-                                                                D(tas!(b"hunk")),
-                                                                scry.reff,
-                                                                scry.path,
-                                                            ],
-                                                        );
-                                                        mean_push(stack, hunk);
+
+                                    let answer = if let Ok(handle) = scry_handler.as_atom() {
+                                        match native_scry(context, handle, scry.reff, scry.path) {
+                                            Some(answer) => answer,
+                                            None => break BAIL_EXIT,
+                                        }
+                                    } else {
+                                        let scry_gate = scry_handler.as_cell()?;
+                                        let payload = T(&mut context.stack, &[scry.reff, res]);
+                                        let scry_core = T(
+                                            &mut context.stack,
+                                            &[
+                                                scry_gate.head(),
+                                                payload,
+                                                scry_gate.tail().as_cell()?.tail(),
+                                            ],
+                                        );
+                                        let scry_form =
+                                            T(&mut context.stack, &[D(9), D(2), D(1), scry_core]);
+
+                                        // Alternately, we could use scry_core as the subject and [9 2 0 1] as
+                                        // the formula. It's unclear if performance will be better with a purely
+                                        // static formula.
+                                        match interpret(context, D(0), scry_form) {
+                                            Ok(noun) => match noun.as_either_atom_cell() {
+                                                Left(atom) => {
+                                                    if atom.as_noun().raw_equals(D(0)) {
+                                                        ScryResult::NotFound
+                                                    } else {
                                                         break Err(Error::ScryCrashed(D(0)));
                                                     }
-                                                    Right(cell) => {
-                                                        res = cell.tail();
-                                                        context.scry_stack = scry_stack;
-                                                        context.stack.pop::<NockWork>();
-                                                    }
                                                 }
-                                            }
-                                        },
-                                        Err(error) => match error {
-                                            Error::Deterministic(_, trace)
-                                            | Error::ScryCrashed(trace) => {
-                                                break Err(Error::ScryCrashed(trace));
-                                            }
-                                            Error::NonDeterministic(_, _) => {
-                                                break Err(error);
-                                            }
-                                            Error::ScryBlocked(_) => {
-                                                break BAIL_FAIL;
-                                            }
-                                        },
+                                                Right(cell) => match cell
+                                                    .tail()
+                                                    .as_either_atom_cell()
+                                                {
+                                                    Left(_) => ScryResult::Crash,
+                                                    Right(cell) => ScryResult::Found(cell.tail()),
+                                                },
+                                            },
+                                            Err(error) => match error {
+                                                Error::Deterministic(_, trace)
+                                                | Error::ScryCrashed(trace) => {
+                                                    break Err(Error::ScryCrashed(trace));
+                                                }
+                                                Error::NonDeterministic(_, _) => {
+                                                    break Err(error);
+                                                }
+                                                Error::ScryBlocked(_) => {
+                                                    break BAIL_FAIL;
+                                                }
+                                            },
+                                        }
+                                    };
+
+                                    match answer {
+                                        ScryResult::Found(noun) => {
+                                            res = noun;
+                                            context.scry_stack = scry_stack;
+                                            context.stack.pop::<NockWork>();
+                                        }
+                                        ScryResult::NotFound => {
+                                            break Err(Error::ScryBlocked(scry.path));
+                                        }
+                                        ScryResult::Crash => {
+                                            let stack = &mut context.stack;
+                                            let hunk =
+                                                T(stack, &[D(tas!(b"hunk")), scry.reff, scry.path]);
+                                            mean_push(stack, hunk);
+                                            break Err(Error::ScryCrashed(D(0)));
+                                        }
                                     }
                                 } else {
                                     // No scry handler
@@ -959,10 +1437,26 @@ pub fn interpret(context: &mut Context, mut subject: Noun, formula: Noun) -> Res
                         },
                     };
                 }
-            }
+            })
+            .unwrap_or_else(|payload| {
+                if payload.is::<AllocationError>() {
+                    permit_alloc(|| drop(payload));
+                    BAIL_MEME
+                } else {
+                    resume_unwind(payload)
+                }
+            })
         })
     });
 
+    // Back to the enclosing call's mean stack
+    context.mean_slot = road.mean;
+    context.mean_road = road.outer;
+    // Trace entries in frames popped by an error are never closed
+    if let Some(ref mut profile) = context.profile {
+        profile.reset(profile_node);
+    }
+
     match nock {
         Ok(res) => Ok(res),
         Err(err) => Err(exit(context, &snapshot, virtual_frame, err)),
@@ -1197,27 +1691,46 @@ fn exit(
         }
 
         let stack = &mut context.stack;
-        let mut preserve = match error {
-            Error::ScryBlocked(path) => path,
-            Error::Deterministic(_, t) | Error::NonDeterministic(_, t) | Error::ScryCrashed(t) => {
-                // Return $tang of traces
-                let h = *(stack.local_noun_pointer(0));
-                // XX: Small chance of clobbering something important after OOM?
-                // XX: what if we OOM while making a stack trace
-                T(stack, &[h, t])
+        let preserved = catch_alloc(|| {
+            let mut preserve = match error {
+                Error::ScryBlocked(path) => path,
+                Error::Deterministic(_, t)
+                | Error::NonDeterministic(_, t)
+                | Error::ScryCrashed(t) => {
+                    // Return $tang of traces
+                    let h = *(stack.local_noun_pointer(0));
+                    T(stack, &[h, t])
+                }
+            };
+
+            while stack.get_frame_pointer() != virtual_frame {
+                stack.preserve(&mut preserve);
+                stack.frame_pop();
             }
-        };
 
-        while stack.get_frame_pointer() != virtual_frame {
-            stack.preserve(&mut preserve);
-            stack.frame_pop();
-        }
+            preserve
+        });
 
-        match error {
-            Error::Deterministic(mote, _) => Error::Deterministic(mote, preserve),
-            Error::NonDeterministic(mote, _) => Error::NonDeterministic(mote, preserve),
-            Error::ScryCrashed(_) => Error::ScryCrashed(preserve),
-            Error::ScryBlocked(_) => error,
+        match preserved {
+            Ok(preserve) => match error {
+                Error::Deterministic(mote, _) => Error::Deterministic(mote, preserve),
+                Error::NonDeterministic(mote, _) => Error::NonDeterministic(mote, preserve),
+                Error::ScryCrashed(_) => Error::ScryCrashed(preserve),
+                Error::ScryBlocked(_) => error,
+            },
+            Err(payload) => {
+                if !payload.is::<AllocationError>() {
+                    resume_unwind(payload);
+                }
+                // Out of memory while saving the trace: give up on it and finish popping
+                if stack.copying() {
+                    stack.frame_pop();
+                }
+                while stack.get_frame_pointer() != virtual_frame {
+                    stack.frame_pop();
+                }
+                Error::NonDeterministic(Mote::Meme, D(0))
+            }
         }
     }
 }
@@ -1233,6 +1746,75 @@ fn mean_frame_push(stack: &mut NockStack, slots: usize) {
     }
 }
 
+/** Run the raw Nock a jet replaced, and slog the jet path, sample, and both results if they differ
+ *
+ * Produces the Nock result, so that a broken jet can't change the outcome of the computation.
+ */
+fn test_jet(
+    context: &mut Context,
+    path: Noun,
+    subject: Noun,
+    formula: Noun,
+    mut jet_res: Noun,
+) -> Result {
+    let sample = subject.slot(6).unwrap_or(D(0));
+    let nock_res = interpret(context, subject, formula);
+    let text = match nock_res {
+        Ok(mut nock_res) => {
+            if unsafe { unifying_equality(&mut context.stack, &mut nock_res, &mut jet_res) } {
+                return Ok(nock_res);
+            }
+            permit_alloc(|| {
+                format!(
+                    "jet mismatch in {}: sample {}, nock {}, jet {}",
+                    path, sample, nock_res, jet_res
+                )
+            })
+        }
+        Err(ref err) => permit_alloc(|| {
+            format!(
+                "jet mismatch in {}: sample {}, nock crashed ({:?}), jet {}",
+                path, sample, err, jet_res
+            )
+        }),
+    };
+
+    let stack = &mut context.stack;
+    let leaf = permit_alloc(|| tape(stack, &text));
+    let tank = T(stack, &[D(tas!(b"leaf")), leaf]);
+    context.slogger.slog(stack, 0, tank);
+    nock_res
+}
+
+/** Concatenate the mean stacks of the current interpret call and every enclosing one.
+ */
+unsafe fn mean_to_root(context: &mut Context) -> Noun {
+    let mut res = D(0);
+    let mut dest = &mut res as *mut Noun;
+
+    let mut slot = context.mean_slot;
+    let mut road = context.mean_road;
+    while !slot.is_null() {
+        let mut list = *slot;
+        while let Ok(cell) = list.as_cell() {
+            let (new_cell, new_memory) = Cell::new_raw_mut(&mut context.stack);
+            (*new_memory).head = cell.head();
+            *dest = new_cell.as_noun();
+            dest = &mut (*new_memory).tail;
+            list = cell.tail();
+        }
+
+        if road.is_null() {
+            break;
+        }
+        slot = (*road).mean;
+        road = (*road).outer;
+    }
+    *dest = D(0);
+
+    res
+}
+
 /** Push onto the mean stack.
  */
 fn mean_push(stack: &mut NockStack, noun: Noun) {
@@ -1324,22 +1906,60 @@ pub fn inc(stack: &mut NockStack, atom: Atom) -> Atom {
     }
 }
 
+/** Ask the native scry handler registered under `handle`. None if there isn't one. */
+fn native_scry(context: &mut Context, handle: Atom, reff: Noun, path: Noun) -> Option<ScryResult> {
+    let handler = context
+        .scry_handlers
+        .get_mut(handle.as_direct().ok()?.data() as usize)?;
+    let stack = &mut context.stack;
+    // Host code may well use the Rust heap
+    Some(permit_alloc(|| handler.scry(stack, reff, path)))
+}
+
+/// Enter a jet's node in the profile, if profiling, and return what is needed to leave it
+fn open_jet(context: &mut Context, path: Noun) -> Option<(ProfileNode, Instant, u64)> {
+    let stack = &mut context.stack;
+    let profile = context.profile.as_mut()?;
+    let parent = permit_alloc(|| {
+        let name = trace_name(stack, path, TraceCategory::Jet);
+        profile.open(&name)
+    });
+    Some((parent, Instant::now(), stack.words_allocated()))
+}
+
 /// Push onto the tracing stack
-fn append_trace(stack: &mut NockStack, path: Noun) {
+fn append_trace(context: &mut Context, path: Noun, category: TraceCategory) {
+    let stack = &mut context.stack;
+    let profile_parent = match context.profile {
+        // The profile's node table lives on the Rust heap
+        Some(ref mut profile) => permit_alloc(|| {
+            let name = trace_name(stack, path, category);
+            profile.open(&name)
+        }),
+        None => ROOT,
+    };
+
     unsafe {
         let trace_stack = *(stack.local_noun_pointer(1) as *const *const TraceStack);
         let new_trace_entry = stack.struct_alloc(1);
         *new_trace_entry = TraceStack {
             path,
             start: Instant::now(),
+            category,
+            allocated: stack.words_allocated(),
+            profile_parent,
             next: trace_stack,
         };
         *(stack.local_noun_pointer(1) as *mut *const TraceStack) = new_trace_entry;
     }
 }
 
-/// Write fast-hinted traces to trace file
+/// Write fast-hinted traces to trace file, and count them in the profile
 unsafe fn write_trace(context: &mut Context) {
+    if let Some(ref mut profile) = context.profile {
+        let trace_stack = *(context.stack.local_noun_pointer(1) as *mut *const TraceStack);
+        profile.close(&context.stack, trace_stack);
+    }
     if let Some(ref mut info) = &mut context.trace_info {
         let trace_stack = *(context.stack.local_noun_pointer(1) as *mut *const TraceStack);
         // Abort writing to trace file if we encountered an error. This should
@@ -1351,13 +1971,30 @@ unsafe fn write_trace(context: &mut Context) {
     }
 }
 
+/// Pop the trace entry for a %spot once its formula completes, so that it doesn't enclose the
+/// %spots which follow it in the same frame
+unsafe fn close_spot(context: &mut Context, clue: Noun) {
+    let trace_slot = context.stack.local_noun_pointer(1) as *mut *mut TraceStack;
+    let entry = *trace_slot;
+    if entry.is_null()
+        || !matches!((*entry).category, TraceCategory::Spot)
+        || !(*entry).path.raw_equals(clue)
+    {
+        return;
+    }
+
+    let next = (*entry).next;
+    (*entry).next = std::ptr::null();
+    write_trace(context);
+    *trace_slot = next as *mut TraceStack;
+}
+
 mod hint {
     use super::*;
     use crate::jets;
     use crate::jets::cold;
     use crate::jets::nock::util::{mook, LEAF};
     use crate::noun::{tape, Atom, Cell, Noun, D, T};
-    use crate::unifying_equality::unifying_equality;
     use sword_macros::tas;
 
     pub fn is_tail(tag: Atom) -> bool {
@@ -1390,45 +2027,15 @@ mod hint {
                     let jet_name = jet_formula.tail();
 
                     if let Some(jet) = jets::get_jet(context, jet_name) {
+                        if !context.burn_jet(subject) {
+                            return Some(BAIL_FUEL);
+                        }
+                        context.note_jet(jet_name);
                         match jet(context, subject) {
-                            Ok(mut jet_res) => {
-                                //  XX: simplify this by moving jet test mode into the 11 code in interpret, or into its own function?
+                            Ok(jet_res) => {
                                 // if in test mode, check that the jet returns the same result as the raw nock
-                                if jets::get_jet_test_mode(jet_name) {
-                                    //  XX: we throw away trace, which might matter for non-deterministic errors
-                                    //      maybe mook and slog it?
-                                    match interpret(context, subject, body) {
-                                        Ok(mut nock_res) => {
-                                            let stack = &mut context.stack;
-                                            if unsafe {
-                                                !unifying_equality(
-                                                    stack, &mut nock_res, &mut jet_res,
-                                                )
-                                            } {
-                                                //  XX: need NockStack allocated string interpolation
-                                                // let tape = tape(stack, "jet mismatch in {}, raw: {}, jetted: {}", jet_name, nock_res, jet_res);
-                                                // let mean = T(stack, &[D(tas!(b"mean")), tape]);
-                                                // mean_push(stack, mean);
-                                                Some(BAIL_EXIT)
-                                            } else {
-                                                Some(Ok(nock_res))
-                                            }
-                                        }
-                                        Err(error) => {
-                                            //  XX: need NockStack allocated string interpolation
-                                            // let stack = &mut context.stack;
-                                            // let tape = tape(stack, "jet mismatch in {}, raw: {}, jetted: {}", jet_name, err, jet_res);
-                                            // let mean = T(stack, &[D(tas!(b"mean")), tape]);
-                                            // mean_push(stack, mean);
-
-                                            match error {
-                                                Error::NonDeterministic(mote, _) => {
-                                                    Some(Err(Error::NonDeterministic(mote, D(0))))
-                                                }
-                                                _ => Some(BAIL_EXIT),
-                                            }
-                                        }
-                                    }
+                                if context.test_jets.tests_name(jet_name) {
+                                    Some(test_jet(context, jet_name, subject, body, jet_res))
                                 } else {
                                     Some(Ok(jet_res))
                                 }
@@ -1489,20 +2096,33 @@ mod hint {
                 s.slog(stack, pri, tank);
                 None
             }
-            tas!(b"hand") | tas!(b"hunk") | tas!(b"lose") | tas!(b"mean") | tas!(b"spot") => {
+            tas!(b"hand") | tas!(b"hunk") | tas!(b"lose") | tas!(b"mean") => {
                 let stack = &mut context.stack;
                 let (_form, clue) = hint?;
                 let noun = T(stack, &[tag.as_noun(), clue]);
                 mean_push(stack, noun);
                 None
             }
-            tas!(b"hela") => {
-                //  XX: This only prints the trace down to the bottom of THIS
-                //      interpret call, making this neither a %nara nor a %hela
-                //      hint, as Vere understands them. We'll need to
-                //      recursively work down frames to get the stack trace all
-                //      the way to the root.
-                let mean = unsafe { *(context.stack.local_noun_pointer(0)) };
+            tas!(b"spot") => {
+                let (_form, clue) = hint?;
+                let noun = T(&mut context.stack, &[tag.as_noun(), clue]);
+                mean_push(&mut context.stack, noun);
+                if context.traces(TraceCategory::Spot) {
+                    // Traced until the hinted formula completes, or in tail position until the
+                    // enclosing frame returns
+                    append_trace(context, clue, TraceCategory::Spot);
+                }
+                None
+            }
+            tas!(b"hela") | tas!(b"nara") => {
+                // %nara prints the trace of this interpret call, %hela of every enclosing one
+                let mean = unsafe {
+                    if tag.direct()?.data() == tas!(b"hela") {
+                        mean_to_root(context)
+                    } else {
+                        *(context.stack.local_noun_pointer(0))
+                    }
+                };
                 let tone = Cell::new(&mut context.stack, D(2), mean);
 
                 match mook(context, tone, true) {
@@ -1563,10 +2183,14 @@ mod hint {
         match tag.direct()?.data() {
             tas!(b"memo") => {
                 let mut key = Cell::new(stack, subject, body).as_noun();
-                context.cache = cache.insert(stack, &mut key, res);
+                cache.insert(stack, &mut key, res);
+            }
+            tas!(b"hand") | tas!(b"hunk") | tas!(b"lose") | tas!(b"mean") => {
+                mean_pop(stack);
             }
-            tas!(b"hand") | tas!(b"hunk") | tas!(b"lose") | tas!(b"mean") | tas!(b"spot") => {
+            tas!(b"spot") => {
                 mean_pop(stack);
+                unsafe { close_spot(context, hint?) };
             }
             tas!(b"fast") => {
                 if !cfg!(feature = "sham_hints") {
@@ -1681,3 +2305,222 @@ mod debug {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::assert_noun_eq;
+    use crate::noun::tape;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    fn small_context() -> Context {
+        ContextBuilder::new()
+            .stack_size(1 << 16)
+            .slogger(StderrSlogger)
+            .build()
+            .expect("could not build test context")
+    }
+
+    #[test]
+    fn test_stack_exhaustion_is_meme() {
+        let mut c = small_context();
+
+        // [[0 1] [2 [0 1] [0 1]]] run against itself recurses forever, never in tail position
+        let axis = T(&mut c.stack, &[D(0), D(1)]);
+        let rec = T(&mut c.stack, &[D(2), axis, axis]);
+        let bomb = T(&mut c.stack, &[axis, rec]);
+
+        let frame = c.stack.get_frame_pointer();
+        match interpret(&mut c, bomb, bomb) {
+            Err(Error::NonDeterministic(Mote::Meme, _)) => {}
+            Err(err) => panic!("expected %meme, got {:?}", err),
+            Ok(res) => panic!("expected %meme, got {}", res),
+        }
+        assert_eq!(frame, c.stack.get_frame_pointer());
+
+        // The stack is still usable afterwards
+        let subject = T(&mut c.stack, &[D(3), D(4)]);
+        let formula = T(&mut c.stack, &[D(0), D(3)]);
+        let res = interpret(&mut c, subject, formula).expect("interpret failed after %meme");
+        assert_noun_eq(&mut c.stack, res, D(4));
+    }
+
+    #[test]
+    fn test_interrupt_is_intr() {
+        let mut c = small_context();
+
+        // [2 [0 1] [0 1]] run against itself loops forever in tail position
+        let axis = T(&mut c.stack, &[D(0), D(1)]);
+        let spin = T(&mut c.stack, &[D(2), axis, axis]);
+
+        c.interrupt.store(true, Ordering::Relaxed);
+        match interpret(&mut c, spin, spin) {
+            Err(Error::NonDeterministic(Mote::Intr, _)) => {}
+            Err(err) => panic!("expected %intr, got {:?}", err),
+            Ok(res) => panic!("expected %intr, got {}", res),
+        }
+        assert!(!c.interrupt.load(Ordering::Relaxed));
+
+        let _deadline = interrupt_at(&c.interrupt, Instant::now() + Duration::from_millis(50));
+        match interpret(&mut c, spin, spin) {
+            Err(Error::NonDeterministic(Mote::Intr, _)) => {}
+            Err(err) => panic!("expected %intr, got {:?}", err),
+            Ok(res) => panic!("expected %intr, got {}", res),
+        }
+    }
+
+    struct CaptureSlogger(Rc<RefCell<Vec<String>>>);
+
+    impl Slogger for CaptureSlogger {
+        fn slog(&mut self, _stack: &mut NockStack, _pri: u64, tank: Noun) {
+            // The tank may live in a frame that is about to be popped, so render it now
+            permit_alloc(|| self.0.borrow_mut().push(format!("{}", tank)));
+        }
+
+        fn flog(&mut self, _stack: &mut NockStack, _cord: Noun) {}
+    }
+
+    /** Slog a trace with `tag` from inside a scry handler, and check which frames were printed */
+    fn trace_through_scry(tag: u64, expected: &[&str]) {
+        let tanks = Rc::new(RefCell::new(Vec::new()));
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .slogger(CaptureSlogger(tanks.clone()))
+            .build()
+            .expect("could not build test context");
+        let stack = &mut c.stack;
+
+        // The scry handler runs in a nested interpret call:
+        // [11 [%mean 1 'inner'] [11 [tag 1 0] [1 0 0 42]]]
+        let tag = D(tag);
+        let zero = T(stack, &[D(1), D(0)]);
+        let unit = T(stack, &[D(1), D(0), D(0), D(42)]);
+        let dump = T(stack, &[tag, zero]);
+        let dump = T(stack, &[D(11), dump, unit]);
+        let inner = T(stack, &[D(1), D(tas!(b"inner"))]);
+        let arm = T(stack, &[D(tas!(b"mean")), inner]);
+        let arm = T(stack, &[D(11), arm, dump]);
+        let gate = T(stack, &[arm, D(0), D(0), D(0)]);
+        c.scry_stack = T(stack, &[gate, D(0)]);
+
+        // [11 [%mean 1 'outer'] [12 [1 0] [1 0]]]
+        let outer = T(stack, &[D(1), D(tas!(b"outer"))]);
+        let scry = T(stack, &[D(12), zero, zero]);
+        let formula = T(stack, &[D(tas!(b"mean")), outer]);
+        let formula = T(stack, &[D(11), formula, scry]);
+
+        let res = interpret(&mut c, D(0), formula).expect("scry failed");
+        assert_noun_eq(&mut c.stack, res, D(42));
+
+        let tanks = tanks.borrow();
+        assert_eq!(tanks.len(), expected.len());
+        for (tank, text) in tanks.iter().zip(expected) {
+            let leaf = tape(&mut c.stack, text);
+            let leaf = T(&mut c.stack, &[D(tas!(b"leaf")), leaf]);
+            assert_eq!(*tank, format!("{}", leaf));
+        }
+    }
+
+    #[test]
+    fn test_hela_crosses_interpret_calls() {
+        trace_through_scry(tas!(b"hela"), &["outer", "inner"]);
+        trace_through_scry(tas!(b"nara"), &["inner"]);
+    }
+
+    #[test]
+    fn test_memo_hint_uses_cache() {
+        let mut c = small_context();
+        // [11 [%memo 1 0] [4 0 1]]
+        let clue = T(&mut c.stack, &[D(tas!(b"memo")), D(1), D(0)]);
+        let body = T(&mut c.stack, &[D(4), D(0), D(1)]);
+        let formula = T(&mut c.stack, &[D(11), clue, body]);
+
+        for _ in 0..2 {
+            let res = interpret(&mut c, D(41), formula).unwrap();
+            assert_noun_eq(&mut c.stack, res, D(42));
+        }
+        let stats = c.cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    }
+
+    /** Squares of small atoms, nothing yet at 10, and a crash above that */
+    struct Squares;
+
+    impl ScryHandler for Squares {
+        fn scry(&mut self, _stack: &mut NockStack, _reff: Noun, path: Noun) -> ScryResult {
+            match path.as_direct().map(|a| a.data()) {
+                Ok(n) if n < 10 => ScryResult::Found(D(n * n)),
+                Ok(10) => ScryResult::NotFound,
+                _ => ScryResult::Crash,
+            }
+        }
+    }
+
+    #[test]
+    fn test_native_scry_handler() {
+        let mut c = small_context();
+        let squares = c.scry_handler(Squares);
+
+        for (path, expected) in [(7, Ok(49)), (10, Err(true)), (11, Err(false))] {
+            // A failed scry leaves its handler popped, as with scry gates
+            c.scry_stack = T(&mut c.stack, &[squares, D(0)]);
+            let reff = T(&mut c.stack, &[D(1), D(0)]);
+            let path = T(&mut c.stack, &[D(1), D(path)]);
+            let formula = T(&mut c.stack, &[D(12), reff, path]);
+            match (interpret(&mut c, D(0), formula), expected) {
+                (Ok(res), Ok(n)) => assert_noun_eq(&mut c.stack, res, D(n)),
+                (Err(Error::ScryBlocked(_)), Err(true)) => {}
+                (Err(Error::ScryCrashed(_)), Err(false)) => {}
+                (res, _) => panic!("unexpected scry result for {}: {:?}", path, res.err()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_jet_mismatch_is_slogged() {
+        let tanks = Rc::new(RefCell::new(Vec::new()));
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .slogger(CaptureSlogger(tanks.clone()))
+            .build()
+            .expect("could not build test context");
+
+        // A "core" whose formula [4 0 6] increments the sample
+        let formula = T(&mut c.stack, &[D(4), D(0), D(6)]);
+        let core = T(&mut c.stack, &[formula, D(41), D(0)]);
+        let path = T(&mut c.stack, &[D(tas!(b"inc")), D(0)]);
+
+        let res = test_jet(&mut c, path, core, formula, D(42)).unwrap();
+        assert_noun_eq(&mut c.stack, res, D(42));
+        assert!(tanks.borrow().is_empty());
+
+        let res = test_jet(&mut c, path, core, formula, D(43)).unwrap();
+        assert_noun_eq(&mut c.stack, res, D(42));
+        let tanks = tanks.borrow();
+        assert_eq!(tanks.len(), 1);
+        assert!(tanks[0].starts_with("[%leaf"));
+    }
+
+    #[test]
+    fn test_note_jet_names() {
+        let mut c = small_context();
+        c.fired_jets = Some(BTreeSet::new());
+
+        // A %sham jet is named by an atom, a cold state jet by its path
+        c.note_jet(D(tas!(b"add")));
+        let k = T(&mut c.stack, &[D(tas!(b"k")), D(139)]);
+        let path = T(&mut c.stack, &[D(tas!(b"add")), D(tas!(b"one")), k, D(0)]);
+        c.note_jet(path);
+
+        let fired: Vec<&str> = c
+            .fired_jets
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(fired, ["/add/one/k139", "add"]);
+    }
+}
@@ -3,6 +3,7 @@ pub mod hot;
 pub mod warm;
 
 pub mod bits;
+pub mod float;
 pub mod form;
 pub mod hash;
 pub mod list;
@@ -10,18 +11,19 @@ pub mod lock;
 pub mod lute;
 pub mod math;
 pub mod nock;
+pub mod ob;
 pub mod parse;
 pub mod serial;
 pub mod sort;
+pub mod treap;
 pub mod tree;
 
 use crate::flog;
 use crate::interpreter::{Context, Error, Mote};
 use crate::jets::bits::*;
-use crate::jets::cold::Cold;
+use crate::jets::float::*;
 use crate::jets::form::*;
 use crate::jets::hash::*;
-use crate::jets::hot::{Hot, URBIT_HOT_STATE};
 use crate::jets::list::*;
 use crate::jets::lock::aes::*;
 use crate::jets::lock::ed::*;
@@ -29,14 +31,17 @@ use crate::jets::lock::sha::*;
 use crate::jets::lute::*;
 use crate::jets::math::*;
 use crate::jets::nock::*;
+use crate::jets::ob::*;
 use crate::jets::parse::*;
 use crate::jets::serial::*;
 use crate::jets::sort::*;
 
+use crate::jets::hot::HotPath;
+use crate::jets::treap::*;
 use crate::jets::tree::*;
-use crate::jets::warm::Warm;
 use crate::mem::{NockStack, Preserve};
-use crate::noun::{self, Noun, Slots, D};
+use crate::noun::{self, Atom, Noun, Slots, D};
+use either::Either::{self, Left, Right};
 use sword_macros::tas;
 
 crate::gdb!();
@@ -150,6 +155,7 @@ pub fn get_jet(context: &mut Context, jet_name: Noun) -> Option<Jet> {
         tas!(b"sha1") => Some(jet_sha1),
         //
         tas!(b"scow") => Some(jet_scow),
+        tas!(b"slaw") => Some(jet_slaw),
         //
         tas!(b"mink") => Some(jet_mink),
         //
@@ -172,14 +178,71 @@ pub fn get_jet(context: &mut Context, jet_name: Noun) -> Option<Jet> {
     }
 }
 
-pub fn get_jet_test_mode(_jet_name: Noun) -> bool {
-    /*
-    match jet_name.as_direct().unwrap().data() {
-        tas!(b"cut") => true,
-        _ => false,
+/** Jets to check against the raw Nock of their formulas as they run (see [Context::test_jets])
+ *
+ * Paths are given in the same form as [hot::HotEntry] paths.
+ */
+#[derive(Clone, Copy, Default)]
+pub enum JetTestMode {
+    #[default]
+    Off,
+    All,
+    Paths(&'static [HotPath]),
+}
+
+impl JetTestMode {
+    /** Whether to test the jet registered at this cold state path (innermost first) */
+    pub fn tests_path(&self, path: Noun) -> bool {
+        match self {
+            JetTestMode::Off => false,
+            JetTestMode::All => true,
+            JetTestMode::Paths(htaps) => htaps.iter().any(|htap| path_matches(path, htap)),
+        }
+    }
+
+    /** Whether to test the %sham jet with this name */
+    pub fn tests_name(&self, name: Noun) -> bool {
+        match self {
+            JetTestMode::Off => false,
+            JetTestMode::All => true,
+            JetTestMode::Paths(htaps) => htaps.iter().any(|htap| match htap.last() {
+                Some(Left(tas)) => name.atom().is_some_and(|name| atom_is_bytes(name, tas)),
+                _ => false,
+            }),
+        }
+    }
+}
+
+fn path_matches(mut path: Noun, htap: &[Either<&[u8], (u64, u64)>]) -> bool {
+    for chum in htap.iter().rev() {
+        let cell = match path.cell() {
+            Some(cell) => cell,
+            None => return false,
+        };
+        let matches = match chum {
+            Left(tas) => cell
+                .head()
+                .atom()
+                .is_some_and(|atom| atom_is_bytes(atom, tas)),
+            Right((tas, ver)) => cell.head().cell().is_some_and(|chum| unsafe {
+                chum.head().raw_equals(D(*tas)) && chum.tail().raw_equals(D(*ver))
+            }),
+        };
+        if !matches {
+            return false;
+        }
+        path = cell.tail();
     }
-    */
-    false
+    unsafe { path.raw_equals(D(0)) }
+}
+
+fn atom_is_bytes(atom: Atom, bytes: &[u8]) -> bool {
+    let atom_bytes = atom.as_bytes();
+    let len = atom_bytes
+        .iter()
+        .rposition(|b| *b != 0)
+        .map_or(0, |i| i + 1);
+    atom_bytes[..len] == *bytes
 }
 
 pub mod util {
@@ -302,8 +365,7 @@ pub mod util {
 
     pub mod test {
         use super::*;
-        use crate::hamt::Hamt;
-        use crate::interpreter::Slogger;
+        use crate::interpreter::{ContextBuilder, Slogger};
         use crate::mem::NockStack;
         use crate::noun::{Atom, Noun, D, T};
         use crate::unifying_equality::unifying_equality;
@@ -323,23 +385,39 @@ pub mod util {
         }
 
         pub fn init_context() -> Context {
-            let mut stack = NockStack::new(8 << 10 << 10, 0);
-            let cold = Cold::new(&mut stack);
-            let warm = Warm::new(&mut stack);
-            let hot = Hot::init(&mut stack, URBIT_HOT_STATE);
-            let cache = Hamt::<Noun>::new(&mut stack);
-            let slogger = std::boxed::Box::pin(TestSlogger {});
-
-            Context {
-                stack,
-                slogger,
-                cold,
-                warm,
-                hot,
-                cache,
-                scry_stack: D(0),
-                trace_info: None,
-            }
+            ContextBuilder::new()
+                .stack_size(8 << 10 << 10)
+                .slogger(TestSlogger {})
+                .build()
+                .expect("could not build test context")
+        }
+
+        /** Register the %k139 root and a core `chum` under it through %fast hints
+         *
+         * Returns the core, [battery [[1 0] 139]], whose arm runs the jet at [%k139 chum] in
+         * the context's hot state when invoked with [9 2 0 1].
+         */
+        pub fn register_core(context: &mut Context, chum: u64, battery: Noun) -> Noun {
+            let stack = &mut context.stack;
+            let root = T(stack, &[D(tas!(b"k")), D(139)]);
+            let parent = T(stack, &[D(1), D(0)]);
+            let root_clue = T(stack, &[root, parent, D(0)]);
+            let root_clue = T(stack, &[D(1), root_clue]);
+            let root_hint = T(stack, &[D(tas!(b"fast")), root_clue]);
+            let root_battery = T(stack, &[D(1), D(0)]);
+            let root_body = T(stack, &[D(1), root_battery, D(139)]);
+            let root_formula = T(stack, &[D(11), root_hint, root_body]);
+            let root = interpret(context, D(0), root_formula).expect("could not register root");
+
+            let stack = &mut context.stack;
+            let parent = T(stack, &[D(0), D(3)]);
+            let clue = T(stack, &[D(chum), parent, D(0)]);
+            let clue = T(stack, &[D(1), clue]);
+            let hint = T(stack, &[D(tas!(b"fast")), clue]);
+            let core = T(stack, &[battery, root]);
+            let body = T(stack, &[D(1), core]);
+            let formula = T(stack, &[D(11), hint, body]);
+            interpret(context, D(0), formula).expect("could not register core")
         }
 
         #[allow(non_snake_case)]
@@ -470,3 +548,34 @@ pub mod util {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::hot::K_139;
+    use crate::jets::util::test::init_context;
+    use crate::noun::T;
+
+    #[test]
+    fn test_jet_test_mode_paths() {
+        let c = &mut init_context();
+        let stack = &mut c.stack;
+
+        // [%add %one [%k 139] ~]
+        let k = T(stack, &[D(tas!(b"k")), D(139)]);
+        let path = T(stack, &[D(tas!(b"add")), D(tas!(b"one")), k, D(0)]);
+
+        let mode = JetTestMode::Paths(&[&[K_139, Left(b"one"), Left(b"add")]]);
+        assert!(mode.tests_path(path));
+        assert!(mode.tests_name(D(tas!(b"add"))));
+        assert!(!mode.tests_name(D(tas!(b"sub"))));
+
+        let mode = JetTestMode::Paths(&[&[K_139, Left(b"one"), Left(b"sub")]]);
+        assert!(!mode.tests_path(path));
+        let mode = JetTestMode::Paths(&[&[K_139, Left(b"add")]]);
+        assert!(!mode.tests_path(path));
+
+        assert!(JetTestMode::All.tests_path(path));
+        assert!(!JetTestMode::Off.tests_path(path));
+    }
+}
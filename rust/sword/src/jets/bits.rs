@@ -5,8 +5,7 @@ use crate::jets::util::*;
 use crate::jets::Result;
 use crate::noun::{IndirectAtom, Noun, D};
 use std::cmp;
-use std::cmp::max;
-use std::cmp::min;
+use std::cmp::{max, min};
 
 crate::gdb!();
 
@@ -299,8 +298,7 @@ pub mod util {
     use crate::jets::{JetErr, Result};
     use crate::mem::NockStack;
     use crate::noun::{Atom, Cell, DirectAtom, IndirectAtom, Noun, D};
-    use std::cmp;
-    use std::result;
+    use std::{cmp, result};
 
     /// Binary exponent
     pub fn bex(stack: &mut NockStack, arg: usize) -> Atom {
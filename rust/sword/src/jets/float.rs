@@ -0,0 +1,1480 @@
+/** Floating-point jets
+ *
+ * +rh, +rs, +rd and +rq are doors over a rounding mode with arms for IEEE 754 half, single,
+ * double and quad precision. The arithmetic here gives SoftFloat's results bit for bit, with every
+ * NaN result replaced by the canonical quiet NaN, as the hoon does.
+ */
+use crate::interpreter::Context;
+use crate::jets::util::slot;
+use crate::jets::{JetErr, Result};
+use crate::mem::NockStack;
+use crate::noun::{Atom, IndirectAtom, Noun, D, NO, T, YES};
+use bitvec::field::BitField;
+use std::cmp::Ordering;
+use std::result;
+use sword_macros::tas;
+use util::{Format, Mode, DOUBLE, HALF, QUAD, SINGLE};
+
+crate::gdb!();
+
+// +rh
+
+pub fn jet_rh_add(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, HALF, util::add)
+}
+
+pub fn jet_rh_sub(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, HALF, util::sub)
+}
+
+pub fn jet_rh_mul(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, HALF, util::mul)
+}
+
+pub fn jet_rh_div(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, HALF, util::div)
+}
+
+pub fn jet_rh_sqt(context: &mut Context, subject: Noun) -> Result {
+    unary(context, subject, HALF, util::sqt)
+}
+
+pub fn jet_rh_fma(context: &mut Context, subject: Noun) -> Result {
+    fused(context, subject, HALF)
+}
+
+pub fn jet_rh_lth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, HALF, |o| o == Ordering::Less)
+}
+
+pub fn jet_rh_lte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, HALF, |o| o != Ordering::Greater)
+}
+
+pub fn jet_rh_equ(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, HALF, |o| o == Ordering::Equal)
+}
+
+pub fn jet_rh_gte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, HALF, |o| o != Ordering::Less)
+}
+
+pub fn jet_rh_gth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, HALF, |o| o == Ordering::Greater)
+}
+
+pub fn jet_rh_sun(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, HALF, util::sun)
+}
+
+pub fn jet_rh_san(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, HALF, util::san)
+}
+
+pub fn jet_rh_toi(context: &mut Context, subject: Noun) -> Result {
+    to_integer(context, subject, HALF)
+}
+
+// +rs
+
+pub fn jet_rs_add(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, SINGLE, util::add)
+}
+
+pub fn jet_rs_sub(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, SINGLE, util::sub)
+}
+
+pub fn jet_rs_mul(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, SINGLE, util::mul)
+}
+
+pub fn jet_rs_div(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, SINGLE, util::div)
+}
+
+pub fn jet_rs_sqt(context: &mut Context, subject: Noun) -> Result {
+    unary(context, subject, SINGLE, util::sqt)
+}
+
+pub fn jet_rs_fma(context: &mut Context, subject: Noun) -> Result {
+    fused(context, subject, SINGLE)
+}
+
+pub fn jet_rs_lth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, SINGLE, |o| o == Ordering::Less)
+}
+
+pub fn jet_rs_lte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, SINGLE, |o| o != Ordering::Greater)
+}
+
+pub fn jet_rs_equ(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, SINGLE, |o| o == Ordering::Equal)
+}
+
+pub fn jet_rs_gte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, SINGLE, |o| o != Ordering::Less)
+}
+
+pub fn jet_rs_gth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, SINGLE, |o| o == Ordering::Greater)
+}
+
+pub fn jet_rs_sun(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, SINGLE, util::sun)
+}
+
+pub fn jet_rs_san(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, SINGLE, util::san)
+}
+
+pub fn jet_rs_toi(context: &mut Context, subject: Noun) -> Result {
+    to_integer(context, subject, SINGLE)
+}
+
+// +rd
+
+pub fn jet_rd_add(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, DOUBLE, util::add)
+}
+
+pub fn jet_rd_sub(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, DOUBLE, util::sub)
+}
+
+pub fn jet_rd_mul(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, DOUBLE, util::mul)
+}
+
+pub fn jet_rd_div(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, DOUBLE, util::div)
+}
+
+pub fn jet_rd_sqt(context: &mut Context, subject: Noun) -> Result {
+    unary(context, subject, DOUBLE, util::sqt)
+}
+
+pub fn jet_rd_fma(context: &mut Context, subject: Noun) -> Result {
+    fused(context, subject, DOUBLE)
+}
+
+pub fn jet_rd_lth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, DOUBLE, |o| o == Ordering::Less)
+}
+
+pub fn jet_rd_lte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, DOUBLE, |o| o != Ordering::Greater)
+}
+
+pub fn jet_rd_equ(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, DOUBLE, |o| o == Ordering::Equal)
+}
+
+pub fn jet_rd_gte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, DOUBLE, |o| o != Ordering::Less)
+}
+
+pub fn jet_rd_gth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, DOUBLE, |o| o == Ordering::Greater)
+}
+
+pub fn jet_rd_sun(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, DOUBLE, util::sun)
+}
+
+pub fn jet_rd_san(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, DOUBLE, util::san)
+}
+
+pub fn jet_rd_toi(context: &mut Context, subject: Noun) -> Result {
+    to_integer(context, subject, DOUBLE)
+}
+
+// +rq
+
+pub fn jet_rq_add(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, QUAD, util::add)
+}
+
+pub fn jet_rq_sub(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, QUAD, util::sub)
+}
+
+pub fn jet_rq_mul(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, QUAD, util::mul)
+}
+
+pub fn jet_rq_div(context: &mut Context, subject: Noun) -> Result {
+    binary(context, subject, QUAD, util::div)
+}
+
+pub fn jet_rq_sqt(context: &mut Context, subject: Noun) -> Result {
+    unary(context, subject, QUAD, util::sqt)
+}
+
+pub fn jet_rq_fma(context: &mut Context, subject: Noun) -> Result {
+    fused(context, subject, QUAD)
+}
+
+pub fn jet_rq_lth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, QUAD, |o| o == Ordering::Less)
+}
+
+pub fn jet_rq_lte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, QUAD, |o| o != Ordering::Greater)
+}
+
+pub fn jet_rq_equ(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, QUAD, |o| o == Ordering::Equal)
+}
+
+pub fn jet_rq_gte(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, QUAD, |o| o != Ordering::Less)
+}
+
+pub fn jet_rq_gth(_context: &mut Context, subject: Noun) -> Result {
+    compare(subject, QUAD, |o| o == Ordering::Greater)
+}
+
+pub fn jet_rq_sun(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, QUAD, util::sun)
+}
+
+pub fn jet_rq_san(context: &mut Context, subject: Noun) -> Result {
+    integer(context, subject, QUAD, util::san)
+}
+
+pub fn jet_rq_toi(context: &mut Context, subject: Noun) -> Result {
+    to_integer(context, subject, QUAD)
+}
+
+/// The door's rounding mode, which is at +30 from its gates
+///
+/// The doors only admit %n, %u, %d and %z; anything else runs in nock.
+fn mode(subject: Noun) -> result::Result<Mode, JetErr> {
+    match slot(subject, 30)?.as_direct()?.data() {
+        tas!(b"n") => Ok(Mode::Near),
+        tas!(b"u") => Ok(Mode::Up),
+        tas!(b"d") => Ok(Mode::Down),
+        tas!(b"z") => Ok(Mode::Zero),
+        _ => Err(JetErr::Punt),
+    }
+}
+
+/// Read a float from an atom, ignoring any bits above the format's width as the hoon does
+fn load(format: Format, noun: Noun) -> result::Result<u128, JetErr> {
+    let bits = noun.as_atom()?;
+    let bits = bits.as_bitslice();
+    let width = format.width().min(bits.len());
+    let lo = bits[..width.min(64)].load_le::<u64>() as u128;
+    let hi = if width > 64 {
+        bits[64..width].load_le::<u64>() as u128
+    } else {
+        0
+    };
+    Ok(hi << 64 | lo)
+}
+
+fn store(stack: &mut NockStack, bits: u128) -> Noun {
+    if bits >> 64 == 0 {
+        Atom::new(stack, bits as u64).as_noun()
+    } else {
+        let words = [bits as u64, (bits >> 64) as u64];
+        unsafe { IndirectAtom::new_raw(stack, 2, words.as_ptr()).as_noun() }
+    }
+}
+
+fn unary(
+    context: &mut Context,
+    subject: Noun,
+    format: Format,
+    op: fn(Format, Mode, u128) -> u128,
+) -> Result {
+    let a = load(format, slot(subject, 6)?)?;
+    let mode = mode(subject)?;
+    Ok(store(&mut context.stack, op(format, mode, a)))
+}
+
+fn binary(
+    context: &mut Context,
+    subject: Noun,
+    format: Format,
+    op: fn(Format, Mode, u128, u128) -> u128,
+) -> Result {
+    let a = load(format, slot(subject, 12)?)?;
+    let b = load(format, slot(subject, 13)?)?;
+    let mode = mode(subject)?;
+    Ok(store(&mut context.stack, op(format, mode, a, b)))
+}
+
+fn fused(context: &mut Context, subject: Noun, format: Format) -> Result {
+    let a = load(format, slot(subject, 12)?)?;
+    let b = load(format, slot(subject, 26)?)?;
+    let c = load(format, slot(subject, 27)?)?;
+    let mode = mode(subject)?;
+    Ok(store(&mut context.stack, util::fma(format, mode, a, b, c)))
+}
+
+/// Comparisons are false whenever either side is a NaN, and don't depend on the rounding mode
+fn compare(subject: Noun, format: Format, test: fn(Ordering) -> bool) -> Result {
+    let a = load(format, slot(subject, 12)?)?;
+    let b = load(format, slot(subject, 13)?)?;
+    Ok(match util::compare(format, a, b) {
+        Some(order) if test(order) => YES,
+        _ => NO,
+    })
+}
+
+/// Conversion of an @u or @s, punting on integers wider than 128 bits
+fn integer(
+    context: &mut Context,
+    subject: Noun,
+    format: Format,
+    op: fn(Format, Mode, u128) -> u128,
+) -> Result {
+    let a = slot(subject, 6)?.as_atom()?;
+    if a.bit_size() > 128 {
+        return Err(JetErr::Punt);
+    }
+    let a = load(QUAD, a.as_noun())?;
+    let mode = mode(subject)?;
+    Ok(store(&mut context.stack, op(format, mode, a)))
+}
+
+/// Rounding to an integer in @s form, or ~ for a NaN or infinity
+fn to_integer(context: &mut Context, subject: Noun, format: Format) -> Result {
+    let a = load(format, slot(subject, 6)?)?;
+    let mode = mode(subject)?;
+    let stack = &mut context.stack;
+    Ok(match util::toi(format, mode, a) {
+        Some((sign, exp, sig)) => {
+            let integer = store_signed(stack, sign, exp, sig);
+            T(stack, &[D(0), integer])
+        }
+        None => D(0),
+    })
+}
+
+/// Store `sig * 2^exp`, negated if `sign`, as an @s: twice the magnitude, less one if negative
+fn store_signed(stack: &mut NockStack, sign: bool, exp: u32, sig: u128) -> Noun {
+    if sig == 0 {
+        return D(0);
+    }
+    let shift = exp as usize + 1;
+    // Less one is `(sig - 1) << shift` with the bits below `shift` set
+    let (high, ones) = if sign { (sig - 1, shift) } else { (sig, 0) };
+    if shift + 128 - high.leading_zeros() as usize <= 128 {
+        let low = u128::MAX.checked_shr(128 - ones as u32).unwrap_or(0);
+        return store(stack, high.checked_shl(shift as u32).unwrap_or(0) | low);
+    }
+    unsafe {
+        let (mut atom, bits) = IndirectAtom::new_raw_mut_bitslice(stack, (shift + 191) >> 6);
+        bits[..ones].fill(true);
+        bits[shift..shift + 64].store_le(high as u64);
+        bits[shift + 64..shift + 128].store_le((high >> 64) as u64);
+        atom.normalize_as_atom().as_noun()
+    }
+}
+
+pub mod util {
+    use std::cmp::{max, min, Ordering};
+
+    /// An IEEE 754 binary interchange format
+    #[derive(Copy, Clone, Debug)]
+    pub struct Format {
+        exp_bits: u32,
+        frac_bits: u32,
+    }
+
+    pub const HALF: Format = Format {
+        exp_bits: 5,
+        frac_bits: 10,
+    };
+    pub const SINGLE: Format = Format {
+        exp_bits: 8,
+        frac_bits: 23,
+    };
+    pub const DOUBLE: Format = Format {
+        exp_bits: 11,
+        frac_bits: 52,
+    };
+    pub const QUAD: Format = Format {
+        exp_bits: 15,
+        frac_bits: 112,
+    };
+
+    impl Format {
+        pub fn width(self) -> usize {
+            (1 + self.exp_bits + self.frac_bits) as usize
+        }
+
+        /// Significand bits, including the implicit leading bit
+        fn precision(self) -> i32 {
+            self.frac_bits as i32 + 1
+        }
+
+        fn bias(self) -> i32 {
+            (1 << (self.exp_bits - 1)) - 1
+        }
+
+        /// Exponent of the leading bit of the smallest normal number
+        fn emin(self) -> i32 {
+            1 - self.bias()
+        }
+
+        fn sign_bit(self) -> u128 {
+            1 << (self.exp_bits + self.frac_bits)
+        }
+
+        fn exp_mask(self) -> u128 {
+            ((1 << self.exp_bits) - 1) << self.frac_bits
+        }
+
+        fn frac_mask(self) -> u128 {
+            (1 << self.frac_bits) - 1
+        }
+
+        fn signed(self, sign: bool, bits: u128) -> u128 {
+            if sign {
+                bits | self.sign_bit()
+            } else {
+                bits
+            }
+        }
+
+        /// The canonical NaN: positive and quiet, with no other payload
+        fn nan(self) -> u128 {
+            self.exp_mask() | 1 << (self.frac_bits - 1)
+        }
+
+        fn inf(self, sign: bool) -> u128 {
+            self.signed(sign, self.exp_mask())
+        }
+
+        fn zero(self, sign: bool) -> u128 {
+            self.signed(sign, 0)
+        }
+
+        fn max_finite(self, sign: bool) -> u128 {
+            self.signed(
+                sign,
+                self.exp_mask() - (1 << self.frac_bits) + self.frac_mask(),
+            )
+        }
+
+        fn unpack(self, bits: u128) -> Class {
+            let sign = bits & self.sign_bit() != 0;
+            let exp = ((bits & self.exp_mask()) >> self.frac_bits) as i32;
+            let frac = bits & self.frac_mask();
+            if exp == (1 << self.exp_bits) - 1 {
+                if frac == 0 {
+                    Class::Inf(sign)
+                } else {
+                    Class::Nan
+                }
+            } else if exp == 0 {
+                if frac == 0 {
+                    Class::Zero(sign)
+                } else {
+                    Class::Finite(sign, self.emin() - self.frac_bits as i32, frac)
+                }
+            } else {
+                let sig = frac | 1 << self.frac_bits;
+                Class::Finite(sign, exp - self.bias() - self.frac_bits as i32, sig)
+            }
+        }
+    }
+
+    /// Rounding direction, named for the hoon rounding modes
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Mode {
+        /// %n: to nearest, ties to even
+        Near,
+        /// %u: toward positive infinity
+        Up,
+        /// %d: toward negative infinity
+        Down,
+        /// %z: toward zero
+        Zero,
+    }
+
+    /// A decoded float; finite values are `sig * 2^exp`, negative if the sign is set
+    enum Class {
+        Nan,
+        Inf(bool),
+        Zero(bool),
+        Finite(bool, i32, u128),
+    }
+
+    /// A 256-bit unsigned integer, wide enough for the exact product of two quad significands
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Wide {
+        hi: u128,
+        lo: u128,
+    }
+
+    impl Wide {
+        const ZERO: Wide = Wide { hi: 0, lo: 0 };
+
+        fn new(lo: u128) -> Wide {
+            Wide { hi: 0, lo }
+        }
+
+        fn is_zero(self) -> bool {
+            self.hi == 0 && self.lo == 0
+        }
+
+        fn bits(self) -> i32 {
+            if self.hi != 0 {
+                256 - self.hi.leading_zeros() as i32
+            } else {
+                128 - self.lo.leading_zeros() as i32
+            }
+        }
+
+        fn bit(self, i: u32) -> u128 {
+            if i >= 128 {
+                (self.hi >> (i - 128)) & 1
+            } else {
+                (self.lo >> i) & 1
+            }
+        }
+
+        fn shl(self, n: u32) -> Wide {
+            match n {
+                0 => self,
+                1..=127 => Wide {
+                    hi: self.hi << n | self.lo >> (128 - n),
+                    lo: self.lo << n,
+                },
+                128..=255 => Wide {
+                    hi: self.lo << (n - 128),
+                    lo: 0,
+                },
+                _ => Wide::ZERO,
+            }
+        }
+
+        fn shr(self, n: u32) -> Wide {
+            match n {
+                0 => self,
+                1..=127 => Wide {
+                    hi: self.hi >> n,
+                    lo: self.lo >> n | self.hi << (128 - n),
+                },
+                128..=255 => Wide {
+                    hi: 0,
+                    lo: self.hi >> (n - 128),
+                },
+                _ => Wide::ZERO,
+            }
+        }
+
+        fn add(self, other: Wide) -> Wide {
+            let (lo, carry) = self.lo.overflowing_add(other.lo);
+            Wide {
+                hi: self.hi + other.hi + carry as u128,
+                lo,
+            }
+        }
+
+        fn sub(self, other: Wide) -> Wide {
+            let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+            Wide {
+                hi: self.hi - other.hi - borrow as u128,
+                lo,
+            }
+        }
+
+        fn mul(a: u128, b: u128) -> Wide {
+            let (a1, a0) = (a >> 64, a & u64::MAX as u128);
+            let (b1, b0) = (b >> 64, b & u64::MAX as u128);
+            Wide {
+                hi: a1 * b1,
+                lo: a0 * b0,
+            }
+            .add(Wide::new(a0 * b1).shl(64))
+            .add(Wide::new(a1 * b0).shl(64))
+        }
+
+        /// Quotient and remainder by a divisor with its top bit set
+        fn div(self, d: u128) -> (Wide, u128) {
+            let mut q = Wide::ZERO;
+            let mut r = 0u128;
+            for i in (0..256).rev() {
+                let carry = r >> 127;
+                r = r << 1 | self.bit(i);
+                q = q.shl(1);
+                if carry == 1 || r >= d {
+                    r = r.wrapping_sub(d);
+                    q.lo |= 1;
+                }
+            }
+            (q, r)
+        }
+
+        /// Integer square root, and whether it was inexact
+        fn sqrt(self) -> (u128, bool) {
+            let mut rem = Wide::ZERO;
+            let mut root = 0u128;
+            for i in (0..128).rev() {
+                rem = rem.shl(2).add(Wide::new(self.shr(2 * i).lo & 3));
+                let trial = Wide::new(root).shl(2).add(Wide::new(1));
+                root <<= 1;
+                if rem >= trial {
+                    rem = rem.sub(trial);
+                    root |= 1;
+                }
+            }
+            (root, !rem.is_zero())
+        }
+    }
+
+    /// Whether rounding a value with discarded bits moves it away from zero, given the parity of
+    /// the part kept and how the discarded part compares to half the last place kept
+    fn increment(mode: Mode, sign: bool, odd: bool, half: Ordering, inexact: bool) -> bool {
+        match mode {
+            Mode::Near => half == Ordering::Greater || (half == Ordering::Equal && odd),
+            Mode::Up => inexact && !sign,
+            Mode::Down => inexact && sign,
+            Mode::Zero => false,
+        }
+    }
+
+    /// Round `sig * 2^exp` (plus a little more if `sticky`) to the format
+    ///
+    /// When `sticky` is set, `sig` must carry at least two bits beyond the format's precision, so
+    /// that the discarded part is all below the rounding position.
+    fn round(format: Format, mode: Mode, sign: bool, exp: i32, sig: Wide, sticky: bool) -> u128 {
+        if sig.is_zero() {
+            return format.zero(sign);
+        }
+        let precision = format.precision();
+        let top = exp + sig.bits() - 1;
+        // Exponent of the result's last place, which is fixed for subnormals
+        let mut quantum = max(top, format.emin()) - (precision - 1);
+
+        let (kept, increment) = if exp >= quantum {
+            debug_assert!(!sticky);
+            (sig.shl((exp - quantum) as u32), false)
+        } else {
+            let shift = (quantum - exp) as u32;
+            let kept = sig.shr(shift);
+            let rem = sig.sub(kept.shl(shift));
+            let half = if shift > 256 {
+                Ordering::Less
+            } else {
+                match rem.cmp(&Wide::new(1).shl(shift - 1)) {
+                    Ordering::Equal if sticky => Ordering::Greater,
+                    order => order,
+                }
+            };
+            let inexact = sticky || !rem.is_zero();
+            (kept, increment(mode, sign, kept.lo & 1 == 1, half, inexact))
+        };
+        debug_assert!(kept.hi == 0);
+
+        let mut sig = kept.lo + increment as u128;
+        if sig == 1 << precision {
+            sig >>= 1;
+            quantum += 1;
+        }
+        if sig < 1 << (precision - 1) {
+            // Subnormal, or zero if it rounded away
+            return format.signed(sign, sig);
+        }
+        let exp = quantum + precision - 1;
+        if exp > format.bias() {
+            return match mode {
+                Mode::Near => format.inf(sign),
+                Mode::Up if !sign => format.inf(sign),
+                Mode::Down if sign => format.inf(sign),
+                _ => format.max_finite(sign),
+            };
+        }
+        let biased = (exp + format.bias()) as u128;
+        format.signed(
+            sign,
+            biased << format.frac_bits | (sig & format.frac_mask()),
+        )
+    }
+
+    /// Round the exact sum of two signed values
+    ///
+    /// The operands are aligned in a 250-bit window below the larger one's top bit. Any bits of the
+    /// smaller one below the window only matter as a sticky bit, since it is then so much smaller
+    /// than the other that their difference keeps most of the window.
+    #[allow(clippy::too_many_arguments)]
+    fn sum(
+        format: Format,
+        mode: Mode,
+        sign_a: bool,
+        exp_a: i32,
+        sig_a: Wide,
+        sign_b: bool,
+        exp_b: i32,
+        sig_b: Wide,
+    ) -> u128 {
+        let top = max(exp_a + sig_a.bits(), exp_b + sig_b.bits());
+        let base = max(min(exp_a, exp_b), top - 250);
+        let align = |exp: i32, sig: Wide| {
+            if exp >= base {
+                (sig.shl((exp - base) as u32), false)
+            } else {
+                let shift = (base - exp) as u32;
+                let kept = sig.shr(shift);
+                (kept, kept.shl(shift) != sig)
+            }
+        };
+        let (a, sticky_a) = align(exp_a, sig_a);
+        let (b, sticky_b) = align(exp_b, sig_b);
+
+        if sign_a == sign_b {
+            return round(format, mode, sign_a, base, a.add(b), sticky_a || sticky_b);
+        }
+        // At most one side lost bits, which makes it a little larger than it looks
+        let a_larger = match a.cmp(&b) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal if sticky_a => true,
+            Ordering::Equal if sticky_b => false,
+            Ordering::Equal => return format.zero(mode == Mode::Down),
+        };
+        let (sign, large, small, sticky_large, sticky_small) = if a_larger {
+            (sign_a, a, b, sticky_a, sticky_b)
+        } else {
+            (sign_b, b, a, sticky_b, sticky_a)
+        };
+        if sticky_small {
+            let diff = large.sub(small).sub(Wide::new(1));
+            round(format, mode, sign, base, diff, true)
+        } else {
+            round(format, mode, sign, base, large.sub(small), sticky_large)
+        }
+    }
+
+    /// The sign of a zero sum of zeros
+    fn zero_sum(format: Format, mode: Mode, sign_a: bool, sign_b: bool) -> u128 {
+        if mode == Mode::Down {
+            format.zero(sign_a || sign_b)
+        } else {
+            format.zero(sign_a && sign_b)
+        }
+    }
+
+    pub fn add(format: Format, mode: Mode, a: u128, b: u128) -> u128 {
+        match (format.unpack(a), format.unpack(b)) {
+            (Class::Nan, _) | (_, Class::Nan) => format.nan(),
+            (Class::Inf(sign_a), Class::Inf(sign_b)) => {
+                if sign_a == sign_b {
+                    format.inf(sign_a)
+                } else {
+                    format.nan()
+                }
+            }
+            (Class::Inf(sign), _) | (_, Class::Inf(sign)) => format.inf(sign),
+            (Class::Zero(sign_a), Class::Zero(sign_b)) => zero_sum(format, mode, sign_a, sign_b),
+            (Class::Zero(_), Class::Finite(..)) => b,
+            (Class::Finite(..), Class::Zero(_)) => a,
+            (Class::Finite(sign_a, exp_a, sig_a), Class::Finite(sign_b, exp_b, sig_b)) => sum(
+                format,
+                mode,
+                sign_a,
+                exp_a,
+                Wide::new(sig_a),
+                sign_b,
+                exp_b,
+                Wide::new(sig_b),
+            ),
+        }
+    }
+
+    pub fn sub(format: Format, mode: Mode, a: u128, b: u128) -> u128 {
+        add(format, mode, a, b ^ format.sign_bit())
+    }
+
+    pub fn mul(format: Format, mode: Mode, a: u128, b: u128) -> u128 {
+        match (format.unpack(a), format.unpack(b)) {
+            (Class::Nan, _) | (_, Class::Nan) => format.nan(),
+            (Class::Inf(_), Class::Zero(_)) | (Class::Zero(_), Class::Inf(_)) => format.nan(),
+            (Class::Inf(sign_a), Class::Inf(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Inf(sign_b)) => format.inf(sign_a != sign_b),
+            (Class::Zero(sign_a), Class::Zero(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Zero(sign_b)) => format.zero(sign_a != sign_b),
+            (Class::Finite(sign_a, exp_a, sig_a), Class::Finite(sign_b, exp_b, sig_b)) => round(
+                format,
+                mode,
+                sign_a != sign_b,
+                exp_a + exp_b,
+                Wide::mul(sig_a, sig_b),
+                false,
+            ),
+        }
+    }
+
+    pub fn div(format: Format, mode: Mode, a: u128, b: u128) -> u128 {
+        match (format.unpack(a), format.unpack(b)) {
+            (Class::Nan, _) | (_, Class::Nan) => format.nan(),
+            (Class::Inf(_), Class::Inf(_)) | (Class::Zero(_), Class::Zero(_)) => format.nan(),
+            (Class::Inf(sign_a), Class::Zero(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Zero(sign_b)) => format.inf(sign_a != sign_b),
+            (Class::Zero(sign_a), Class::Inf(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Inf(sign_b)) => format.zero(sign_a != sign_b),
+            (Class::Finite(sign_a, exp_a, sig_a), Class::Finite(sign_b, exp_b, sig_b)) => {
+                // With both significands in [2^127, 2^128), the quotient of 2^128 times the
+                // dividend has at least 128 bits
+                let shift_a = sig_a.leading_zeros();
+                let shift_b = sig_b.leading_zeros();
+                let dividend = Wide {
+                    hi: sig_a << shift_a,
+                    lo: 0,
+                };
+                let (quotient, rem) = dividend.div(sig_b << shift_b);
+                let exp = exp_a - shift_a as i32 - (exp_b - shift_b as i32) - 128;
+                round(format, mode, sign_a != sign_b, exp, quotient, rem != 0)
+            }
+        }
+    }
+
+    pub fn sqt(format: Format, mode: Mode, a: u128) -> u128 {
+        match format.unpack(a) {
+            Class::Nan | Class::Inf(true) | Class::Finite(true, ..) => format.nan(),
+            Class::Inf(false) => format.inf(false),
+            Class::Zero(sign) => format.zero(sign),
+            Class::Finite(false, exp, sig) => {
+                // Fill 255 or 256 bits, leaving an even exponent to halve
+                let sig = Wide::new(sig);
+                let mut shift = 256 - sig.bits();
+                if (exp - shift) % 2 != 0 {
+                    shift -= 1;
+                }
+                let (root, inexact) = sig.shl(shift as u32).sqrt();
+                round(
+                    format,
+                    mode,
+                    false,
+                    (exp - shift) / 2,
+                    Wide::new(root),
+                    inexact,
+                )
+            }
+        }
+    }
+
+    /// `a * b + c` with a single rounding
+    pub fn fma(format: Format, mode: Mode, a: u128, b: u128, c: u128) -> u128 {
+        // The exact product, whose significand may be twice as wide as the format's
+        enum Product {
+            Inf(bool),
+            Zero(bool),
+            Finite(bool, i32, Wide),
+        }
+
+        let product = match (format.unpack(a), format.unpack(b)) {
+            (Class::Nan, _) | (_, Class::Nan) => return format.nan(),
+            (Class::Inf(_), Class::Zero(_)) | (Class::Zero(_), Class::Inf(_)) => {
+                return format.nan()
+            }
+            (Class::Inf(sign_a), Class::Inf(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Inf(sign_b)) => Product::Inf(sign_a != sign_b),
+            (Class::Zero(sign_a), Class::Zero(sign_b) | Class::Finite(sign_b, ..))
+            | (Class::Finite(sign_a, ..), Class::Zero(sign_b)) => Product::Zero(sign_a != sign_b),
+            (Class::Finite(sign_a, exp_a, sig_a), Class::Finite(sign_b, exp_b, sig_b)) => {
+                Product::Finite(sign_a != sign_b, exp_a + exp_b, Wide::mul(sig_a, sig_b))
+            }
+        };
+        match (product, format.unpack(c)) {
+            (_, Class::Nan) => format.nan(),
+            (Product::Inf(sign_p), Class::Inf(sign_c)) => {
+                if sign_p == sign_c {
+                    format.inf(sign_p)
+                } else {
+                    format.nan()
+                }
+            }
+            (Product::Inf(sign), _) | (_, Class::Inf(sign)) => format.inf(sign),
+            (Product::Zero(sign_p), Class::Zero(sign_c)) => zero_sum(format, mode, sign_p, sign_c),
+            (Product::Zero(_), Class::Finite(..)) => c,
+            (Product::Finite(sign_p, exp_p, sig_p), Class::Zero(_)) => {
+                round(format, mode, sign_p, exp_p, sig_p, false)
+            }
+            (Product::Finite(sign_p, exp_p, sig_p), Class::Finite(sign_c, exp_c, sig_c)) => sum(
+                format,
+                mode,
+                sign_p,
+                exp_p,
+                sig_p,
+                sign_c,
+                exp_c,
+                Wide::new(sig_c),
+            ),
+        }
+    }
+
+    /// Order two floats, or None if either is a NaN; the zeros are equal
+    pub fn compare(format: Format, a: u128, b: u128) -> Option<Ordering> {
+        if let (Class::Nan, _) | (_, Class::Nan) = (format.unpack(a), format.unpack(b)) {
+            return None;
+        }
+        let sign_a = a & format.sign_bit() != 0;
+        let sign_b = b & format.sign_bit() != 0;
+        let mag_a = a & !format.sign_bit();
+        let mag_b = b & !format.sign_bit();
+        if mag_a == 0 && mag_b == 0 {
+            return Some(Ordering::Equal);
+        }
+        Some(match (sign_a, sign_b) {
+            (false, false) => mag_a.cmp(&mag_b),
+            (true, true) => mag_b.cmp(&mag_a),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        })
+    }
+
+    /// Convert an unsigned integer
+    pub fn sun(format: Format, mode: Mode, a: u128) -> u128 {
+        round(format, mode, false, 0, Wide::new(a), false)
+    }
+
+    /// Round to an integral value in the same format, as SoftFloat's `roundToInt`
+    pub fn rnd(format: Format, mode: Mode, a: u128) -> u128 {
+        match format.unpack(a) {
+            Class::Nan => format.nan(),
+            Class::Finite(sign, exp, sig) if exp < 0 => {
+                let shift = -exp as u32;
+                let (kept, half, inexact) = if shift >= 128 {
+                    // The significand is all below half of the ones place
+                    (0, Ordering::Less, true)
+                } else {
+                    let kept = sig >> shift;
+                    let rem = sig - (kept << shift);
+                    (kept, rem.cmp(&(1 << (shift - 1))), rem != 0)
+                };
+                let sig = kept + increment(mode, sign, kept & 1 == 1, half, inexact) as u128;
+                round(format, mode, sign, 0, Wide::new(sig), false)
+            }
+            _ => a,
+        }
+    }
+
+    /// Round to an integer `sig * 2^exp`, negative if the sign is set, or None for a NaN or an
+    /// infinity
+    pub fn toi(format: Format, mode: Mode, a: u128) -> Option<(bool, u32, u128)> {
+        match format.unpack(rnd(format, mode, a)) {
+            Class::Nan | Class::Inf(_) => None,
+            Class::Zero(_) => Some((false, 0, 0)),
+            Class::Finite(sign, exp, sig) if exp < 0 => Some((sign, 0, sig >> -exp)),
+            Class::Finite(sign, exp, sig) => Some((sign, exp as u32, sig)),
+        }
+    }
+
+    /// Convert a signed integer in @s form: twice the magnitude, less one if negative
+    pub fn san(format: Format, mode: Mode, a: u128) -> u128 {
+        let sign = a & 1 == 1;
+        let magnitude = (a >> 1) + sign as u128;
+        round(format, mode, sign, 0, Wide::new(magnitude), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::util::*;
+    use super::*;
+    use crate::jets::util::test::{assert_jet_door, init_context, A};
+    use crate::jets::Jet;
+    use crate::noun::{D, T};
+    use crate::unifying_equality::unifying_equality;
+    use assert_no_alloc::assert_no_alloc;
+    use ibig::{ubig, UBig};
+
+    const HALF_NAN: u128 = 0x7e00;
+    const SINGLE_NAN: u128 = 0x7fc0_0000;
+    const DOUBLE_NAN: u128 = 0x7ff8_0000_0000_0000;
+
+    /// xorshift64, for reproducible operands
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Random bits, biased toward special values, subnormals and near-cancellation
+        fn operands(&mut self, width: u32, prev: u64) -> u64 {
+            let mask = if width == 64 {
+                u64::MAX
+            } else {
+                (1 << width) - 1
+            };
+            let bits = self.next();
+            let x = match bits % 8 {
+                0 => prev ^ (self.next() & 0xff),
+                1 => prev ^ (1 << (width - 1)) ^ (self.next() & 0xf),
+                2 => (bits >> 8) & (mask >> 9),
+                3 => [0, 1 << (width - 1), mask >> 1, 0x7f80_0000, 0x7ff0_0000_0000_0000]
+                    [(bits >> 8) as usize % 5],
+                _ => bits >> 3,
+            };
+            x & mask
+        }
+    }
+
+    fn check(want: u128, got: u128, what: &str, operands: &[u128]) {
+        assert_eq!(
+            want, got,
+            "{} of {:x?}: want {:x}, got {:x}",
+            what, operands, want, got
+        );
+    }
+
+    #[test]
+    fn test_single_against_hardware() {
+        let nan = SINGLE_NAN;
+        let canon = |x: f32| if x.is_nan() { nan } else { x.to_bits() as u128 };
+        let mut rng = Rng(0x5eed);
+        let mut b = 0;
+        for _ in 0..200_000 {
+            let a = rng.operands(32, b);
+            b = rng.operands(32, a);
+            let c = rng.operands(32, b);
+            let (x, y, z) = (
+                f32::from_bits(a as u32),
+                f32::from_bits(b as u32),
+                f32::from_bits(c as u32),
+            );
+            let (a, b, c) = (a as u128, b as u128, c as u128);
+            check(canon(x + y), add(SINGLE, Mode::Near, a, b), "add", &[a, b]);
+            check(canon(x - y), sub(SINGLE, Mode::Near, a, b), "sub", &[a, b]);
+            check(canon(x * y), mul(SINGLE, Mode::Near, a, b), "mul", &[a, b]);
+            check(canon(x / y), div(SINGLE, Mode::Near, a, b), "div", &[a, b]);
+            check(canon(x.sqrt()), sqt(SINGLE, Mode::Near, a), "sqt", &[a]);
+            check(
+                canon(x.mul_add(y, z)),
+                fma(SINGLE, Mode::Near, a, b, c),
+                "fma",
+                &[a, b, c],
+            );
+            let order = x.partial_cmp(&y);
+            assert_eq!(
+                order,
+                util::compare(SINGLE, a, b),
+                "compare {:x} {:x}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_double_against_hardware() {
+        let nan = DOUBLE_NAN;
+        let canon = |x: f64| if x.is_nan() { nan } else { x.to_bits() as u128 };
+        let mut rng = Rng(0xf10a7);
+        let mut b = 0;
+        for _ in 0..200_000 {
+            let a = rng.operands(64, b);
+            b = rng.operands(64, a);
+            let c = rng.operands(64, b);
+            let (x, y, z) = (f64::from_bits(a), f64::from_bits(b), f64::from_bits(c));
+            let (a, b, c) = (a as u128, b as u128, c as u128);
+            check(canon(x + y), add(DOUBLE, Mode::Near, a, b), "add", &[a, b]);
+            check(canon(x - y), sub(DOUBLE, Mode::Near, a, b), "sub", &[a, b]);
+            check(canon(x * y), mul(DOUBLE, Mode::Near, a, b), "mul", &[a, b]);
+            check(canon(x / y), div(DOUBLE, Mode::Near, a, b), "div", &[a, b]);
+            check(canon(x.sqrt()), sqt(DOUBLE, Mode::Near, a), "sqt", &[a]);
+            check(
+                canon(x.mul_add(y, z)),
+                fma(DOUBLE, Mode::Near, a, b, c),
+                "fma",
+                &[a, b, c],
+            );
+        }
+    }
+
+    /// Directed rounding brackets the exact product, which f64 holds for singles
+    #[test]
+    fn test_single_directed() {
+        let value = |bits: u128| f32::from_bits(bits as u32) as f64;
+        let mut rng = Rng(0xd1ec7);
+        let mut b = 0;
+        for _ in 0..100_000 {
+            let a = rng.operands(32, b);
+            b = rng.operands(32, a);
+            let exact = f32::from_bits(a as u32) as f64 * f32::from_bits(b as u32) as f64;
+            if exact.is_nan() {
+                continue;
+            }
+            let (a, b) = (a as u128, b as u128);
+            let down = mul(SINGLE, Mode::Down, a, b);
+            let up = mul(SINGLE, Mode::Up, a, b);
+            let zero = mul(SINGLE, Mode::Zero, a, b);
+            assert!(
+                value(down) <= exact && exact <= value(up),
+                "{:x} * {:x}",
+                a,
+                b
+            );
+            if value(down) == exact {
+                assert_eq!(down, up);
+            } else {
+                // Adjacent floats; stepping the bits of a negative float moves it down
+                let step = |x: u128| if x >> 31 == 1 { x - 1 } else { x + 1 };
+                let below = if value(down) == 0.0 {
+                    value(up)
+                } else {
+                    value(step(down))
+                };
+                assert!(below == value(up), "{:x} * {:x}", a, b);
+            }
+            assert_eq!(zero, if exact < 0.0 { up } else { down });
+        }
+    }
+
+    /// Nearest half to a double, found independently of the code under test, by search
+    fn nearest_half(x: f64) -> u128 {
+        let decode = |h: u32| {
+            let exp = (h >> 10) as i32;
+            let frac = (h & 0x3ff) as f64;
+            if exp == 0 {
+                frac * 2f64.powi(-24)
+            } else {
+                (frac + 1024.0) * 2f64.powi(exp - 25)
+            }
+        };
+        if x.is_nan() {
+            return HALF_NAN;
+        }
+        let sign = if x.is_sign_negative() { 0x8000 } else { 0 };
+        let m = x.abs();
+        if m >= 65520.0 {
+            return sign | 0x7c00;
+        }
+        let (mut lo, mut hi) = (0u32, 0x7bff);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if decode(mid) <= m {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        let below = decode(lo);
+        let above = decode(lo + 1);
+        let pick = if m - below < above - m || (m - below == above - m && lo % 2 == 0) {
+            lo
+        } else {
+            lo + 1
+        };
+        sign | pick as u128
+    }
+
+    #[test]
+    fn test_half_against_reference() {
+        let value = half_value;
+        let mut rng = Rng(0x4a1f);
+        for _ in 0..50_000 {
+            let a = (rng.next() & 0xffff) as u128;
+            let b = (rng.next() & 0xffff) as u128;
+            let (x, y) = (value(a), value(b));
+            check(
+                nearest_half(x + y),
+                add(HALF, Mode::Near, a, b),
+                "add",
+                &[a, b],
+            );
+            check(
+                nearest_half(x - y),
+                sub(HALF, Mode::Near, a, b),
+                "sub",
+                &[a, b],
+            );
+            check(
+                nearest_half(x * y),
+                mul(HALF, Mode::Near, a, b),
+                "mul",
+                &[a, b],
+            );
+            check(
+                nearest_half(x / y),
+                div(HALF, Mode::Near, a, b),
+                "div",
+                &[a, b],
+            );
+        }
+        for a in 0..=0xffffu128 {
+            check(
+                nearest_half(value(a).sqrt()),
+                sqt(HALF, Mode::Near, a),
+                "sqt",
+                &[a],
+            );
+        }
+    }
+
+    /// Exact double for a half
+    fn half_value(h: u128) -> f64 {
+        let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exp = ((h >> 10) & 0x1f) as i32;
+        let frac = (h & 0x3ff) as f64;
+        sign * match exp {
+            0 => frac * 2f64.powi(-24),
+            31 if frac == 0.0 => f64::INFINITY,
+            31 => f64::NAN,
+            _ => (frac + 1024.0) * 2f64.powi(exp - 25),
+        }
+    }
+
+    #[test]
+    fn test_quad() {
+        let one = 0x3fff_0000_0000_0000_0000_0000_0000_0000;
+        let two = 0x4000_0000_0000_0000_0000_0000_0000_0000;
+        let three = 0x4000_8000_0000_0000_0000_0000_0000_0000;
+        let third = 0x3ffd_5555_5555_5555_5555_5555_5555_5555;
+        let root_two = 0x3fff_6a09_e667_f3bc_c908_b2fb_1366_ea95;
+        let inf = 0x7fff_0000_0000_0000_0000_0000_0000_0000;
+        let nan = 0x7fff_8000_0000_0000_0000_0000_0000_0000;
+        assert_eq!(add(QUAD, Mode::Near, one, one), two);
+        assert_eq!(div(QUAD, Mode::Near, one, three), third);
+        assert_eq!(div(QUAD, Mode::Up, one, three), third + 1);
+        assert_eq!(sqt(QUAD, Mode::Near, two), root_two);
+        // 3 * third is 1 - 2^-114, exactly
+        let tiny = 0xbf8d << 112;
+        assert_eq!(fma(QUAD, Mode::Near, third, three, one ^ 1 << 127), tiny);
+        assert_eq!(sub(QUAD, Mode::Near, inf, inf), nan);
+        assert_eq!(util::compare(QUAD, third, one), Some(Ordering::Less));
+        assert_eq!(sun(QUAD, Mode::Near, 3), three);
+        assert_eq!(san(QUAD, Mode::Near, 5), three ^ 1 << 127);
+    }
+
+    #[test]
+    fn test_float_jets() {
+        let c = &mut init_context();
+        let door = |c: &mut Context, mode: u64| T(&mut c.stack, &[D(0), D(mode), D(0)]);
+
+        let near = door(c, tas!(b"n"));
+        let sam = T(&mut c.stack, &[D(0x3f80_0000), D(0x4000_0000)]);
+        assert_jet_door(c, jet_rs_add, sam, near, D(0x4040_0000));
+        assert_jet_door(c, jet_rs_lth, sam, near, YES);
+        assert_jet_door(c, jet_rs_gte, sam, near, NO);
+
+        // 1 + 2^-24 ties to 1, unless rounding up
+        let sam = T(&mut c.stack, &[D(0x3f80_0000), D(0x3380_0000)]);
+        assert_jet_door(c, jet_rs_add, sam, near, D(0x3f80_0000));
+        let up = door(c, tas!(b"u"));
+        assert_jet_door(c, jet_rs_add, sam, up, D(0x3f80_0001));
+
+        // 0.1 + 0.2
+        let sam = T(
+            &mut c.stack,
+            &[D(0x3fb9_9999_9999_999a), D(0x3fc9_9999_9999_999a)],
+        );
+        let res = A(&mut c.stack, &ubig!(0x3fd3_3333_3333_3334));
+        assert_jet_door(c, jet_rd_add, sam, near, res);
+
+        // 0 / 0 is the canonical NaN
+        let sam = T(&mut c.stack, &[D(0), D(0)]);
+        assert_jet_door(c, jet_rs_div, sam, near, D(0x7fc0_0000));
+        assert_jet_door(c, jet_rh_div, sam, near, D(0x7e00));
+        assert_jet_door(c, jet_rs_equ, sam, near, YES);
+
+        let sam = A(
+            &mut c.stack,
+            &ubig!(0x4000_0000_0000_0000_0000_0000_0000_0000),
+        );
+        let res = A(
+            &mut c.stack,
+            &ubig!(0x3fff_6a09_e667_f3bc_c908_b2fb_1366_ea95),
+        );
+        assert_jet_door(c, jet_rq_sqt, sam, near, res);
+
+        let sam = T(&mut c.stack, &[D(0x3c00), D(0x4000), D(0x3c00)]);
+        assert_jet_door(c, jet_rh_fma, sam, near, D(0x4200));
+
+        assert_jet_door(c, jet_rs_san, D(5), near, D(0xc040_0000));
+
+        let away = door(c, tas!(b"a"));
+        let sam = T(&mut c.stack, &[D(0), D(0)]);
+        let subject = T(&mut c.stack, &[D(0), sam, away]);
+        assert!(matches!(jet_rs_add(c, subject), Err(JetErr::Punt)));
+    }
+
+    const TESTFLOAT: &str = include_str!("float/testfloat.txt");
+
+    /// The jets for a TestFloat format, in the order add, sub, mul, div, sqt, fma, toi, equ, lte,
+    /// lth, gte, gth, sun and san, with the format's width and exponent bits
+    fn door_jets(name: &str) -> ([Jet; 14], Format, u32) {
+        match name {
+            "f16" => (
+                [
+                    jet_rh_add, jet_rh_sub, jet_rh_mul, jet_rh_div, jet_rh_sqt, jet_rh_fma,
+                    jet_rh_toi, jet_rh_equ, jet_rh_lte, jet_rh_lth, jet_rh_gte, jet_rh_gth,
+                    jet_rh_sun, jet_rh_san,
+                ],
+                HALF,
+                5,
+            ),
+            "f32" => (
+                [
+                    jet_rs_add, jet_rs_sub, jet_rs_mul, jet_rs_div, jet_rs_sqt, jet_rs_fma,
+                    jet_rs_toi, jet_rs_equ, jet_rs_lte, jet_rs_lth, jet_rs_gte, jet_rs_gth,
+                    jet_rs_sun, jet_rs_san,
+                ],
+                SINGLE,
+                8,
+            ),
+            "f64" => (
+                [
+                    jet_rd_add, jet_rd_sub, jet_rd_mul, jet_rd_div, jet_rd_sqt, jet_rd_fma,
+                    jet_rd_toi, jet_rd_equ, jet_rd_lte, jet_rd_lth, jet_rd_gte, jet_rd_gth,
+                    jet_rd_sun, jet_rd_san,
+                ],
+                DOUBLE,
+                11,
+            ),
+            "f128" => (
+                [
+                    jet_rq_add, jet_rq_sub, jet_rq_mul, jet_rq_div, jet_rq_sqt, jet_rq_fma,
+                    jet_rq_toi, jet_rq_equ, jet_rq_lte, jet_rq_lth, jet_rq_gte, jet_rq_gth,
+                    jet_rq_sun, jet_rq_san,
+                ],
+                QUAD,
+                15,
+            ),
+            _ => panic!("unknown format {}", name),
+        }
+    }
+
+    /// What +toi should give for a float already rounded to an integral value
+    fn integral(c: &mut Context, format: Format, exp_bits: u32, bits: u128) -> Noun {
+        let frac_bits = format.width() as u32 - 1 - exp_bits;
+        let biased = (bits >> frac_bits) as u32 & ((1 << exp_bits) - 1);
+        if biased == (1 << exp_bits) - 1 {
+            return D(0);
+        }
+        let frac = bits & ((1 << frac_bits) - 1);
+        let negative = bits >> (format.width() - 1) == 1;
+        let (sig, exp) = if biased == 0 {
+            (frac, 0)
+        } else {
+            (frac | 1 << frac_bits, biased - 1)
+        };
+        let bias = (1 << (exp_bits - 1)) - 1;
+        let shift = exp as i64 + 1 - bias - frac_bits as i64;
+        let magnitude = if shift >= 0 {
+            UBig::from(sig) << shift as usize
+        } else {
+            UBig::from(sig) >> -shift as usize
+        };
+        let signed = if magnitude == UBig::from(0u8) {
+            magnitude
+        } else if negative {
+            magnitude * UBig::from(2u8) - UBig::from(1u8)
+        } else {
+            magnitude * UBig::from(2u8)
+        };
+        let signed = A(&mut c.stack, &signed);
+        T(&mut c.stack, &[D(0), signed])
+    }
+
+    #[test]
+    fn test_testfloat_vectors() {
+        let c = &mut init_context();
+        let mut section = None;
+        for line in TESTFLOAT.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if line.contains('_') {
+                // A heading: the function, and the rounding mode if it has one
+                let (left, right) = fields[0].split_once('_').unwrap();
+                let (name, op) = if left.starts_with('f') {
+                    (left, right)
+                } else {
+                    (right.trim_start_matches("to_"), left)
+                };
+                let (tag, mode) = match fields.get(1) {
+                    None | Some(&"rnear_even") => (tas!(b"n"), Mode::Near),
+                    Some(&"rmax") => (tas!(b"u"), Mode::Up),
+                    Some(&"rmin") => (tas!(b"d"), Mode::Down),
+                    Some(&"rminMag") => (tas!(b"z"), Mode::Zero),
+                    Some(mode) => panic!("unknown rounding mode {}", mode),
+                };
+                let door = T(&mut c.stack, &[D(0), D(tag), D(0)]);
+                section = Some((name, op, mode, door, line));
+                continue;
+            }
+            let (name, op, mode, door, heading) = section.expect("vector before a heading");
+            let (jets, format, exp_bits) = door_jets(name);
+            let value: Vec<u128> = fields
+                .iter()
+                .map(|field| u128::from_str_radix(field, 16).unwrap())
+                .collect();
+            let [add, sub, mul, div, sqt, fma, toi, equ, lte, lth, gte, gth, sun, san] = jets;
+            let mut atoms: Vec<Noun> = value
+                .iter()
+                .map(|v| A(&mut c.stack, &UBig::from(*v)))
+                .collect();
+            let res = atoms.pop().unwrap();
+            let check = |c: &mut Context, jet: Jet, sam: Noun, mut res: Noun| {
+                let subject = T(&mut c.stack, &[D(0), sam, door]);
+                let mut got = assert_no_alloc(|| jet(c, subject).unwrap());
+                let eq = unsafe { unifying_equality(&mut c.stack, &mut got, &mut res) };
+                assert!(eq, "{}: {}: got {}, need {}", heading, line, got, res);
+            };
+            match op {
+                "add" | "sub" | "mul" | "div" | "mulAdd" => {
+                    let jet = match op {
+                        "add" => add,
+                        "sub" => sub,
+                        "mul" => mul,
+                        "div" => div,
+                        _ => fma,
+                    };
+                    let sam = T(&mut c.stack, &atoms);
+                    check(c, jet, sam, res);
+                }
+                "sqrt" => check(c, sqt, atoms[0], res),
+                "roundToInt" => {
+                    assert_eq!(
+                        rnd(format, mode, value[0]),
+                        value[1],
+                        "{}: {}",
+                        heading,
+                        line
+                    );
+                    let res = integral(c, format, exp_bits, value[1]);
+                    check(c, toi, atoms[0], res);
+                }
+                "eq" | "le" | "lt" => {
+                    let loobean = if value[2] == 1 { YES } else { NO };
+                    let (jet, flipped) = match op {
+                        "eq" => (equ, equ),
+                        "le" => (lte, gte),
+                        _ => (lth, gth),
+                    };
+                    let sam = T(&mut c.stack, &atoms[..2]);
+                    check(c, jet, sam, loobean);
+                    let sam = T(&mut c.stack, &[atoms[1], atoms[0]]);
+                    check(c, flipped, sam, loobean);
+                }
+                "ui64" => check(c, sun, atoms[0], res),
+                "i64" => {
+                    let n = value[0] as u64 as i64;
+                    let signed = if n < 0 {
+                        n.unsigned_abs() as u128 * 2 - 1
+                    } else {
+                        n as u128 * 2
+                    };
+                    let sam = A(&mut c.stack, &UBig::from(signed));
+                    check(c, san, sam, res);
+                }
+                _ => panic!("unknown function {}", heading),
+            }
+        }
+    }
+}
@@ -0,0 +1,421 @@
+#!/usr/bin/env python3
+"""Generate testfloat.txt, the vectors for the +rh/+rs/+rd/+rq jets, from exact rational arithmetic.
+
+    python3 gen_testfloat.py > testfloat.txt
+
+reproduces the checked-in vectors exactly (the operands come from a fixed seed), and
+
+    python3 gen_testfloat.py --check
+
+checks the model this uses against the host's binary64 arithmetic on random operands, so that the
+vectors don't only agree with themselves. Needs nothing beyond the Python 3 standard library.
+"""
+from fractions import Fraction
+import math, random, struct, sys
+
+FORMATS = {'f16': (5, 10), 'f32': (8, 23), 'f64': (11, 52), 'f128': (15, 112)}
+MODES = [('rnear_even', 'n'), ('rmax', 'u'), ('rmin', 'd'), ('rminMag', 'z')]
+# Vectors per section
+N = 12
+
+HEADER = """\
+# Test vectors for the floating-point jets, in the layout of TestFloat's testfloat_gen
+#
+# Each section is headed by a TestFloat function name and, where the result depends on it, a
+# rounding mode: rnear_even, rmax, rmin and rminMag are %n, %u, %d and %z. Each line under it has
+# the operands and then the result, in hex. Unlike testfloat_gen, there is no column of exception
+# flags, which the jets don't report, and every NaN result is the canonical quiet NaN, as in the
+# hoon. Comparison results are 1 for true, and the integers converted by ui64_to and i64_to are
+# 64-bit unsigned and two's complement.
+#
+# The results were computed in exact rational arithmetic, independently of the code under test,
+# for operands drawn as TestFloat draws them: special values, extreme exponents, and significands
+# with long runs of ones or zeros, with some sums and products arranged to tie or cancel.
+#
+# Generated by gen_testfloat.py in this directory; see there to regenerate or check them."""
+
+
+class Fmt:
+    def __init__(self, e, f):
+        self.E, self.F = e, f
+        self.p = f + 1
+        self.bias = (1 << (e - 1)) - 1
+        self.emin = 1 - self.bias
+        self.emax = self.bias
+        self.width = 1 + e + f
+        self.sign = 1 << (e + f)
+        self.expmask = ((1 << e) - 1) << f
+        self.nan = self.expmask | (1 << (f - 1))
+        self.inf = self.expmask
+        self.maxf = self.expmask - (1 << f) + ((1 << f) - 1)
+
+    def decode(self, x):
+        s = bool(x & self.sign)
+        ex = (x & self.expmask) >> self.F
+        fr = x & ((1 << self.F) - 1)
+        if ex == (1 << self.E) - 1:
+            return ('nan',) if fr else ('inf', s)
+        if ex == 0:
+            v = Fraction(fr) * Fraction(2) ** (self.emin - self.F)
+        else:
+            v = Fraction(fr + (1 << self.F)) * Fraction(2) ** (ex - self.bias - self.F)
+        return ('num', s, -v if s else v)
+
+    def zero(self, neg):
+        return self.sign if neg else 0
+
+    def finish(self, mode, neg, n, q, half, inexact):
+        """n * 2^q kept, `half` compares the discarded part with half of 2^q"""
+        if mode == 'n':
+            inc = half > 0 or (half == 0 and n & 1)
+        elif mode == 'u':
+            inc = inexact and not neg
+        elif mode == 'd':
+            inc = inexact and neg
+        else:
+            inc = False
+        n += bool(inc)
+        if n == 1 << self.p:
+            n >>= 1
+            q += 1
+        s = self.sign if neg else 0
+        if n == 0:
+            return s
+        if n < 1 << (self.p - 1):
+            assert q == self.emin - (self.p - 1)
+            return s | n
+        e = q + self.p - 1
+        if e > self.emax:
+            if mode == 'n' or (mode == 'u' and not neg) or (mode == 'd' and neg):
+                return s | self.inf
+            return s | self.maxf
+        return s | ((e + self.bias) << self.F) | (n - (1 << (self.p - 1)))
+
+    def round(self, mode, v, neg=None):
+        if neg is None:
+            neg = v < 0
+        x = abs(v)
+        if x == 0:
+            return self.zero(neg)
+        e = floor_log2(x)
+        q = max(e, self.emin) - (self.p - 1)
+        scaled = x / Fraction(2) ** q
+        n = scaled.numerator // scaled.denominator
+        r = scaled - n
+        return self.finish(mode, neg, n, q, cmp(r, Fraction(1, 2)), r != 0)
+
+    def sqrt(self, mode, x):
+        e = floor_log2(x) // 2
+        q = max(e, self.emin) - (self.p - 1)
+        y = x / Fraction(4) ** q
+        n = isqrt(y.numerator // y.denominator)
+        # sqrt(y) against n + 1/2
+        return self.finish(mode, False, n, q, cmp(y, Fraction(n * n + n) + Fraction(1, 4)), y != n * n)
+
+
+def cmp(a, b):
+    return (a > b) - (a < b)
+
+
+def floor_log2(x):
+    e = x.numerator.bit_length() - x.denominator.bit_length()
+    while Fraction(2) ** e > x:
+        e -= 1
+    while Fraction(2) ** (e + 1) <= x:
+        e += 1
+    return e
+
+
+def isqrt(n):
+    if n == 0:
+        return 0
+    x = 1 << ((n.bit_length() + 1) // 2)
+    while True:
+        y = (x + n // x) // 2
+        if y >= x:
+            return x
+        x = y
+
+
+def add(f, m, a, b):
+    da, db = f.decode(a), f.decode(b)
+    if da[0] == 'nan' or db[0] == 'nan':
+        return f.nan
+    if da[0] == 'inf' and db[0] == 'inf':
+        return f.sign * da[1] | f.inf if da[1] == db[1] else f.nan
+    if da[0] == 'inf':
+        return (f.sign if da[1] else 0) | f.inf
+    if db[0] == 'inf':
+        return (f.sign if db[1] else 0) | f.inf
+    s = da[2] + db[2]
+    if s == 0:
+        if da[2] == 0 and db[2] == 0 and da[1] == db[1]:
+            return f.zero(da[1])
+        return f.zero(m == 'd')
+    return f.round(m, s)
+
+
+def sub(f, m, a, b):
+    return add(f, m, a, b ^ f.sign)
+
+
+def mul(f, m, a, b):
+    da, db = f.decode(a), f.decode(b)
+    if da[0] == 'nan' or db[0] == 'nan':
+        return f.nan
+    neg = da[1] != db[1]
+    if da[0] == 'inf' or db[0] == 'inf':
+        if (da[0] == 'num' and da[2] == 0) or (db[0] == 'num' and db[2] == 0):
+            return f.nan
+        return (f.sign if neg else 0) | f.inf
+    return f.round(m, da[2] * db[2], neg)
+
+
+def div(f, m, a, b):
+    da, db = f.decode(a), f.decode(b)
+    if da[0] == 'nan' or db[0] == 'nan':
+        return f.nan
+    neg = da[1] != db[1]
+    sgn = f.sign if neg else 0
+    if da[0] == 'inf':
+        return f.nan if db[0] == 'inf' else sgn | f.inf
+    if db[0] == 'inf':
+        return sgn
+    if db[2] == 0:
+        return f.nan if da[2] == 0 else sgn | f.inf
+    return f.round(m, da[2] / db[2], neg)
+
+
+def sqrt(f, m, a):
+    d = f.decode(a)
+    if d[0] == 'nan':
+        return f.nan
+    if d[0] == 'inf':
+        return f.nan if d[1] else f.inf
+    if d[2] == 0:
+        return f.zero(d[1])
+    if d[1]:
+        return f.nan
+    return f.sqrt(m, d[2])
+
+
+def fma(f, m, a, b, c):
+    da, db, dc = f.decode(a), f.decode(b), f.decode(c)
+    if 'nan' in (da[0], db[0], dc[0]):
+        return f.nan
+    pneg = da[1] != db[1]
+    pinf = da[0] == 'inf' or db[0] == 'inf'
+    if pinf:
+        if (da[0] == 'num' and da[2] == 0) or (db[0] == 'num' and db[2] == 0):
+            return f.nan
+        if dc[0] == 'inf' and dc[1] != pneg:
+            return f.nan
+        return (f.sign if pneg else 0) | f.inf
+    if dc[0] == 'inf':
+        return (f.sign if dc[1] else 0) | f.inf
+    p = da[2] * db[2]
+    s = p + dc[2]
+    if s == 0:
+        if p == 0 and dc[2] == 0 and pneg == dc[1]:
+            return f.zero(pneg)
+        return f.zero(m == 'd')
+    return f.round(m, s)
+
+
+def rnd(f, m, a):
+    d = f.decode(a)
+    if d[0] == 'nan':
+        return f.nan
+    if d[0] == 'inf':
+        return a
+    v = d[2]
+    x = abs(v)
+    n = x.numerator // x.denominator
+    r = x - n
+    neg = d[1]
+    if m == 'n':
+        inc = r > Fraction(1, 2) or (r == Fraction(1, 2) and n & 1)
+    elif m == 'u':
+        inc = r != 0 and not neg
+    elif m == 'd':
+        inc = r != 0 and neg
+    else:
+        inc = False
+    n += bool(inc)
+    return f.round(m, Fraction(n), neg) if n else f.zero(neg)
+
+
+def compare(f, a, b):
+    da, db = f.decode(a), f.decode(b)
+    if da[0] == 'nan' or db[0] == 'nan':
+        return None
+    va = da[2] if da[0] == 'num' else (Fraction(-1) if da[1] else Fraction(1)) * Fraction(2) ** 20000
+    vb = db[2] if db[0] == 'num' else (Fraction(-1) if db[1] else Fraction(1)) * Fraction(2) ** 20000
+    return cmp(va, vb)
+
+
+def operand(f, rng, near=None):
+    """Random operands in the spirit of TestFloat's: special values, extreme exponents and
+    significands with long runs of ones or zeros"""
+    r = rng.random()
+    sgn = f.sign if rng.random() < 0.5 else 0
+    if near is not None and r < 0.2:
+        # close to another operand, for cancellation
+        return (near ^ rng.randrange(1 << min(8, f.F))) ^ (f.sign if rng.random() < 0.7 else 0)
+    if r < 0.3:
+        return sgn | rng.choice([
+            0, 1, (1 << f.F) - 1, 1 << f.F, f.maxf, f.inf, f.nan,
+            f.expmask | 1,  # signalling
+            f.bias << f.F,  # 1
+            (f.bias + 1) << f.F | (1 << (f.F - 1)),  # 3
+            (f.bias - 1) << f.F | ((1 << f.F) - 1),  # just under 1
+        ])
+    if r < 0.75:
+        exps = [0, 1, 2, f.bias - 2, f.bias - 1, f.bias, f.bias + 1, f.bias + 2,
+                (1 << f.E) - 3, (1 << f.E) - 2, rng.randrange(1, (1 << f.E) - 1)]
+        ex = rng.choice(exps)
+        kind = rng.randrange(5)
+        if kind == 0:
+            fr = rng.getrandbits(f.F)
+        elif kind == 1:
+            fr = (1 << rng.randrange(f.F + 1)) - 1  # low ones
+        elif kind == 2:
+            fr = ((1 << f.F) - 1) ^ ((1 << rng.randrange(f.F + 1)) - 1)  # high ones
+        elif kind == 3:
+            fr = 1 << rng.randrange(f.F)
+        else:
+            fr = ((1 << f.F) - 1) ^ (1 << rng.randrange(f.F))
+        return sgn | ex << f.F | fr
+    return rng.getrandbits(f.width)
+
+
+def tie(f, rng, a):
+    """An addend half a unit in the last place of `a`, or a quarter or three quarters of one"""
+    ex = (a & f.expmask) >> f.F
+    if ex < 2 or ex >= (1 << f.E) - 1:
+        return operand(f, rng)
+    e = ex - 1 - rng.choice([0, 0, 1])
+    if e - f.F <= 0:
+        return operand(f, rng)
+    sgn = f.sign if rng.random() < 0.5 else 0
+    return sgn | (e - f.F) << f.F | rng.choice([0, 0, 1 << (f.F - 1)])
+
+
+def h(f, x):
+    return format(x, '0%dX' % (f.width // 4))
+
+
+def check(count=200000):
+    """Compare the model's binary64 round-to-nearest results with the host's"""
+    f = Fmt(*FORMATS['f64'])
+    rng = random.Random(1)
+
+    def bits(x):
+        return struct.unpack('<Q', struct.pack('<d', x))[0]
+
+    def value(x):
+        return struct.unpack('<d', struct.pack('<Q', x))[0]
+
+    def host(x):
+        # the host's NaNs keep their payloads, where the hoon's are canonical
+        return f.nan if math.isnan(x) else bits(x)
+
+    def quotient(a, b):
+        # Python raises on division by zero, rather than following IEEE 754
+        if b:
+            return a / b
+        if a == 0 or math.isnan(a):
+            return math.nan
+        return math.copysign(math.inf, a) * math.copysign(1, b)
+
+    ops = [
+        ('add', add, lambda a, b: a + b),
+        ('sub', sub, lambda a, b: a - b),
+        ('mul', mul, lambda a, b: a * b),
+        ('div', div, quotient),
+    ]
+    bad = 0
+    for _ in range(count):
+        a = operand(f, rng)
+        b = operand(f, rng, a)
+        for name, model, native in ops:
+            want = host(native(value(a), value(b)))
+            got = model(f, 'n', a, b)
+            if got != want:
+                bad += 1
+                print('%s %s %s: model %s, host %s' % (name, h(f, a), h(f, b), h(f, got), h(f, want)))
+        if not a & f.sign or a & ~f.sign == 0:
+            want = host(math.sqrt(value(a)))
+            got = sqrt(f, 'n', a)
+            if got != want:
+                bad += 1
+                print('sqrt %s: model %s, host %s' % (h(f, a), h(f, got), h(f, want)))
+    print('%d mismatches' % bad)
+    return bad == 0
+
+
+def main():
+    if sys.argv[1:] == ['--check']:
+        sys.exit(0 if check() else 1)
+
+    rng = random.Random(0x7e57f10a7)
+    out = [HEADER]
+    for name, (e, fr) in FORMATS.items():
+        f = Fmt(e, fr)
+        for op in ['add', 'sub', 'mul', 'div', 'mulAdd', 'sqrt', 'roundToInt']:
+            for mname, m in MODES:
+                out.append('%s_%s %s' % (name, op, mname))
+                for i in range(N):
+                    a = operand(f, rng)
+                    if op in ('add', 'sub'):
+                        b = tie(f, rng, a) if i % 3 == 0 else operand(f, rng, a)
+                        res = (add if op == 'add' else sub)(f, m, a, b)
+                        out.append(' '.join(h(f, x) for x in (a, b, res)))
+                    elif op in ('mul', 'div'):
+                        b = operand(f, rng)
+                        res = (mul if op == 'mul' else div)(f, m, a, b)
+                        out.append(' '.join(h(f, x) for x in (a, b, res)))
+                    elif op == 'mulAdd':
+                        b = operand(f, rng)
+                        if i % 3 == 0:
+                            c = mul(f, 'n', a, b) ^ f.sign
+                        else:
+                            c = operand(f, rng)
+                        res = fma(f, m, a, b, c)
+                        out.append(' '.join(h(f, x) for x in (a, b, c, res)))
+                    elif op == 'sqrt':
+                        res = sqrt(f, m, a)
+                        out.append(' '.join(h(f, x) for x in (a, res)))
+                    else:
+                        if i % 2 == 0:
+                            # near the integers, with halves
+                            k = rng.randrange(-40, 41)
+                            frac = rng.choice([Fraction(0), Fraction(1, 2), Fraction(1, 4), Fraction(3, 4)])
+                            a = f.round('n', Fraction(k) / 2 + frac) if k or frac else a
+                        res = rnd(f, m, a)
+                        out.append(' '.join(h(f, x) for x in (a, res)))
+        for op in ['eq', 'le', 'lt']:
+            out.append('%s_%s' % (name, op))
+            for i in range(N):
+                a = operand(f, rng)
+                b = a if i % 4 == 0 else (a ^ f.sign if i % 4 == 1 else operand(f, rng, a))
+                c = compare(f, a, b)
+                res = {'eq': c == 0, 'le': c is not None and c <= 0, 'lt': c == -1}[op]
+                out.append('%s %s %d' % (h(f, a), h(f, b), res))
+        for op in ['ui64', 'i64']:
+            for mname, m in MODES:
+                out.append('%s_to_%s %s' % (op, name, mname))
+                for i in range(N):
+                    x = rng.choice([
+                        rng.getrandbits(64),
+                        rng.getrandbits(rng.randrange(1, 65)),
+                        (1 << rng.randrange(64)) + rng.choice([-1, 0, 1]),
+                        rng.randrange(0, 70000),
+                    ]) & ((1 << 64) - 1)
+                    v = x if op == 'ui64' or x < 1 << 63 else x - (1 << 64)
+                    out.append('%016X %s' % (x, h(f, f.round(m, Fraction(v)))))
+    print('\n'.join(out))
+
+
+if __name__ == '__main__':
+    main()
@@ -13,65 +13,960 @@ pub fn jet_scow(context: &mut Context, subject: Noun) -> Result {
     util::scow(&mut context.stack, aura, atom)
 }
 
+pub fn jet_slaw(context: &mut Context, subject: Noun) -> Result {
+    let aura = slot(subject, 12)?.as_direct()?;
+    let text = slot(subject, 13)?.as_atom()?;
+    util::slaw(&mut context.stack, aura, text)
+}
+
 pub mod util {
     use crate::jets;
+    use crate::jets::bits::util::met;
+    use crate::jets::ob::util::{fein, fynd};
     use crate::jets::JetErr;
     use crate::mem::NockStack;
-    use crate::noun::{Atom, Cell, DirectAtom, D, T};
+    use crate::noun::{Atom, Cell, DirectAtom, IndirectAtom, Noun, D, T};
+    use bitvec::field::BitField;
+    use bitvec::prelude::{BitSlice, Lsb0};
+    use ibig::UBig;
     use num_traits::identities::Zero;
+    use std::result;
     use sword_macros::tas;
 
+    /// Digits of every numeric aura up to base 64
+    const DIGITS: &[u8; 64] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ-~";
+
+    /// Leading syllables of @p words, indexed by high byte (+sis:po)
+    const SIS: &[u8; 768] = b"\
+        dozmarbinwansamlitsighidfidlissogdirwacsabwissib\
+        rigsoldopmodfoglidhopdardorlorhodfolrintogsilmir\
+        holpaslacrovlivdalsatlibtabhanticpidtorbolfosdot\
+        losdilforpilramtirwintadbicdifrocwidbisdasmidlop\
+        rilnardapmolsanlocnovsitnidtipsicropwitnatpanmin\
+        ritpodmottamtolsavposnapnopsomfinfonbanmorworsip\
+        ronnorbotwicsocwatdolmagpicdavbidbaltimtasmallig\
+        sivtagpadsaldivdactansidfabtarmonranniswolmispal\
+        lasdismaprabtobrollatlonnodnavfignomnibpagsopral\
+        bilhaddocridmocpacravripfaltodtiltinhapmicfanpat\
+        taclabmogsimsonpinlomrictapfirhasbosbatpochactid\
+        havsaplindibhosdabbitbarracparloddosbortochilmac\
+        tomdigfilfasmithobharmighinradmashalraglagfadtop\
+        mophabnilnosmilfopfamdatnoldinhatnacrisfotribhoc\
+        nimlarfitwalrapsarnalmoslandondanladdovrivbacpol\
+        laptalpitnambonrostonfodponsovnocsorlavmatmipfip";
+
+    /// Trailing syllables of @p words, indexed by low byte (+dex:po)
+    const DEX: &[u8; 768] = b"\
+        zodnecbudwessevpersutletfulpensytdurwepserwylsun\
+        rypsyxdyrnuphebpeglupdepdysputlughecryttyvsydnex\
+        lunmeplutseppesdelsulpedtemledtulmetwenbynhexfeb\
+        pyldulhetmevruttylwydtepbesdexsefwycburderneppur\
+        rysrebdennutsubpetrulsynregtydsupsemwynrecmegnet\
+        secmulnymtevwebsummutnyxrextebfushepbenmuswyxsym\
+        selrucdecwexsyrwetdylmynmesdetbetbeltuxtugmyrpel\
+        syptermebsetdutdegtexsurfeltudnuxruxrenwytnubmed\
+        lytdusnebrumtynseglyxpunresredfunrevrefmectedrus\
+        bexlebduxrynnumpyxrygryxfeptyrtustyclegnemfermer\
+        tenlusnussyltecmexpubrymtucfyllepdebbermughuttun\
+        bylsudpemdevlurdefbusbeprunmelpexdytbyttyplevmyl\
+        wedducfurfexnulluclennerlexrupnedlecrydlydfenwel\
+        nydhusrelrudneshesfetdesretdunlernyrsebhulryllud\
+        remlysfynwerrycsugnysnyllyndyndemluxfedsedbecmun\
+        lyrtesmudnytbyrsenwegfyrmurtelreptegpecnelnevfes";
+
+    /// Year of the @da epoch, 1 AD
+    const EPOCH: u64 = 292_277_024_400;
+
+    /// Days in a 400-year era and a non-leap century (+era:yo, +cet:yo)
+    const ERA: u64 = 146_097;
+    const CET: u64 = 36_524;
+
+    /// Seconds in a day, hour and minute (+day:yo, +hor:yo, +mit:yo)
+    const DAY: u64 = 86_400;
+    const HOR: u64 = 3_600;
+    const MIT: u64 = 60;
+
+    /// Days in each month of a common and a leap year (+moh:yo, +moy:yo)
+    const MOH: [u64; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    const MOY: [u64; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
     pub fn scow(
         stack: &mut NockStack,
         aura: DirectAtom, // XX: technically this should be Atom?
         atom: Atom,
     ) -> jets::Result {
         match aura.data() {
-            tas!(b"ud") => {
-                if atom.as_bitslice().first_one().is_none() {
-                    return Ok(T(stack, &[D(b'0' as u64), D(0)]));
+            tas!(b"ud") => ud(stack, atom),
+            tas!(b"ux") => Ok(base(stack, atom, b"0x", 4, 4)),
+            tas!(b"uv") => Ok(base(stack, atom, b"0v", 5, 5)),
+            tas!(b"uw") => Ok(base(stack, atom, b"0w", 6, 5)),
+            tas!(b"sd") => sd(stack, atom),
+            tas!(b"p") => Ok(p(stack, atom)),
+            tas!(b"da") => da(stack, atom),
+            tas!(b"dr") => dr(stack, atom),
+            tas!(b"t") => t(stack, atom),
+            tas!(b"ta") => {
+                let tape = trip(stack, atom, D(0));
+                Ok(weld(stack, b"~.", tape))
+            }
+            tas!(b"tas") => Ok(trip(stack, atom, D(0))),
+            _ => Err(JetErr::Punt),
+        }
+    }
+
+    pub fn slaw(stack: &mut NockStack, aura: DirectAtom, text: Atom) -> jets::Result {
+        let txt = &text.as_bytes()[..met(3, text)];
+        // +dog accepts whitespace and comments after a `.`; leave those to the Hoon
+        if txt.iter().any(|&c| c == b' ' || c == b'\n' || c == b':') {
+            return Err(JetErr::Punt);
+        }
+
+        let atom = match aura.data() {
+            tas!(b"ud") => dem(txt)?.map(|n| Atom::new(stack, n)),
+            tas!(b"ux") => bass(stack, txt, b"0x", 4, 4)?,
+            tas!(b"uv") => bass(stack, txt, b"0v", 5, 5)?,
+            tas!(b"uw") => bass(stack, txt, b"0w", 6, 5)?,
+            tas!(b"sd") => tash(stack, txt)?,
+            tas!(b"p") => fed(stack, txt)?,
+            tas!(b"da") => when(stack, txt)?,
+            tas!(b"dr") => rel(stack, txt)?,
+            tas!(b"t") => urx(stack, txt)?,
+            tas!(b"ta") => urs(stack, txt)?,
+            tas!(b"tas") => sym(text, txt),
+            _ => return Err(JetErr::Punt),
+        };
+
+        Ok(match atom {
+            Some(atom) => T(stack, &[D(0), atom.as_noun()]),
+            None => D(0),
+        })
+    }
+
+    //
+    //  Rendering
+    //
+
+    /// Render `.`-grouped decimal
+    fn ud(stack: &mut NockStack, atom: Atom) -> jets::Result {
+        if atom.as_bitslice().first_one().is_none() {
+            return Ok(T(stack, &[D(b'0' as u64), D(0)]));
+        }
+
+        let mut root = D(0);
+        let mut lent = 0;
+        if atom.direct().is_some() {
+            let mut n = atom.as_direct()?.data();
+
+            while n != 0 {
+                root = T(stack, &[D(b'0' as u64 + (n % 10)), root]);
+                n /= 10;
+                lent += 1;
+            }
+        } else {
+            let mut n = atom.as_indirect()?.as_ubig(stack);
+
+            while !n.is_zero() {
+                root = T(stack, &[D(b'0' as u64 + (&n % 10u64)), root]);
+                n /= 10u64;
+                lent += 1;
+            }
+        }
+
+        unsafe {
+            let mut list = root.as_cell()?;
+            lent -= 1;
+
+            while lent > 2 {
+                if lent % 3 == 0 {
+                    let (cell, memory) = Cell::new_raw_mut(stack);
+                    (*memory).head = D(b'.' as u64);
+                    (*memory).tail = list.tail();
+                    (*(list.to_raw_pointer_mut())).tail = cell.as_noun();
+                    list = list.tail().as_cell()?;
                 }
+                list = list.tail().as_cell()?;
+                lent -= 1;
+            }
 
-                let mut root = D(0);
-                let mut lent = 0;
-                if atom.direct().is_some() {
-                    let mut n = atom.as_direct()?.data();
+            Ok(root)
+        }
+    }
 
-                    while n != 0 {
-                        root = T(stack, &[D(b'0' as u64 + (n % 10)), root]);
-                        n /= 10;
-                        lent += 1;
-                    }
-                } else {
-                    let mut n = atom.as_indirect()?.as_ubig(stack);
+    /// Render a power-of-two base with `bits` per digit, in `.`-separated groups of `size`
+    fn base(stack: &mut NockStack, atom: Atom, prefix: &[u8], bits: usize, size: usize) -> Noun {
+        let slice = atom.as_bitslice();
+        let count = atom.bit_size().div_ceil(bits);
+        let mut tape = D(0);
+        if count == 0 {
+            tape = T(stack, &[D(b'0' as u64), tape]);
+        }
+        for i in 0..count {
+            if i > 0 && i % size == 0 {
+                tape = T(stack, &[D(b'.' as u64), tape]);
+            }
+            let digit = slice[i * bits..slice.len().min((i + 1) * bits)].load_le::<usize>();
+            tape = T(stack, &[D(DIGITS[digit] as u64), tape]);
+        }
+        weld(stack, prefix, tape)
+    }
 
-                    while !n.is_zero() {
-                        root = T(stack, &[D(b'0' as u64 + (&n % 10u64)), root]);
-                        n /= 10u64;
-                        lent += 1;
-                    }
+    /// Render a signed decimal (+old:si): even atoms are positive
+    fn sd(stack: &mut NockStack, atom: Atom) -> jets::Result {
+        let syn = !atom.as_bitslice()[0];
+        let abs = match atom.as_u64() {
+            Ok(n) => Atom::new(stack, (n >> 1) + (n & 1)),
+            Err(_) => {
+                let big = atom.as_ubig(stack);
+                let big = UBig::add_stack(stack, big, UBig::from(1u64));
+                let abs = UBig::div_stack(stack, big, UBig::from(2u64));
+                Atom::from_ubig(stack, &abs)
+            }
+        };
+        let tape = ud(stack, abs)?;
+        let sign: &[u8] = if syn { b"--" } else { b"-" };
+        Ok(weld(stack, sign, tape))
+    }
+
+    /// Render a ship name, scrambled with +fein
+    fn p(stack: &mut NockStack, atom: Atom) -> Noun {
+        let sxz;
+        let slice = match atom.as_u64() {
+            Ok(n) => {
+                sxz = [fein(n)];
+                BitSlice::<u64, Lsb0>::from_slice(&sxz)
+            }
+            Err(_) => atom.as_bitslice(),
+        };
+        let len = slice.last_one().map_or(0, |i| i + 1);
+
+        let mut tape = D(0);
+        if len <= 8 {
+            tape = syllable(stack, DEX, slice[..8].load_le::<u8>(), tape);
+        } else {
+            for imp in 0..len.div_ceil(16) {
+                if imp > 0 {
+                    let sep: &[u8] = if imp % 4 == 0 { b"--" } else { b"-" };
+                    tape = weld(stack, sep, tape);
+                }
+                let log = slice[imp * 16..(imp + 1) * 16].load_le::<u16>();
+                tape = syllable(stack, DEX, log as u8, tape);
+                tape = syllable(stack, SIS, (log >> 8) as u8, tape);
+            }
+        }
+        T(stack, &[D(b'~' as u64), tape])
+    }
+
+    /// Render an absolute date
+    fn da(stack: &mut NockStack, atom: Atom) -> jets::Result {
+        let now = u128_of(atom)?;
+        let (day, hor, mit, sec) = yell((now >> 64) as u64);
+        let (yer, mot, day) = yall(day);
+
+        let mut tape = fraction(stack, now as u64, D(0));
+        if now as u64 != 0 || hor != 0 || mit != 0 || sec != 0 {
+            tape = decimal(stack, sec, 2, tape);
+            tape = weld(stack, b".", tape);
+            tape = decimal(stack, mit, 2, tape);
+            tape = weld(stack, b".", tape);
+            tape = decimal(stack, hor, 2, tape);
+            tape = weld(stack, b"..", tape);
+        }
+        tape = decimal(stack, day, 1, tape);
+        tape = weld(stack, b".", tape);
+        tape = decimal(stack, mot, 1, tape);
+        tape = weld(stack, b".", tape);
+        tape = if yer > EPOCH {
+            decimal(stack, yer - EPOCH, 1, tape)
+        } else {
+            tape = weld(stack, b"-", tape);
+            decimal(stack, EPOCH + 1 - yer, 1, tape)
+        };
+        Ok(T(stack, &[D(b'~' as u64), tape]))
+    }
+
+    /// Render a relative date
+    fn dr(stack: &mut NockStack, atom: Atom) -> jets::Result {
+        let now = u128_of(atom)?;
+        let (day, hor, mit, sec) = yell((now >> 64) as u64);
+
+        let mut tape = fraction(stack, now as u64, D(0));
+        if day == 0 && hor == 0 && mit == 0 && sec == 0 {
+            tape = weld(stack, b"s0", tape);
+        } else {
+            for (unit, n) in [(b's', sec), (b'm', mit), (b'h', hor), (b'd', day)] {
+                if n != 0 {
+                    tape = decimal(stack, n, 1, tape);
+                    tape = weld(stack, &[b'.', unit], tape);
                 }
+            }
+            tape = tape.as_cell()?.tail();
+        }
+        Ok(T(stack, &[D(b'~' as u64), tape]))
+    }
 
-                unsafe {
-                    let mut list = root.as_cell()?;
-                    lent -= 1;
-
-                    while lent > 2 {
-                        if lent % 3 == 0 {
-                            let (cell, memory) = Cell::new_raw_mut(stack);
-                            (*memory).head = D(b'.' as u64);
-                            (*memory).tail = list.tail();
-                            (*(list.to_raw_pointer_mut())).tail = cell.as_noun();
-                            list = list.tail().as_cell()?;
+    /// Render a cord as an escaped knot (+wood)
+    fn t(stack: &mut NockStack, atom: Atom) -> jets::Result {
+        let bytes = &atom.as_bytes()[..met(3, atom)];
+        let mut res = D(0);
+        let mut dest = &mut res as *mut Noun;
+        unsafe {
+            dest = push(stack, dest, b'~');
+            dest = push(stack, dest, b'~');
+
+            let mut i = 0;
+            while i < bytes.len() {
+                let (c, width) = taft(&bytes[i..])?;
+                i += width;
+                match c {
+                    0x61..=0x7a | 0x30..=0x39 | 0x2d => dest = push(stack, dest, c as u8),
+                    0x20 => dest = push(stack, dest, b'.'),
+                    0x2e | 0x7e => {
+                        dest = push(stack, dest, b'~');
+                        dest = push(stack, dest, c as u8);
+                    }
+                    _ => {
+                        dest = push(stack, dest, b'~');
+                        for e in (0..(32 - c.leading_zeros()).div_ceil(4)).rev() {
+                            dest = push(stack, dest, DIGITS[(c >> (4 * e)) as usize & 0xf]);
                         }
-                        list = list.tail().as_cell()?;
-                        lent -= 1;
+                        dest = push(stack, dest, b'.');
                     }
+                }
+            }
+            *dest = D(0);
+        }
+        Ok(res)
+    }
+
+    /// Decode the UTF-8 character at the front of `bytes` (+teff, +taft), punting on anything the
+    /// Hoon would crash on or might decode differently
+    fn taft(bytes: &[u8]) -> result::Result<(u32, usize), JetErr> {
+        let lead = bytes[0];
+        let width = match lead {
+            b'\n' | 0x20..=0x7f => return Ok((lead as u32, 1)),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(JetErr::Punt),
+        };
+        if bytes.len() < width {
+            return Err(JetErr::Punt);
+        }
+        let mut c = (lead & (0x7f >> width)) as u32;
+        for byte in &bytes[1..width] {
+            if byte & 0xc0 != 0x80 {
+                return Err(JetErr::Punt);
+            }
+            c = (c << 6) | (byte & 0x3f) as u32;
+        }
+        Ok((c, width))
+    }
+
+    /// Prepend the sub-second fraction of a date as `..`-introduced groups of four hex digits,
+    /// most significant first, dropping trailing zero groups
+    fn fraction(stack: &mut NockStack, frac: u64, mut tape: Noun) -> Noun {
+        if frac == 0 {
+            return tape;
+        }
+        for i in (frac.trailing_zeros() / 16)..4 {
+            let group = frac >> (16 * i);
+            for e in 0..4 {
+                let digit = DIGITS[(group >> (4 * e)) as usize & 0xf];
+                tape = T(stack, &[D(digit as u64), tape]);
+            }
+            tape = T(stack, &[D(b'.' as u64), tape]);
+        }
+        T(stack, &[D(b'.' as u64), tape])
+    }
+
+    /// Prepend at least `min` decimal digits of `n`
+    fn decimal(stack: &mut NockStack, mut n: u64, min: usize, mut tape: Noun) -> Noun {
+        let mut count = 0;
+        while n != 0 || count < min {
+            tape = T(stack, &[D(b'0' as u64 + n % 10), tape]);
+            n /= 10;
+            count += 1;
+        }
+        tape
+    }
+
+    /// Prepend the bytes of `atom`
+    fn trip(stack: &mut NockStack, atom: Atom, tape: Noun) -> Noun {
+        weld(stack, &atom.as_bytes()[..met(3, atom)], tape)
+    }
+
+    /// Prepend the syllable at `index` of `table`
+    fn syllable(stack: &mut NockStack, table: &[u8; 768], index: u8, tape: Noun) -> Noun {
+        let index = index as usize * 3;
+        weld(stack, &table[index..index + 3], tape)
+    }
+
+    /// Prepend `bytes`
+    fn weld(stack: &mut NockStack, bytes: &[u8], mut tape: Noun) -> Noun {
+        for byte in bytes.iter().rev() {
+            tape = T(stack, &[D(*byte as u64), tape]);
+        }
+        tape
+    }
 
-                    Ok(root)
+    /// Append `byte` at `dest`, giving the new end of the list
+    unsafe fn push(stack: &mut NockStack, dest: *mut Noun, byte: u8) -> *mut Noun {
+        let (cell, memory) = Cell::new_raw_mut(stack);
+        (*memory).head = D(byte as u64);
+        *dest = cell.as_noun();
+        &mut (*memory).tail
+    }
+
+    //
+    //  Parsing
+    //
+
+    /// Parse `.`-grouped decimal (+dem:ag), punting on anything too wide for a word
+    fn dem(txt: &[u8]) -> result::Result<Option<u64>, JetErr> {
+        if !grouped(txt, &DIGITS[..10], 3) {
+            return Ok(None);
+        }
+        txt.iter()
+            .filter(|&&c| c != b'.')
+            .try_fold(0u64, |n, c| {
+                n.checked_mul(10)?.checked_add((c - b'0') as u64)
+            })
+            .map(Some)
+            .ok_or(JetErr::Punt)
+    }
+
+    /// Parse a prefixed power-of-two base with `bits` per digit, in `.`-separated groups of `size`
+    /// (+hex:ag, +viz:ag, +wiz:ag)
+    fn bass(
+        stack: &mut NockStack,
+        txt: &[u8],
+        prefix: &[u8],
+        bits: usize,
+        size: usize,
+    ) -> result::Result<Option<Atom>, JetErr> {
+        // +hex:ag accepts uppercase in only some positions
+        if bits < 6 && txt.iter().any(u8::is_ascii_uppercase) {
+            return Err(JetErr::Punt);
+        }
+        let digits = &DIGITS[..1 << bits];
+        let Some(txt) = txt.strip_prefix(prefix) else {
+            return Ok(None);
+        };
+        if !grouped(txt, digits, size) {
+            return Ok(None);
+        }
+
+        let count = txt.iter().filter(|&&c| c != b'.').count();
+        unsafe {
+            let (mut atom, slice) =
+                IndirectAtom::new_raw_mut_bitslice(stack, (count * bits).div_ceil(64));
+            for (i, c) in txt.iter().rev().filter(|&&c| c != b'.').enumerate() {
+                let digit = digits.iter().position(|d| d == c).unwrap_or(0);
+                slice[i * bits..(i + 1) * bits].store_le(digit);
+            }
+            Ok(Some(atom.normalize_as_atom()))
+        }
+    }
+
+    /// Whether `txt` is `0`, or a leading group of one to `size` digits without a leading zero
+    /// followed by `.`-separated groups of exactly `size` digits (+ape:ag over +bass)
+    fn grouped(txt: &[u8], digits: &[u8], size: usize) -> bool {
+        if txt == b"0" {
+            return true;
+        }
+        let mut groups = txt.split(|&c| c == b'.');
+        let lead = groups.next().unwrap_or_default();
+        !lead.is_empty()
+            && lead.len() <= size
+            && lead[0] != b'0'
+            && lead.iter().all(|c| digits.contains(c))
+            && groups.all(|group| group.len() == size && group.iter().all(|c| digits.contains(c)))
+    }
+
+    /// Parse a signed decimal (+tash:so): `--` for positive, `-` for negative
+    fn tash(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"-") else {
+            return Ok(None);
+        };
+        let (syn, txt) = match txt.strip_prefix(b"-") {
+            Some(txt) => (true, txt),
+            None => (false, txt),
+        };
+        let Some(abs) = dem(txt)? else {
+            return Ok(None);
+        };
+        let abs = abs as u128;
+        let new = if syn {
+            abs * 2
+        } else if abs == 0 {
+            0
+        } else {
+            abs * 2 - 1
+        };
+        Ok(Some(atom_u128(stack, new)))
+    }
+
+    /// Parse a ship name, unscrambling with +fynd (+fed:ag)
+    fn fed(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"~") else {
+            return Ok(None);
+        };
+
+        // Galaxies and stars
+        if txt.len() == 3 {
+            return Ok(index(DEX, txt).map(|dex| Atom::new(stack, dex as u64)));
+        }
+        if txt.len() == 6 {
+            return Ok(match word(txt) {
+                Some(log) if log >> 8 != 0 => Some(Atom::new(stack, log as u64)),
+                _ => None,
+            });
+        }
+
+        // Planets and moons, or longer names in `--`-separated groups of four words
+        let mut count: usize = 0;
+        let mut group = 0;
+        let mut split = false;
+        let mut pos = 0;
+        loop {
+            let Some(log) = txt.get(pos..pos + 6).and_then(word) else {
+                return Ok(None);
+            };
+            if count == 0 && log == 0 {
+                return Ok(None);
+            }
+            count += 1;
+            group += 1;
+            pos += 6;
+            if group > 4 {
+                return Ok(None);
+            }
+            if pos == txt.len() {
+                break;
+            }
+            if txt[pos..].starts_with(b"--") {
+                if split && group != 4 {
+                    return Ok(None);
                 }
+                split = true;
+                group = 0;
+                pos += 2;
+            } else if txt[pos] == b'-' {
+                pos += 1;
+            } else {
+                return Ok(None);
+            }
+        }
+        if (split && group != 4) || (!split && count < 2) {
+            return Ok(None);
+        }
+
+        let words = txt.rsplit(|&c| c == b'-').filter(|w| !w.is_empty());
+        if count <= 4 {
+            let cry = words
+                .rev()
+                .fold(0, |cry, w| (cry << 16) | word(w).unwrap_or(0) as u64);
+            Ok(Some(Atom::new(stack, fynd(cry))))
+        } else {
+            unsafe {
+                let (mut atom, slice) =
+                    IndirectAtom::new_raw_mut_bitslice(stack, count.div_ceil(4));
+                for (i, w) in words.enumerate() {
+                    slice[i * 16..(i + 1) * 16].store_le(word(w).unwrap_or(0));
+                }
+                Ok(Some(atom.normalize_as_atom()))
             }
-            _ => Err(JetErr::Punt),
+        }
+    }
+
+    /// Value of a two-syllable @p word
+    fn word(txt: &[u8]) -> Option<u16> {
+        if txt.len() != 6 {
+            return None;
+        }
+        let sis = index(SIS, &txt[..3])?;
+        let dex = index(DEX, &txt[3..])?;
+        Some(((sis as u16) << 8) | dex as u16)
+    }
+
+    /// Index of a syllable in `table`
+    fn index(table: &[u8; 768], syllable: &[u8]) -> Option<u8> {
+        table.chunks(3).position(|s| s == syllable).map(|i| i as u8)
+    }
+
+    /// Parse an absolute date (+when:so), converting with +year
+    fn when(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"~") else {
+            return Ok(None);
+        };
+        let pos = &mut 0;
+
+        let Some(y) = dim(txt, pos)? else {
+            return Ok(None);
+        };
+        let ad = !eat(txt, pos, b"-");
+        if !eat(txt, pos, b".") {
+            return Ok(None);
+        }
+        let Some(mot) = mot(txt, pos)? else {
+            return Ok(None);
+        };
+        if !eat(txt, pos, b".") {
+            return Ok(None);
+        }
+        let Some(day) = dip(txt, pos)? else {
+            return Ok(None);
+        };
+
+        let (mut hor, mut mit, mut sec, mut frac) = (0, 0, 0, 0);
+        if eat(txt, pos, b"..") {
+            let Some(h) = dum(txt, pos)? else {
+                return Ok(None);
+            };
+            if !eat(txt, pos, b".") {
+                return Ok(None);
+            }
+            let Some(m) = dum(txt, pos)? else {
+                return Ok(None);
+            };
+            if !eat(txt, pos, b".") {
+                return Ok(None);
+            }
+            let Some(s) = dum(txt, pos)? else {
+                return Ok(None);
+            };
+            (hor, mit, sec) = (h, m, s);
+            if eat(txt, pos, b"..") {
+                let Some(f) = qix(txt, pos)? else {
+                    return Ok(None);
+                };
+                frac = f;
+            }
+        }
+        if *pos != txt.len() {
+            return Ok(None);
+        }
+
+        // +year crashes on the year before 1 BC
+        let yer = if ad {
+            EPOCH.checked_add(y)
+        } else {
+            y.checked_sub(1).and_then(|y| EPOCH.checked_sub(y))
+        }
+        .ok_or(JetErr::Punt)?;
+        let days = yawn(yer, mot, day);
+        yule(stack, days, hor, mit, sec, frac)
+    }
+
+    /// Parse a relative date (+crub:so)
+    fn rel(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"~") else {
+            return Ok(None);
+        };
+        let pos = &mut 0;
+
+        // Each unit may be given once, or again if it was zero
+        let mut rop = [0u64; 4];
+        loop {
+            let unit = match txt.get(*pos) {
+                Some(b'd') => 0,
+                Some(b'h') => 1,
+                Some(b'm') => 2,
+                Some(b's') => 3,
+                _ => return Ok(None),
+            };
+            *pos += 1;
+            let Some(n) = dim(txt, pos)? else {
+                return Ok(None);
+            };
+            if rop[unit] != 0 {
+                return Ok(None);
+            }
+            rop[unit] = n;
+            match txt.get(*pos..*pos + 2) {
+                Some([b'.', b'd' | b'h' | b'm' | b's']) => *pos += 1,
+                _ => break,
+            }
+        }
+
+        let mut frac = 0;
+        if eat(txt, pos, b"..") {
+            let Some(f) = qix(txt, pos)? else {
+                return Ok(None);
+            };
+            frac = f;
+        }
+        if *pos != txt.len() {
+            return Ok(None);
+        }
+
+        let [day, hor, mit, sec] = rop;
+        yule(stack, day as u128, hor, mit, sec, frac)
+    }
+
+    /// Parse an escaped knot into a cord (+urx:ab), punting on hex escapes
+    fn urx(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"~~") else {
+            return Ok(None);
+        };
+
+        let mut len = 0;
+        let mut i = 0;
+        while i < txt.len() {
+            match txt[i] {
+                b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' => i += 1,
+                b'~' => match txt.get(i + 1) {
+                    Some(b'~' | b'.') => i += 2,
+                    Some(_) => return Err(JetErr::Punt),
+                    None => return Ok(None),
+                },
+                b'_' => return Err(JetErr::Punt),
+                _ => return Ok(None),
+            }
+            len += 1;
+        }
+        if len == 0 {
+            return Ok(Some(Atom::new(stack, 0)));
+        }
+
+        unsafe {
+            let (mut atom, buffer) = IndirectAtom::new_raw_mut_bytes(stack, len);
+            let mut i = 0;
+            for byte in buffer.iter_mut() {
+                *byte = match txt[i] {
+                    b'.' => b' ',
+                    b'~' => {
+                        i += 1;
+                        txt[i]
+                    }
+                    c => c,
+                };
+                i += 1;
+            }
+            Ok(Some(atom.normalize_as_atom()))
+        }
+    }
+
+    /// Parse an unescaped knot (+urs:ab)
+    fn urs(stack: &mut NockStack, txt: &[u8]) -> result::Result<Option<Atom>, JetErr> {
+        let Some(txt) = txt.strip_prefix(b"~.") else {
+            return Ok(None);
+        };
+        for c in txt {
+            match c {
+                b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'~' => {}
+                b'_' => return Err(JetErr::Punt),
+                _ => return Ok(None),
+            }
+        }
+        if txt.is_empty() {
+            return Ok(Some(Atom::new(stack, 0)));
+        }
+        unsafe {
+            let (mut atom, buffer) = IndirectAtom::new_raw_mut_bytes(stack, txt.len());
+            buffer.copy_from_slice(txt);
+            Ok(Some(atom.normalize_as_atom()))
+        }
+    }
+
+    /// Parse a symbol, which is its own value (+sym)
+    fn sym(text: Atom, txt: &[u8]) -> Option<Atom> {
+        let valid = txt.first().is_some_and(u8::is_ascii_lowercase)
+            && txt
+                .iter()
+                .all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-');
+        valid.then_some(text)
+    }
+
+    /// Consume `tag` if `txt` continues with it
+    fn eat(txt: &[u8], pos: &mut usize, tag: &[u8]) -> bool {
+        let found = txt[*pos..].starts_with(tag);
+        if found {
+            *pos += tag.len();
+        }
+        found
+    }
+
+    /// Consume a run of decimal digits
+    fn digits<'a>(txt: &'a [u8], pos: &mut usize) -> &'a [u8] {
+        let start = *pos;
+        while txt.get(*pos).is_some_and(u8::is_ascii_digit) {
+            *pos += 1;
+        }
+        &txt[start..*pos]
+    }
+
+    /// Value of a run of decimal digits, punting if it is too wide for a word
+    fn value(run: &[u8]) -> result::Result<u64, JetErr> {
+        run.iter()
+            .try_fold(0u64, |n, c| {
+                n.checked_mul(10)?.checked_add((c - b'0') as u64)
+            })
+            .ok_or(JetErr::Punt)
+    }
+
+    /// Decimal without a leading zero, or zero itself (+dim:ag)
+    fn dim(txt: &[u8], pos: &mut usize) -> result::Result<Option<u64>, JetErr> {
+        let run = digits(txt, pos);
+        if run.is_empty() || (run[0] == b'0' && run.len() > 1) {
+            return Ok(None);
+        }
+        value(run).map(Some)
+    }
+
+    /// Positive decimal without a leading zero (+dip:ag)
+    fn dip(txt: &[u8], pos: &mut usize) -> result::Result<Option<u64>, JetErr> {
+        let run = digits(txt, pos);
+        if run.first().is_none_or(|&c| c == b'0') {
+            return Ok(None);
+        }
+        value(run).map(Some)
+    }
+
+    /// Decimal with any leading zeroes (+dum:ag)
+    fn dum(txt: &[u8], pos: &mut usize) -> result::Result<Option<u64>, JetErr> {
+        let run = digits(txt, pos);
+        if run.is_empty() {
+            return Ok(None);
+        }
+        value(run).map(Some)
+    }
+
+    /// Month from 1 to 12 (+mot:ag)
+    fn mot(txt: &[u8], pos: &mut usize) -> result::Result<Option<u64>, JetErr> {
+        let run = digits(txt, pos);
+        if run.is_empty() || run.len() > 2 || run[0] == b'0' {
+            return Ok(None);
+        }
+        Ok(value(run).ok().filter(|&mot| mot <= 12))
+    }
+
+    /// Sub-second fraction in `.`-separated groups of four hex digits, most significant first;
+    /// +yule crashes on more than four
+    fn qix(txt: &[u8], pos: &mut usize) -> result::Result<Option<u64>, JetErr> {
+        let hex = &DIGITS[..16];
+        let mut frac = 0;
+        let mut muc = 4;
+        let mut at = *pos;
+        loop {
+            let Some(group) = txt.get(at..at + 4) else {
+                break;
+            };
+            if !group.iter().all(|c| hex.contains(c)) {
+                break;
+            }
+            if muc == 0 {
+                return Err(JetErr::Punt);
+            }
+            muc -= 1;
+            let group = group.iter().fold(0, |n, c| {
+                (n << 4) | hex.iter().position(|d| d == c).unwrap_or(0) as u64
+            });
+            frac |= group << (16 * muc);
+            // only take the `.` once the group after it has parsed
+            *pos = at + 4;
+            if !txt[*pos..].starts_with(b".") {
+                break;
+            }
+            at = *pos + 1;
+        }
+        Ok((muc < 4).then_some(frac))
+    }
+
+    //
+    //  Dates
+    //
+
+    /// Day count and time of day of whole seconds (+yell)
+    fn yell(sec: u64) -> (u64, u64, u64, u64) {
+        let day = sec / DAY;
+        let sec = sec % DAY;
+        (day, sec / HOR, sec % HOR / MIT, sec % MIT)
+    }
+
+    /// Year, month and day of a day count (+yall)
+    fn yall(mut day: u64) -> (u64, u64, u64) {
+        let era = day / ERA;
+        day %= ERA;
+        let (mut lep, cet) = if day < CET + 1 {
+            (true, 0)
+        } else {
+            day -= CET + 1;
+            let cet = 1 + day / CET;
+            day %= CET;
+            (false, cet)
+        };
+
+        let mut yer = 400 * era + 100 * cet;
+        loop {
+            let dis = if lep { 366 } else { 365 };
+            if day < dis {
+                break;
+            }
+            yer += 1;
+            day -= dis;
+            lep = yer % 4 == 0;
+        }
+
+        let cah = if lep { MOY } else { MOH };
+        let mut mot = 0;
+        while day >= cah[mot] {
+            day -= cah[mot];
+            mot += 1;
+        }
+        (yer, mot as u64 + 1, day + 1)
+    }
+
+    /// Day count of a date (+yawn)
+    fn yawn(yer: u64, mot: u64, day: u64) -> u128 {
+        let leap = yer % 4 == 0 && (yer % 100 != 0 || yer % 400 == 0);
+        let cah = if leap { MOY } else { MOH };
+        let before: u64 = cah[..mot as usize - 1].iter().sum();
+        let yer = yer as u128;
+        let leaps = (yer + 3) / 4 - (yer + 99) / 100 + (yer + 399) / 400;
+        365 * yer + leaps + before as u128 + day as u128 - 1
+    }
+
+    /// Date atom of a day count, time of day and fraction (+yule), punting past 128 bits
+    fn yule(
+        stack: &mut NockStack,
+        day: u128,
+        hor: u64,
+        mit: u64,
+        sec: u64,
+        frac: u64,
+    ) -> result::Result<Option<Atom>, JetErr> {
+        let sec = day
+            .checked_mul(DAY as u128)
+            .and_then(|s| s.checked_add(hor as u128 * HOR as u128))
+            .and_then(|s| s.checked_add(mit as u128 * MIT as u128))
+            .and_then(|s| s.checked_add(sec as u128))
+            .filter(|&s| s <= u64::MAX as u128)
+            .ok_or(JetErr::Punt)?;
+        Ok(Some(atom_u128(stack, (sec << 64) | frac as u128)))
+    }
+
+    /// An atom of at most 128 bits, punting on anything wider
+    fn u128_of(atom: Atom) -> result::Result<u128, JetErr> {
+        if atom.bit_size() > 128 {
+            return Err(JetErr::Punt);
+        }
+        let bits = atom.as_bitslice();
+        let lo = bits[..64].load_le::<u64>() as u128;
+        let hi = if bits.len() > 64 {
+            bits[64..128].load_le::<u64>() as u128
+        } else {
+            0
+        };
+        Ok((hi << 64) | lo)
+    }
+
+    fn atom_u128(stack: &mut NockStack, n: u128) -> Atom {
+        if n >> 64 == 0 {
+            Atom::new(stack, n as u64)
+        } else {
+            let words = [n as u64, (n >> 64) as u64];
+            unsafe { IndirectAtom::new_raw(stack, 2, words.as_ptr()).as_atom() }
         }
     }
 }
@@ -82,7 +977,7 @@ mod tests {
     use crate::jets::util::test::{assert_jet, assert_jet_err, init_context, A};
     use crate::jets::JetErr;
     use crate::noun::{Noun, D, T};
-    use ibig::ubig;
+    use ibig::{ubig, UBig};
     use sword_macros::tas;
 
     // Rust can't handle implicit conversions from u8 to u64
@@ -91,6 +986,41 @@ mod tests {
         D(b as u64)
     }
 
+    fn tape(c: &mut crate::interpreter::Context, text: &str) -> Noun {
+        let mut tape = D(0);
+        for byte in text.bytes().rev() {
+            tape = T(&mut c.stack, &[B(byte), tape]);
+        }
+        tape
+    }
+
+    fn cord(c: &mut crate::interpreter::Context, text: &str) -> Noun {
+        A(&mut c.stack, &UBig::from_le_bytes(text.as_bytes()))
+    }
+
+    fn assert_scow(c: &mut crate::interpreter::Context, aura: &[u8], atom: Noun, res: &str) {
+        let aura = A(&mut c.stack, &UBig::from_le_bytes(aura));
+        let sam = T(&mut c.stack, &[aura, atom]);
+        let res = tape(c, res);
+        assert_jet(c, jet_scow, sam, res);
+    }
+
+    fn assert_slaw(
+        c: &mut crate::interpreter::Context,
+        aura: &[u8],
+        text: &str,
+        res: Option<Noun>,
+    ) {
+        let aura = A(&mut c.stack, &UBig::from_le_bytes(aura));
+        let text = cord(c, text);
+        let sam = T(&mut c.stack, &[aura, text]);
+        let res = match res {
+            Some(atom) => T(&mut c.stack, &[D(0), atom]),
+            None => D(0),
+        };
+        assert_jet(c, jet_slaw, sam, res);
+    }
+
     #[test]
     fn test_scow() {
         let c = &mut init_context();
@@ -140,8 +1070,123 @@ mod tests {
             ],
         );
         assert_jet(c, jet_scow, sam, res);
-        let bad_aura = D(tas!(b"ux"));
+        let bad_aura = D(tas!(b"rs"));
         let sam = T(&mut c.stack, &[bad_aura, D(0)]);
         assert_jet_err(c, jet_scow, sam, JetErr::Punt);
     }
+
+    #[test]
+    fn test_scow_auras() {
+        let c = &mut init_context();
+
+        assert_scow(c, b"ux", D(0), "0x0");
+        assert_scow(c, b"ux", D(0x1_0000), "0x1.0000");
+        assert_scow(c, b"ux", D(0xdead_beef), "0xdead.beef");
+        let big = A(&mut c.stack, &ubig!(_0x10000000000000000));
+        assert_scow(c, b"ux", big, "0x1.0000.0000.0000.0000");
+        assert_scow(c, b"uv", D(0), "0v0");
+        assert_scow(c, b"uv", D(1 << 25), "0v1.00000");
+        assert_scow(c, b"uw", D(62), "0w-");
+        assert_scow(c, b"uw", D(63 << 6 | 36), "0w~A");
+
+        assert_scow(c, b"sd", D(0), "--0");
+        assert_scow(c, b"sd", D(1), "-1");
+        assert_scow(c, b"sd", D(2000), "--1.000");
+        let big = A(&mut c.stack, &ubig!(_0x10000000000000001));
+        assert_scow(c, b"sd", big, "-9.223.372.036.854.775.809");
+
+        assert_scow(c, b"p", D(0), "~zod");
+        assert_scow(c, b"p", D(256), "~marzod");
+        assert_scow(c, b"p", D(0x1_0000), "~dapnep-ronmyl");
+        assert_scow(c, b"p", D(1_624_961_343), "~sampel-palnet");
+        assert_scow(c, b"p", D(0x1_0000_0000), "~doznec-dozzod-dozzod");
+        let comet = A(&mut c.stack, &ubig!(_0x10000000000000000));
+        assert_scow(c, b"p", comet, "~doznec--dozzod-dozzod-dozzod-dozzod");
+
+        let unix = A(&mut c.stack, &ubig!(_0x8000000cce9e0d800000000000000000));
+        assert_scow(c, b"da", unix, "~1970.1.1");
+        let date = A(&mut c.stack, &ubig!(_0x8000000d29981bd21435000000000000));
+        assert_scow(c, b"da", date, "~2018.5.14..22.31.46..1435");
+        let date = A(&mut c.stack, &ubig!(_0x7ffffffe5529917f0000000000000001));
+        assert_scow(c, b"da", date, "~2-.12.31..23.59.59..0000.0000.0000.0001");
+
+        assert_scow(c, b"dr", D(0), "~s0");
+        let half = A(&mut c.stack, &ubig!(_0x8000000000000000));
+        assert_scow(c, b"dr", half, "~s0..8000");
+        let hour = A(&mut c.stack, &ubig!(_0xe100000000000000000));
+        assert_scow(c, b"dr", hour, "~h1");
+        let long = A(&mut c.stack, &ubig!(_0x15fcd0000000000000000));
+        assert_scow(c, b"dr", long, "~d1.h1.m1.s1");
+
+        let text = cord(c, "hello world");
+        assert_scow(c, b"t", text, "~~hello.world");
+        let text = cord(c, "A.b~c");
+        assert_scow(c, b"t", text, "~~~41.~.b~~c");
+        let text = cord(c, "é😀");
+        assert_scow(c, b"t", text, "~~~e9.~1f600.");
+        let text = cord(c, "foo-bar");
+        assert_scow(c, b"ta", text, "~.foo-bar");
+        assert_scow(c, b"tas", text, "foo-bar");
+    }
+
+    #[test]
+    fn test_slaw() {
+        let c = &mut init_context();
+
+        assert_slaw(c, b"ud", "0", Some(D(0)));
+        assert_slaw(c, b"ud", "1.000", Some(D(1000)));
+        assert_slaw(c, b"ud", "1000", None);
+        assert_slaw(c, b"ud", "01", None);
+        assert_slaw(c, b"ud", "1.00", None);
+        assert_slaw(c, b"ux", "0x1.0000", Some(D(0x1_0000)));
+        assert_slaw(c, b"ux", "0x0", Some(D(0)));
+        assert_slaw(c, b"ux", "0x.1", None);
+        let big = A(&mut c.stack, &ubig!(_0x10000000000000000));
+        assert_slaw(c, b"ux", "0x1.0000.0000.0000.0000", Some(big));
+        assert_slaw(c, b"uv", "0v1.00000", Some(D(1 << 25)));
+        assert_slaw(c, b"uw", "0w~A", Some(D(63 << 6 | 36)));
+        assert_slaw(c, b"sd", "--1.000", Some(D(2000)));
+        assert_slaw(c, b"sd", "-1", Some(D(1)));
+        assert_slaw(c, b"sd", "-0", Some(D(0)));
+        assert_slaw(c, b"sd", "1", None);
+
+        assert_slaw(c, b"p", "~zod", Some(D(0)));
+        assert_slaw(c, b"p", "~marzod", Some(D(256)));
+        assert_slaw(c, b"p", "~dozzod", None);
+        assert_slaw(c, b"p", "~sampel-palnet", Some(D(1_624_961_343)));
+        assert_slaw(c, b"p", "~doznec-dozzod-dozzod", Some(D(0x1_0000_0000)));
+        assert_slaw(c, b"p", "~sampel-palnet-", None);
+        let comet = A(&mut c.stack, &ubig!(_0x10000000000000000));
+        assert_slaw(c, b"p", "~doznec--dozzod-dozzod-dozzod-dozzod", Some(comet));
+        assert_slaw(c, b"p", "~doznec--dozzod-dozzod-dozzod", None);
+
+        let date = A(&mut c.stack, &ubig!(_0x8000000d29981bd21435000000000000));
+        assert_slaw(c, b"da", "~2018.5.14..22.31.46..1435", Some(date));
+        let unix = A(&mut c.stack, &ubig!(_0x8000000cce9e0d800000000000000000));
+        assert_slaw(c, b"da", "~1970.1.1", Some(unix));
+        assert_slaw(c, b"da", "~1970.13.1", None);
+        assert_slaw(c, b"da", "~1970.1.1..", None);
+        assert_slaw(c, b"da", "~2018.5.14..22.31.46..1435.", None);
+        assert_slaw(c, b"da", "~2018.5.14..22.31.46..1435.ffff.", None);
+        let long = A(&mut c.stack, &ubig!(_0x15fcd0000000000000000));
+        assert_slaw(c, b"dr", "~d1.h1.m1.s1", Some(long));
+        let half = A(&mut c.stack, &ubig!(_0x8000000000000000));
+        assert_slaw(c, b"dr", "~s0..8000", Some(half));
+        assert_slaw(c, b"dr", "~s0..8000.", None);
+        assert_slaw(c, b"dr", "~s1.s2", None);
+
+        let text = cord(c, "hello world");
+        assert_slaw(c, b"t", "~~hello.world", Some(text));
+        let text = cord(c, "a.b~c");
+        assert_slaw(c, b"t", "~~a~.b~~c", Some(text));
+        let text = cord(c, "foo-bar");
+        assert_slaw(c, b"ta", "~.foo-bar", Some(text));
+        assert_slaw(c, b"tas", "foo-bar", Some(text));
+        assert_slaw(c, b"tas", "Foo", None);
+
+        let sam = T(&mut c.stack, &[D(tas!(b"ux")), D(tas!(b"0xA"))]);
+        assert_jet_err(c, jet_slaw, sam, JetErr::Punt);
+        let sam = T(&mut c.stack, &[D(tas!(b"rs")), D(tas!(b".1"))]);
+        assert_jet_err(c, jet_slaw, sam, JetErr::Punt);
+    }
 }
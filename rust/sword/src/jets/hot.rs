@@ -62,11 +62,14 @@ pub const K_138: Either<&[u8], (u64, u64)> = Right((tas!(b"k"), 138));
 //     (&[A_50, Left(b"mink")], 1, jet_mink),
 // ];
 
+/** Cold state path of a jet, root first */
+pub type HotPath = &'static [Either<&'static [u8], (u64, u64)>];
+
 /**
  * (path, axis in battery, jet function pointer)
  * see the [Jet] typedef in sword::jets for the proper prototype
  */
-pub type HotEntry = (&'static [Either<&'static [u8], (u64, u64)>], u64, Jet);
+pub type HotEntry = (HotPath, u64, Jet);
 
 #[allow(clippy::complexity)]
 pub const URBIT_HOT_STATE: &[HotEntry] = &[
@@ -88,21 +91,71 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
     (&[K_139, Left(b"one"), Left(b"mas")], 1, jet_mas),
     (&[K_139, Left(b"one"), Left(b"peg")], 1, jet_peg),
     //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"find")],
+        1,
+        jet_find,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"flop")],
         1,
         jet_flop,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"into")],
+        1,
+        jet_into,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"lent")],
         1,
         jet_lent,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"murn")],
+        1,
+        jet_murn,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"oust")],
+        1,
+        jet_oust,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"reap")],
+        1,
+        jet_reap,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"reel")],
+        1,
+        jet_reel,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"roll")],
         1,
         jet_roll,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"scag")],
+        1,
+        jet_scag,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"skim")],
+        1,
+        jet_skim,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"skip")],
+        1,
+        jet_skip,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"slag")],
+        1,
+        jet_slag,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"snag")],
         1,
@@ -113,11 +166,31 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         1,
         jet_snip,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"sort")],
+        1,
+        jet_sort,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"spin")],
+        1,
+        jet_spin,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"turn")],
         1,
         jet_turn,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"weld")],
+        1,
+        jet_weld,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"welp")],
+        1,
+        jet_weld,
+    ),
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"zing")],
         1,
@@ -233,6 +306,113 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         jet_mor,
     ),
     //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"put")],
+        1,
+        jet_by_put,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"get")],
+        1,
+        jet_by_get,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"has")],
+        1,
+        jet_by_has,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"del")],
+        1,
+        jet_by_del,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"gas")],
+        1,
+        jet_by_gas,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"tap")],
+        1,
+        jet_by_tap,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"uni")],
+        1,
+        jet_by_uni,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"int")],
+        1,
+        jet_by_int,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"dif")],
+        1,
+        jet_by_dif,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"run")],
+        1,
+        jet_by_run,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"apt")],
+        1,
+        jet_by_apt,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"wyt")],
+        1,
+        jet_by_wyt,
+    ),
+    //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"put")],
+        1,
+        jet_in_put,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"has")],
+        1,
+        jet_in_has,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"del")],
+        1,
+        jet_in_del,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"gas")],
+        1,
+        jet_in_gas,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"tap")],
+        1,
+        jet_in_tap,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"uni")],
+        1,
+        jet_in_uni,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"int")],
+        1,
+        jet_in_int,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"dif")],
+        1,
+        jet_in_dif,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"wyt")],
+        1,
+        jet_in_wyt,
+    ),
+    //
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"cue")],
         1,
@@ -268,6 +448,311 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         jet_shal,
     ),
     //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"add")],
+        1,
+        jet_rh_add,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sub")],
+        1,
+        jet_rh_sub,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"mul")],
+        1,
+        jet_rh_mul,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"div")],
+        1,
+        jet_rh_div,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sqt")],
+        1,
+        jet_rh_sqt,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"fma")],
+        1,
+        jet_rh_fma,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"lth")],
+        1,
+        jet_rh_lth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"lte")],
+        1,
+        jet_rh_lte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"equ")],
+        1,
+        jet_rh_equ,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"gte")],
+        1,
+        jet_rh_gte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"gth")],
+        1,
+        jet_rh_gth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sun")],
+        1,
+        jet_rh_sun,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"san")],
+        1,
+        jet_rh_san,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"toi")],
+        1,
+        jet_rh_toi,
+    ),
+    //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"add")],
+        1,
+        jet_rs_add,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sub")],
+        1,
+        jet_rs_sub,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"mul")],
+        1,
+        jet_rs_mul,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"div")],
+        1,
+        jet_rs_div,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sqt")],
+        1,
+        jet_rs_sqt,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"fma")],
+        1,
+        jet_rs_fma,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"lth")],
+        1,
+        jet_rs_lth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"lte")],
+        1,
+        jet_rs_lte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"equ")],
+        1,
+        jet_rs_equ,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"gte")],
+        1,
+        jet_rs_gte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"gth")],
+        1,
+        jet_rs_gth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sun")],
+        1,
+        jet_rs_sun,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"san")],
+        1,
+        jet_rs_san,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"toi")],
+        1,
+        jet_rs_toi,
+    ),
+    //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"add")],
+        1,
+        jet_rd_add,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sub")],
+        1,
+        jet_rd_sub,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"mul")],
+        1,
+        jet_rd_mul,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"div")],
+        1,
+        jet_rd_div,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sqt")],
+        1,
+        jet_rd_sqt,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"fma")],
+        1,
+        jet_rd_fma,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"lth")],
+        1,
+        jet_rd_lth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"lte")],
+        1,
+        jet_rd_lte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"equ")],
+        1,
+        jet_rd_equ,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"gte")],
+        1,
+        jet_rd_gte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"gth")],
+        1,
+        jet_rd_gth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sun")],
+        1,
+        jet_rd_sun,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"san")],
+        1,
+        jet_rd_san,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"toi")],
+        1,
+        jet_rd_toi,
+    ),
+    //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"add")],
+        1,
+        jet_rq_add,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sub")],
+        1,
+        jet_rq_sub,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"mul")],
+        1,
+        jet_rq_mul,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"div")],
+        1,
+        jet_rq_div,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sqt")],
+        1,
+        jet_rq_sqt,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"fma")],
+        1,
+        jet_rq_fma,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"lth")],
+        1,
+        jet_rq_lth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"lte")],
+        1,
+        jet_rq_lte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"equ")],
+        1,
+        jet_rq_equ,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"gte")],
+        1,
+        jet_rq_gte,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"gth")],
+        1,
+        jet_rq_gth,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sun")],
+        1,
+        jet_rq_sun,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"san")],
+        1,
+        jet_rq_san,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"toi")],
+        1,
+        jet_rq_toi,
+    ),
+    //
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"fein")],
+        1,
+        jet_fein,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"fynd")],
+        1,
+        jet_fynd,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"feis")],
+        1,
+        jet_feis,
+    ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"tail")],
+        1,
+        jet_tail,
+    ),
+    //
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"sha"), Left(b"sha1")],
         1,
@@ -497,6 +982,11 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         1,
         jet_scow,
     ),
+    (
+        &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"qua"), Left(b"slaw")],
+        1,
+        jet_slaw,
+    ),
     //
     (
         &[K_139, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"qua"), Left(b"mink")],
@@ -802,21 +1292,71 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
     (&[K_138, Left(b"one"), Left(b"mas")], 1, jet_mas),
     (&[K_138, Left(b"one"), Left(b"peg")], 1, jet_peg),
     //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"find")],
+        1,
+        jet_find,
+    ),
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"flop")],
         1,
         jet_flop,
     ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"into")],
+        1,
+        jet_into,
+    ),
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"lent")],
         1,
         jet_lent,
     ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"murn")],
+        1,
+        jet_murn,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"oust")],
+        1,
+        jet_oust,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"reap")],
+        1,
+        jet_reap,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"reel")],
+        1,
+        jet_reel,
+    ),
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"roll")],
         1,
         jet_roll,
     ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"scag")],
+        1,
+        jet_scag,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"skim")],
+        1,
+        jet_skim,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"skip")],
+        1,
+        jet_skip,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"slag")],
+        1,
+        jet_slag,
+    ),
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"snag")],
         1,
@@ -825,12 +1365,32 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"snip")],
         1,
-        jet_snip,
+        jet_snip,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"sort")],
+        1,
+        jet_sort,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"spin")],
+        1,
+        jet_spin,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"turn")],
+        1,
+        jet_turn,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"weld")],
+        1,
+        jet_weld,
     ),
     (
-        &[K_138, Left(b"one"), Left(b"two"), Left(b"turn")],
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"welp")],
         1,
-        jet_turn,
+        jet_weld,
     ),
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"zing")],
@@ -947,6 +1507,113 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         jet_mor,
     ),
     //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"put")],
+        1,
+        jet_by_put,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"get")],
+        1,
+        jet_by_get,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"has")],
+        1,
+        jet_by_has,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"del")],
+        1,
+        jet_by_del,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"gas")],
+        1,
+        jet_by_gas,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"tap")],
+        1,
+        jet_by_tap,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"uni")],
+        1,
+        jet_by_uni,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"int")],
+        1,
+        jet_by_int,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"dif")],
+        1,
+        jet_by_dif,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"run")],
+        1,
+        jet_by_run,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"apt")],
+        1,
+        jet_by_apt,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"by"), Left(b"wyt")],
+        1,
+        jet_by_wyt,
+    ),
+    //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"put")],
+        1,
+        jet_in_put,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"has")],
+        1,
+        jet_in_has,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"del")],
+        1,
+        jet_in_del,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"gas")],
+        1,
+        jet_in_gas,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"tap")],
+        1,
+        jet_in_tap,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"uni")],
+        1,
+        jet_in_uni,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"int")],
+        1,
+        jet_in_int,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"dif")],
+        1,
+        jet_in_dif,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"in"), Left(b"wyt")],
+        1,
+        jet_in_wyt,
+    ),
+    //
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"cue")],
         1,
@@ -982,6 +1649,311 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         jet_shal,
     ),
     //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"add")],
+        1,
+        jet_rh_add,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sub")],
+        1,
+        jet_rh_sub,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"mul")],
+        1,
+        jet_rh_mul,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"div")],
+        1,
+        jet_rh_div,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sqt")],
+        1,
+        jet_rh_sqt,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"fma")],
+        1,
+        jet_rh_fma,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"lth")],
+        1,
+        jet_rh_lth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"lte")],
+        1,
+        jet_rh_lte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"equ")],
+        1,
+        jet_rh_equ,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"gte")],
+        1,
+        jet_rh_gte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"gth")],
+        1,
+        jet_rh_gth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"sun")],
+        1,
+        jet_rh_sun,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"san")],
+        1,
+        jet_rh_san,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rh"), Left(b"toi")],
+        1,
+        jet_rh_toi,
+    ),
+    //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"add")],
+        1,
+        jet_rs_add,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sub")],
+        1,
+        jet_rs_sub,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"mul")],
+        1,
+        jet_rs_mul,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"div")],
+        1,
+        jet_rs_div,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sqt")],
+        1,
+        jet_rs_sqt,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"fma")],
+        1,
+        jet_rs_fma,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"lth")],
+        1,
+        jet_rs_lth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"lte")],
+        1,
+        jet_rs_lte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"equ")],
+        1,
+        jet_rs_equ,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"gte")],
+        1,
+        jet_rs_gte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"gth")],
+        1,
+        jet_rs_gth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"sun")],
+        1,
+        jet_rs_sun,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"san")],
+        1,
+        jet_rs_san,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rs"), Left(b"toi")],
+        1,
+        jet_rs_toi,
+    ),
+    //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"add")],
+        1,
+        jet_rd_add,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sub")],
+        1,
+        jet_rd_sub,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"mul")],
+        1,
+        jet_rd_mul,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"div")],
+        1,
+        jet_rd_div,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sqt")],
+        1,
+        jet_rd_sqt,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"fma")],
+        1,
+        jet_rd_fma,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"lth")],
+        1,
+        jet_rd_lth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"lte")],
+        1,
+        jet_rd_lte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"equ")],
+        1,
+        jet_rd_equ,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"gte")],
+        1,
+        jet_rd_gte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"gth")],
+        1,
+        jet_rd_gth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"sun")],
+        1,
+        jet_rd_sun,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"san")],
+        1,
+        jet_rd_san,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rd"), Left(b"toi")],
+        1,
+        jet_rd_toi,
+    ),
+    //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"add")],
+        1,
+        jet_rq_add,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sub")],
+        1,
+        jet_rq_sub,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"mul")],
+        1,
+        jet_rq_mul,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"div")],
+        1,
+        jet_rq_div,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sqt")],
+        1,
+        jet_rq_sqt,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"fma")],
+        1,
+        jet_rq_fma,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"lth")],
+        1,
+        jet_rq_lth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"lte")],
+        1,
+        jet_rq_lte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"equ")],
+        1,
+        jet_rq_equ,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"gte")],
+        1,
+        jet_rq_gte,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"gth")],
+        1,
+        jet_rq_gth,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"sun")],
+        1,
+        jet_rq_sun,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"san")],
+        1,
+        jet_rq_san,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"rq"), Left(b"toi")],
+        1,
+        jet_rq_toi,
+    ),
+    //
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"fein")],
+        1,
+        jet_fein,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"fynd")],
+        1,
+        jet_fynd,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"feis")],
+        1,
+        jet_feis,
+    ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"ob"), Left(b"tail")],
+        1,
+        jet_tail,
+    ),
+    //
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"sha"), Left(b"sha1")],
         1,
@@ -1211,6 +2183,11 @@ pub const URBIT_HOT_STATE: &[HotEntry] = &[
         1,
         jet_scow,
     ),
+    (
+        &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"qua"), Left(b"slaw")],
+        1,
+        jet_slaw,
+    ),
     //
     (
         &[K_138, Left(b"one"), Left(b"two"), Left(b"tri"), Left(b"qua"), Left(b"mink")],
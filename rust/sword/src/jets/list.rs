@@ -1,23 +1,108 @@
 /** Text processing jets
  */
 use crate::interpreter::Context;
-use crate::jets::util::{slot, BAIL_FAIL};
+use crate::jets::util::{slot, BAIL_EXIT, BAIL_FAIL};
 use crate::jets::Result;
 use crate::noun::{Cell, Noun, D, T};
 use crate::site::{site_slam, Site};
 
 crate::gdb!();
 
+pub fn jet_find(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let nedl = slot(sam, 2)?;
+    let hstk = slot(sam, 3)?;
+
+    util::find(&mut context.stack, nedl, hstk)
+}
+
 pub fn jet_flop(context: &mut Context, subject: Noun) -> Result {
     let sam = slot(subject, 6)?;
     util::flop(&mut context.stack, sam)
 }
 
+pub fn jet_into(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let list = slot(sam, 2)?;
+    let index = slot(sam, 6)?;
+    let item = slot(sam, 7)?;
+
+    util::into(&mut context.stack, list, index, item)
+}
+
 pub fn jet_lent(_context: &mut Context, subject: Noun) -> Result {
     let list = slot(subject, 6)?;
     util::lent(list).map(|x| D(x as u64))
 }
 
+pub fn jet_murn(context: &mut Context, subject: Noun) -> Result {
+    let sample = slot(subject, 6)?;
+    let mut list = slot(sample, 2)?;
+    let mut gate = slot(sample, 3)?;
+    let mut res = D(0);
+    let mut dest: *mut Noun = &mut res;
+
+    let site = Site::new(context, &mut gate);
+    loop {
+        if let Ok(list_cell) = list.as_cell() {
+            list = list_cell.tail();
+            let unit = site_slam(context, &site, list_cell.head())?;
+            if unsafe { unit.raw_equals(D(0)) } {
+                continue;
+            }
+            let item = unit.as_cell()?.tail();
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(&mut context.stack);
+                (*new_mem).head = item;
+                *dest = new_cell.as_noun();
+                dest = &mut (*new_mem).tail;
+            }
+        } else {
+            if unsafe { !list.raw_equals(D(0)) } {
+                return Err(BAIL_EXIT);
+            }
+            unsafe {
+                *dest = D(0);
+            };
+            return Ok(res);
+        }
+    }
+}
+
+pub fn jet_oust(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let index = slot(sam, 4)?;
+    let count = slot(sam, 5)?;
+    let list = slot(sam, 3)?;
+
+    util::oust(&mut context.stack, index, count, list)
+}
+
+pub fn jet_reap(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let count = slot(sam, 2)?;
+    let item = slot(sam, 3)?;
+
+    util::reap(&mut context.stack, count, item)
+}
+
+pub fn jet_reel(context: &mut Context, subject: Noun) -> Result {
+    let sample = slot(subject, 6)?;
+    let list = slot(sample, 2)?;
+    let mut gate = slot(sample, 3)?;
+    let mut prod = slot(gate, 13)?;
+
+    // +reel folds from the right, so walk the list backwards
+    let mut tsil = util::flop(&mut context.stack, list)?;
+    let site = Site::new(context, &mut gate);
+    while let Ok(tsil_cell) = tsil.as_cell() {
+        tsil = tsil_cell.tail();
+        let sam = T(&mut context.stack, &[tsil_cell.head(), prod]);
+        prod = site_slam(context, &site, sam)?;
+    }
+    Ok(prod)
+}
+
 pub fn jet_roll(context: &mut Context, subject: Noun) -> Result {
     let sample = slot(subject, 6)?;
     let mut list = slot(sample, 2)?;
@@ -39,6 +124,64 @@ pub fn jet_roll(context: &mut Context, subject: Noun) -> Result {
     }
 }
 
+pub fn jet_scag(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let count = slot(sam, 2)?;
+    let list = slot(sam, 3)?;
+
+    util::scag(&mut context.stack, count, list)
+}
+
+pub fn jet_skim(context: &mut Context, subject: Noun) -> Result {
+    skim(context, subject, true)
+}
+
+pub fn jet_skip(context: &mut Context, subject: Noun) -> Result {
+    skim(context, subject, false)
+}
+
+/// Filter a list by a gate, keeping the items for which it produces `keep`
+fn skim(context: &mut Context, subject: Noun, keep: bool) -> Result {
+    let sample = slot(subject, 6)?;
+    let mut list = slot(sample, 2)?;
+    let mut gate = slot(sample, 3)?;
+    let mut res = D(0);
+    let mut dest: *mut Noun = &mut res;
+
+    let site = Site::new(context, &mut gate);
+    loop {
+        if let Ok(list_cell) = list.as_cell() {
+            list = list_cell.tail();
+            let test = site_slam(context, &site, list_cell.head())?;
+            if util::loobean(test)? != keep {
+                continue;
+            }
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(&mut context.stack);
+                (*new_mem).head = list_cell.head();
+                *dest = new_cell.as_noun();
+                dest = &mut (*new_mem).tail;
+            }
+        } else {
+            if unsafe { !list.raw_equals(D(0)) } {
+                return Err(BAIL_EXIT);
+            }
+            unsafe {
+                *dest = D(0);
+            };
+            return Ok(res);
+        }
+    }
+}
+
+pub fn jet_slag(_context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let count = slot(sam, 2)?;
+    let list = slot(sam, 3)?;
+
+    util::slag(count, list)
+}
+
 pub fn jet_snag(_context: &mut Context, subject: Noun) -> Result {
     let sam = slot(subject, 6)?;
     let index = slot(sam, 2)?;
@@ -52,6 +195,115 @@ pub fn jet_snip(context: &mut Context, subject: Noun) -> Result {
     util::snip(&mut context.stack, list)
 }
 
+pub fn jet_sort(context: &mut Context, subject: Noun) -> Result {
+    let sample = slot(subject, 6)?;
+    let list = slot(sample, 2)?;
+    let mut gate = slot(sample, 3)?;
+    let mut res = D(0);
+    let mut dest: *mut Noun = &mut res;
+
+    // +sort is a quicksort on the head of each sublist, which we must follow exactly to produce
+    // the same list for a gate which is not a strict order. Rather than recurse, keep a list of
+    // pending work on the NockStack: [0 list] sorts a sublist and [1 item] appends an item.
+    let first = T(&mut context.stack, &[D(0), list]);
+    let mut work = T(&mut context.stack, &[first, D(0)]);
+
+    let site = Site::new(context, &mut gate);
+    while let Ok(work_cell) = work.as_cell() {
+        work = work_cell.tail();
+        let task = work_cell.head().as_cell()?;
+        if unsafe { task.head().raw_equals(D(1)) } {
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(&mut context.stack);
+                (*new_mem).head = task.tail();
+                *dest = new_cell.as_noun();
+                dest = &mut (*new_mem).tail;
+            }
+            continue;
+        }
+
+        let sublist = task.tail();
+        if unsafe { sublist.raw_equals(D(0)) } {
+            continue;
+        }
+        let sublist_cell = sublist.as_cell()?;
+        let pivot = sublist_cell.head();
+
+        // Split the rest of the sublist, in order, into the items the gate puts before the pivot
+        // and the rest, as +skid does
+        let mut rest = sublist_cell.tail();
+        let mut before = D(0);
+        let mut after = D(0);
+        let mut before_dest: *mut Noun = &mut before;
+        let mut after_dest: *mut Noun = &mut after;
+        while let Ok(rest_cell) = rest.as_cell() {
+            rest = rest_cell.tail();
+            let sam = T(&mut context.stack, &[rest_cell.head(), pivot]);
+            let test = site_slam(context, &site, sam)?;
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(&mut context.stack);
+                (*new_mem).head = rest_cell.head();
+                if util::loobean(test)? {
+                    *before_dest = new_cell.as_noun();
+                    before_dest = &mut (*new_mem).tail;
+                } else {
+                    *after_dest = new_cell.as_noun();
+                    after_dest = &mut (*new_mem).tail;
+                }
+            }
+        }
+        if unsafe { !rest.raw_equals(D(0)) } {
+            return Err(BAIL_EXIT);
+        }
+        unsafe {
+            *before_dest = D(0);
+            *after_dest = D(0);
+        }
+
+        let before = T(&mut context.stack, &[D(0), before]);
+        let pivot = T(&mut context.stack, &[D(1), pivot]);
+        let after = T(&mut context.stack, &[D(0), after]);
+        work = T(&mut context.stack, &[before, pivot, after, work]);
+    }
+    unsafe {
+        *dest = D(0);
+    }
+    Ok(res)
+}
+
+pub fn jet_spin(context: &mut Context, subject: Noun) -> Result {
+    let sample = slot(subject, 6)?;
+    let mut list = slot(sample, 2)?;
+    let mut state = slot(sample, 6)?;
+    let mut gate = slot(sample, 7)?;
+    let mut res = D(0);
+    let mut dest: *mut Noun = &mut res;
+
+    let site = Site::new(context, &mut gate);
+    loop {
+        if let Ok(list_cell) = list.as_cell() {
+            list = list_cell.tail();
+            let sam = T(&mut context.stack, &[list_cell.head(), state]);
+            let prod = site_slam(context, &site, sam)?.as_cell()?;
+            state = prod.tail();
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(&mut context.stack);
+                (*new_mem).head = prod.head();
+                *dest = new_cell.as_noun();
+                dest = &mut (*new_mem).tail;
+            }
+        } else {
+            if unsafe { !list.raw_equals(D(0)) } {
+                return Err(BAIL_EXIT);
+            }
+            unsafe {
+                *dest = D(0);
+            };
+            return Ok(T(&mut context.stack, &[res, state]));
+        }
+    }
+}
+
 pub fn jet_turn(context: &mut Context, subject: Noun) -> Result {
     let sample = slot(subject, 6)?;
     let mut list = slot(sample, 2)?;
@@ -83,6 +335,15 @@ pub fn jet_turn(context: &mut Context, subject: Noun) -> Result {
     }
 }
 
+/// +welp differs from +weld only in its type
+pub fn jet_weld(context: &mut Context, subject: Noun) -> Result {
+    let sam = slot(subject, 6)?;
+    let a = slot(sam, 2)?;
+    let b = slot(sam, 3)?;
+
+    util::weld(&mut context.stack, a, b)
+}
+
 pub fn jet_zing(context: &mut Context, subject: Noun) -> Result {
     let list = slot(subject, 6)?;
     let stack = &mut context.stack;
@@ -91,10 +352,11 @@ pub fn jet_zing(context: &mut Context, subject: Noun) -> Result {
 }
 
 pub mod util {
-    use crate::jets::util::BAIL_EXIT;
+    use crate::jets::util::{BAIL_EXIT, BAIL_FAIL};
     use crate::jets::{JetErr, Result};
     use crate::mem::NockStack;
-    use crate::noun::{Cell, Noun, D, T};
+    use crate::noun::{Cell, Noun, D, NO, T, YES};
+    use crate::unifying_equality::unifying_equality;
     use std::result;
 
     /// Reverse order of list
@@ -207,6 +469,126 @@ pub mod util {
             Ok(res)
         }
     }
+
+    /// First index at which `nedl` occurs in `hstk`, as a unit
+    pub fn find(stack: &mut NockStack, nedl: Noun, mut hstk: Noun) -> Result {
+        let mut index = 0u64;
+        loop {
+            let mut n = nedl;
+            let mut h = hstk;
+            loop {
+                if unsafe { n.raw_equals(D(0)) || h.raw_equals(D(0)) } {
+                    return Ok(D(0));
+                }
+                let n_cell = n.as_cell()?;
+                let h_cell = h.as_cell()?;
+                let mut n_head = n_cell.head();
+                let mut h_head = h_cell.head();
+                if unsafe { !unifying_equality(stack, &mut n_head, &mut h_head) } {
+                    break;
+                }
+                if unsafe { n_cell.tail().raw_equals(D(0)) } {
+                    return Ok(T(stack, &[D(0), D(index)]));
+                }
+                n = n_cell.tail();
+                h = h_cell.tail();
+            }
+            hstk = hstk.as_cell()?.tail();
+            index += 1;
+        }
+    }
+
+    /// Insert `item` before position `index`
+    pub fn into(stack: &mut NockStack, list: Noun, index: Noun, item: Noun) -> Result {
+        let count = count(index)?;
+        let rest = slag(index, list)?;
+        let tail = T(stack, &[item, rest]);
+        copy(stack, list, count, tail)
+    }
+
+    /// Remove `count` items starting at position `index`
+    pub fn oust(stack: &mut NockStack, index: Noun, count: Noun, list: Noun) -> Result {
+        let index = self::count(index)?;
+        let skip = index.saturating_add(self::count(count)?);
+        let rest = after(list, skip)?;
+        copy(stack, list, index, rest)
+    }
+
+    /// List of `count` copies of `item`
+    pub fn reap(stack: &mut NockStack, count: Noun, item: Noun) -> Result {
+        // Too many cells to fit in memory
+        let count = count.as_atom()?.as_u64().map_err(|_| BAIL_FAIL)?;
+        let mut res = D(0);
+        for _ in 0..count {
+            res = T(stack, &[item, res]);
+        }
+        Ok(res)
+    }
+
+    /// First `count` items of a list
+    pub fn scag(stack: &mut NockStack, count: Noun, list: Noun) -> Result {
+        let count = self::count(count)?;
+        copy(stack, list, count, D(0))
+    }
+
+    /// List without its first `count` items
+    pub fn slag(count: Noun, list: Noun) -> Result {
+        after(list, self::count(count)?)
+    }
+
+    /// Concatenate two lists
+    pub fn weld(stack: &mut NockStack, a: Noun, b: Noun) -> Result {
+        copy(stack, a, u64::MAX, b)
+    }
+
+    /// Read a loobean, crashing on any other noun as ?: does
+    pub fn loobean(noun: Noun) -> result::Result<bool, JetErr> {
+        unsafe {
+            if noun.raw_equals(YES) {
+                Ok(true)
+            } else if noun.raw_equals(NO) {
+                Ok(false)
+            } else {
+                Err(BAIL_EXIT)
+            }
+        }
+    }
+
+    /// A count of list items: no list is long enough for an indirect atom to differ from infinity
+    fn count(count: Noun) -> result::Result<u64, JetErr> {
+        Ok(count.as_atom()?.as_u64().unwrap_or(u64::MAX))
+    }
+
+    /// Copy up to `count` items from the front of `list` onto `tail`
+    fn copy(stack: &mut NockStack, mut list: Noun, mut count: u64, tail: Noun) -> Result {
+        let mut res = D(0);
+        let mut dest = &mut res as *mut Noun;
+        while count > 0 && unsafe { !list.raw_equals(D(0)) } {
+            let cell = list.as_cell()?;
+            unsafe {
+                let (new_cell, new_mem) = Cell::new_raw_mut(stack);
+                (*new_mem).head = cell.head();
+                *dest = new_cell.as_noun();
+                dest = &mut (*new_mem).tail;
+            }
+            list = cell.tail();
+            count -= 1;
+        }
+        unsafe { *dest = tail };
+        Ok(res)
+    }
+
+    /// Drop up to `count` items from the front of `list`, giving ~ if it runs out
+    fn after(mut list: Noun, mut count: u64) -> Result {
+        while count > 0 {
+            if unsafe { list.raw_equals(D(0)) } {
+                return Ok(D(0));
+            }
+            list = list.as_cell()?.tail();
+            count -= 1;
+        }
+        Ok(list)
+    }
 }
 
 #[cfg(test)]
@@ -216,6 +598,12 @@ mod tests {
     use crate::jets::util::BAIL_EXIT;
     use crate::noun::{D, T};
 
+    /// A gate whose battery is `formula`, with default sample `sam`
+    fn gate(c: &mut Context, formula: &[Noun], sam: Noun) -> Noun {
+        let formula = T(&mut c.stack, formula);
+        T(&mut c.stack, &[formula, sam, D(0)])
+    }
+
     #[test]
     fn test_flop() {
         let c = &mut init_context();
@@ -327,4 +715,175 @@ mod tests {
         let sam = T(&mut c.stack, &[list_1, list_2, D(0)]);
         assert_jet(c, jet_zing, sam, list_3);
     }
+
+    #[test]
+    fn test_find() {
+        let c = &mut init_context();
+        let hstk = T(&mut c.stack, &[D(1), D(2), D(3), D(2), D(3), D(0)]);
+
+        let nedl = T(&mut c.stack, &[D(2), D(3), D(0)]);
+        let sam = T(&mut c.stack, &[nedl, hstk]);
+        let res = T(&mut c.stack, &[D(0), D(1)]);
+        assert_jet(c, jet_find, sam, res);
+
+        let nedl = T(&mut c.stack, &[D(3), D(1), D(0)]);
+        let sam = T(&mut c.stack, &[nedl, hstk]);
+        assert_jet(c, jet_find, sam, D(0));
+
+        let sam = T(&mut c.stack, &[D(0), hstk]);
+        assert_jet(c, jet_find, sam, D(0));
+    }
+
+    #[test]
+    fn test_weld() {
+        let c = &mut init_context();
+        let a = T(&mut c.stack, &[D(1), D(2), D(0)]);
+        let b = T(&mut c.stack, &[D(3), D(0)]);
+
+        let sam = T(&mut c.stack, &[a, b]);
+        let res = T(&mut c.stack, &[D(1), D(2), D(3), D(0)]);
+        assert_jet(c, jet_weld, sam, res);
+        let sam = T(&mut c.stack, &[D(0), b]);
+        assert_jet(c, jet_weld, sam, b);
+        let sam = T(&mut c.stack, &[a, D(0)]);
+        assert_jet(c, jet_weld, sam, a);
+
+        let bad = T(&mut c.stack, &[D(1), D(2)]);
+        let sam = T(&mut c.stack, &[bad, b]);
+        assert_jet_err(c, jet_weld, sam, BAIL_EXIT);
+    }
+
+    #[test]
+    fn test_scag_slag() {
+        let c = &mut init_context();
+        let list = T(&mut c.stack, &[D(1), D(2), D(3), D(0)]);
+
+        let sam = T(&mut c.stack, &[D(2), list]);
+        let res = T(&mut c.stack, &[D(1), D(2), D(0)]);
+        assert_jet(c, jet_scag, sam, res);
+        let res = T(&mut c.stack, &[D(3), D(0)]);
+        assert_jet(c, jet_slag, sam, res);
+
+        let sam = T(&mut c.stack, &[D(0), list]);
+        assert_jet(c, jet_scag, sam, D(0));
+        assert_jet(c, jet_slag, sam, list);
+
+        let sam = T(&mut c.stack, &[D(5), list]);
+        assert_jet(c, jet_scag, sam, list);
+        assert_jet(c, jet_slag, sam, D(0));
+    }
+
+    #[test]
+    fn test_reap() {
+        let c = &mut init_context();
+        let item = T(&mut c.stack, &[D(1), D(2)]);
+
+        let sam = T(&mut c.stack, &[D(3), item]);
+        let res = T(&mut c.stack, &[item, item, item, D(0)]);
+        assert_jet(c, jet_reap, sam, res);
+        let sam = T(&mut c.stack, &[D(0), item]);
+        assert_jet(c, jet_reap, sam, D(0));
+    }
+
+    #[test]
+    fn test_oust_into() {
+        let c = &mut init_context();
+        let list = T(&mut c.stack, &[D(1), D(2), D(3), D(4), D(0)]);
+
+        let range = T(&mut c.stack, &[D(1), D(2)]);
+        let sam = T(&mut c.stack, &[range, list]);
+        let res = T(&mut c.stack, &[D(1), D(4), D(0)]);
+        assert_jet(c, jet_oust, sam, res);
+
+        let range = T(&mut c.stack, &[D(3), D(9)]);
+        let sam = T(&mut c.stack, &[range, list]);
+        let res = T(&mut c.stack, &[D(1), D(2), D(3), D(0)]);
+        assert_jet(c, jet_oust, sam, res);
+
+        let sam = T(&mut c.stack, &[list, D(2), D(9)]);
+        let res = T(&mut c.stack, &[D(1), D(2), D(9), D(3), D(4), D(0)]);
+        assert_jet(c, jet_into, sam, res);
+
+        let sam = T(&mut c.stack, &[list, D(9), D(9)]);
+        let res = T(&mut c.stack, &[D(1), D(2), D(3), D(4), D(9), D(0)]);
+        assert_jet(c, jet_into, sam, res);
+    }
+
+    #[test]
+    fn test_skim_skip_murn() {
+        let c = &mut init_context();
+        let list = T(&mut c.stack, &[D(0), D(1), D(0), D(2), D(0)]);
+        let zero = T(&mut c.stack, &[D(1), D(0)]);
+        let sam_axis = T(&mut c.stack, &[D(0), D(6)]);
+
+        // =(0 a)
+        let test = T(&mut c.stack, &[D(5), zero, sam_axis]);
+        let is_zero = gate(c, &[D(5), zero, sam_axis], D(0));
+        let sam = T(&mut c.stack, &[list, is_zero]);
+        let res = T(&mut c.stack, &[D(0), D(0), D(0)]);
+        assert_jet(c, jet_skim, sam, res);
+        let res = T(&mut c.stack, &[D(1), D(2), D(0)]);
+        assert_jet(c, jet_skip, sam, res);
+
+        // ?:(=(0 a) ~ `+(a))
+        let inc = T(&mut c.stack, &[D(4), D(0), D(6)]);
+        let some = T(&mut c.stack, &[zero, inc]);
+        let unit = gate(c, &[D(6), test, zero, some], D(0));
+        let sam = T(&mut c.stack, &[list, unit]);
+        let res = T(&mut c.stack, &[D(2), D(3), D(0)]);
+        assert_jet(c, jet_murn, sam, res);
+
+        // Gates which don't produce loobeans crash
+        let inc = gate(c, &[D(4), D(0), D(6)], D(0));
+        let sam = T(&mut c.stack, &[list, inc]);
+        assert_jet_err(c, jet_skim, sam, BAIL_EXIT);
+    }
+
+    #[test]
+    fn test_reel_spin() {
+        let c = &mut init_context();
+        let list = T(&mut c.stack, &[D(1), D(2), D(3), D(0)]);
+        let item = T(&mut c.stack, &[D(0), D(12)]);
+        let acc = T(&mut c.stack, &[D(0), D(13)]);
+
+        // [acc item], from the right
+        let init = T(&mut c.stack, &[D(0), D(0)]);
+        let swap = gate(c, &[acc, item], init);
+        let sam = T(&mut c.stack, &[list, swap]);
+        let res = T(&mut c.stack, &[D(0), D(3)]);
+        let res = T(&mut c.stack, &[res, D(2)]);
+        let res = T(&mut c.stack, &[res, D(1)]);
+        assert_jet(c, jet_reel, sam, res);
+
+        // [[item state] +(state)]
+        let pair = T(&mut c.stack, &[item, acc]);
+        let next = T(&mut c.stack, &[D(4), D(0), D(13)]);
+        let count = gate(c, &[pair, next], D(0));
+        let sam = T(&mut c.stack, &[list, D(5), count]);
+        let one = T(&mut c.stack, &[D(1), D(5)]);
+        let two = T(&mut c.stack, &[D(2), D(6)]);
+        let three = T(&mut c.stack, &[D(3), D(7)]);
+        let res = T(&mut c.stack, &[one, two, three, D(0)]);
+        let res = T(&mut c.stack, &[res, D(8)]);
+        assert_jet(c, jet_spin, sam, res);
+    }
+
+    #[test]
+    fn test_sort() {
+        let c = &mut init_context();
+        let zero = T(&mut c.stack, &[D(1), D(0)]);
+        let one = T(&mut c.stack, &[D(1), D(1)]);
+        let head = T(&mut c.stack, &[D(0), D(12)]);
+
+        // =(0 a): not a strict order, but +sort still produces a definite list
+        let test = T(&mut c.stack, &[D(5), zero, head]);
+        let zero_first = gate(c, &[D(6), test, zero, one], D(0));
+        let list = T(&mut c.stack, &[D(1), D(0), D(2), D(0), D(3), D(0)]);
+        let sam = T(&mut c.stack, &[list, zero_first]);
+        let res = T(&mut c.stack, &[D(0), D(0), D(1), D(2), D(3), D(0)]);
+        assert_jet(c, jet_sort, sam, res);
+
+        let sam = T(&mut c.stack, &[D(0), zero_first]);
+        assert_jet(c, jet_sort, sam, D(0));
+    }
 }
@@ -115,9 +115,8 @@ pub fn jet_sivc_de(context: &mut Context, subject: Noun) -> Result {
 
 mod util {
     use crate::jets::bits::util::met;
-    use crate::jets::list;
     use crate::jets::util::BAIL_FAIL;
-    use crate::jets::{JetErr, Result};
+    use crate::jets::{list, JetErr, Result};
     use crate::mem::NockStack;
     use crate::noun::{Atom, IndirectAtom, Noun, D, T};
     use std::result;
@@ -70,7 +70,6 @@ pub fn jet_mute(context: &mut Context, subject: Noun) -> Result {
 }
 
 pub mod util {
-    use crate::hamt::Hamt;
     use crate::interpreter::{interpret, Context, Error, Mote};
     use crate::jets;
     use crate::jets::bits::util::rip;
@@ -149,28 +148,35 @@ pub mod util {
         let cache_snapshot = context.cache;
         let scry_snapshot = context.scry_stack;
 
-        context.cache = Hamt::<Noun>::new(&mut context.stack);
+        context.cache.clear(&mut context.stack);
         context.scry_stack = T(&mut context.stack, &[scry, context.scry_stack]);
 
         match interpret(context, subject, formula) {
             Ok(res) => {
-                context.cache = cache_snapshot;
+                context.cache.rewind(cache_snapshot);
                 context.scry_stack = scry_snapshot;
                 Ok(T(&mut context.stack, &[D(0), res]))
             }
             Err(err) => match err {
                 Error::ScryBlocked(path) => {
-                    context.cache = cache_snapshot;
+                    context.cache.rewind(cache_snapshot);
                     context.scry_stack = scry_snapshot;
                     Ok(T(&mut context.stack, &[D(1), path]))
                 }
+                Error::Deterministic(Mote::Fuel, _) => {
+                    // Running out of fuel is not a crash the virtualized code may observe: it must
+                    // reach whoever set the budget (see mink_fuel)
+                    context.cache.rewind(cache_snapshot);
+                    context.scry_stack = scry_snapshot;
+                    Err(err)
+                }
                 Error::Deterministic(_, trace) => {
-                    context.cache = cache_snapshot;
+                    context.cache.rewind(cache_snapshot);
                     context.scry_stack = scry_snapshot;
                     Ok(T(&mut context.stack, &[D(2), trace]))
                 }
                 Error::ScryCrashed(trace) => {
-                    context.cache = cache_snapshot;
+                    context.cache.rewind(cache_snapshot);
                     // When we enter a +mink call, we record the state of the scry handler stack at the
                     // time (i.e. the Noun representing (list scry)). Each scry will pop the head off of
                     // this scry handler stack and calls interpret(), using the rest of the scry handler
@@ -194,7 +200,7 @@ pub mod util {
                     // We choose to restore the cache and scry stack even on NonDeterministic errors
                     // to keep the logic all in one place (as opposed to having the serf reset them
                     // manually ONLY for NonDeterministic errors).
-                    context.cache = cache_snapshot;
+                    context.cache.rewind(cache_snapshot);
                     context.scry_stack = scry_snapshot;
                     Err(err)
                 }
@@ -202,6 +208,30 @@ pub mod util {
         }
     }
 
+    /** +mink with a step budget
+     *
+     * Runs at most `budget` steps of Nock (see Context::fuel), and also no more than any budget
+     * already in force. Produces the $tone along with the fuel consumed, or
+     * Error::Deterministic(Mote::Fuel, _) if the budget ran out.
+     */
+    pub fn mink_fuel(
+        context: &mut Context,
+        subject: Noun,
+        formula: Noun,
+        scry: Noun,
+        budget: u64,
+    ) -> Result<(Noun, u64), Error> {
+        let outer = context.fuel;
+        let limit = outer.map_or(budget, |fuel| fuel.min(budget));
+
+        context.fuel = Some(limit);
+        let res = mink(context, subject, formula, scry);
+        let used = limit - context.fuel.unwrap_or(0);
+        context.fuel = outer.map(|fuel| fuel - used);
+
+        res.map(|tone| (tone, used))
+    }
+
     /** Consume $tone, produce $toon
      */
     //  XX: should write a jet_mook wrapper for this function
@@ -378,7 +408,10 @@ pub mod util {
                 list = cell.tail();
             }
 
-            *dest = D(0);
+            // When flopping, dest still points at res, which is already terminated
+            if !flop {
+                *dest = D(0);
+            }
             let toon = Cell::new(&mut context.stack, D(2), res);
             Ok(toon)
         }
@@ -413,7 +446,8 @@ pub mod util {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::jets::util::test::{assert_jet, init_context};
+    use crate::interpreter::{Error, Mote};
+    use crate::jets::util::test::{assert_jet, assert_noun_eq, init_context};
     use crate::mem::NockStack;
     use crate::noun::{D, T};
 
@@ -543,4 +577,48 @@ mod tests {
 
         assert_jet(context, jet_mink, samp, rest);
     }
+
+    #[test]
+    fn test_mink_fuel() {
+        let context = &mut init_context();
+        let stack = &mut context.stack;
+
+        let form = T(stack, &[D(1), D(53)]);
+        let axis = T(stack, &[D(0), D(1)]);
+        let spin = T(stack, &[D(2), axis, axis]);
+
+        // Enough fuel: a constant costs the same every time
+        let (tone, used) = util::mink_fuel(context, D(0), form, D(0), 100).unwrap();
+        let rest = T(&mut context.stack, &[D(0), D(53)]);
+        assert_noun_eq(&mut context.stack, tone, rest);
+        assert!(used > 0);
+        let (_, again) = util::mink_fuel(context, D(0), form, D(0), 100).unwrap();
+        assert_eq!(used, again);
+        assert_eq!(context.fuel, None);
+
+        // Too little fuel
+        match util::mink_fuel(context, D(0), form, D(0), used - 1) {
+            Err(Error::Deterministic(Mote::Fuel, _)) => {}
+            _ => panic!("expected %fuel"),
+        }
+        match util::mink_fuel(context, spin, spin, D(0), 1000) {
+            Err(Error::Deterministic(Mote::Fuel, _)) => {}
+            _ => panic!("expected %fuel"),
+        }
+        assert_eq!(context.fuel, None);
+
+        // Plain +mink can't catch running out of fuel
+        context.fuel = Some(1000);
+        match util::mink(context, spin, spin, D(0)) {
+            Err(Error::Deterministic(Mote::Fuel, _)) => {}
+            _ => panic!("expected %fuel"),
+        }
+        assert_eq!(context.fuel, Some(0));
+
+        // An outer budget is charged for what the inner one used
+        context.fuel = Some(1000);
+        let (_, used) = util::mink_fuel(context, D(0), form, D(0), 100).unwrap();
+        assert_eq!(context.fuel, Some(1000 - used));
+        context.fuel = None;
+    }
 }
@@ -0,0 +1,186 @@
+/** Scrambling jets
+ */
+use crate::interpreter::Context;
+use crate::jets::util::{slot, BAIL_EXIT};
+use crate::jets::Result;
+use crate::noun::{Atom, Noun};
+
+crate::gdb!();
+
+pub fn jet_fein(context: &mut Context, subject: Noun) -> Result {
+    let pyn = slot(subject, 6)?.as_atom()?;
+    match pyn.as_u64() {
+        Ok(pyn) => Ok(Atom::new(&mut context.stack, util::fein(pyn)).as_noun()),
+        Err(_) => Ok(pyn.as_noun()),
+    }
+}
+
+pub fn jet_fynd(context: &mut Context, subject: Noun) -> Result {
+    let cry = slot(subject, 6)?.as_atom()?;
+    match cry.as_u64() {
+        Ok(cry) => Ok(Atom::new(&mut context.stack, util::fynd(cry)).as_noun()),
+        Err(_) => Ok(cry.as_noun()),
+    }
+}
+
+pub fn jet_feis(context: &mut Context, subject: Noun) -> Result {
+    let m = slot(subject, 6)?.as_atom()?;
+    // +muk crashes on a round input past two bytes
+    match m.as_u64() {
+        Ok(m) if m < util::K => Ok(Atom::new(&mut context.stack, util::feis(m)).as_noun()),
+        _ => Err(BAIL_EXIT),
+    }
+}
+
+pub fn jet_tail(context: &mut Context, subject: Noun) -> Result {
+    let m = slot(subject, 6)?.as_atom()?;
+    match m.as_u64() {
+        Ok(m) if m < util::K => Ok(Atom::new(&mut context.stack, util::tail(m)).as_noun()),
+        _ => Err(BAIL_EXIT),
+    }
+}
+
+pub mod util {
+    use murmur3::murmur3_32_of_slice;
+
+    /// Murmur3 seeds for each round of the Feistel cipher (+raku)
+    const RAKU: [u32; 4] = [0xb76d5eed, 0xee281300, 0x85bcae01, 0x4b387af7];
+
+    /// Low and high moduli of the cipher halves
+    const A: u64 = 0xffff;
+    const B: u64 = 0x10000;
+
+    /// Upper bound of the cipher domain: results at or above this are cycle-walked
+    pub const K: u64 = A * B;
+
+    /// Rounds of the cipher
+    const R: u64 = 4;
+
+    /// Conceal the structure of a ship address (+fein)
+    pub fn fein(pyn: u64) -> u64 {
+        if (0x1_0000..=0xffff_ffff).contains(&pyn) {
+            0x1_0000 + feis(pyn - 0x1_0000)
+        } else if pyn >= 0x1_0000_0000 {
+            (pyn & 0xffff_ffff_0000_0000) | fein(pyn & 0xffff_ffff)
+        } else {
+            pyn
+        }
+    }
+
+    /// Restore the structure of a concealed ship address (+fynd)
+    pub fn fynd(cry: u64) -> u64 {
+        if (0x1_0000..=0xffff_ffff).contains(&cry) {
+            0x1_0000 + tail(cry - 0x1_0000)
+        } else if cry >= 0x1_0000_0000 {
+            (cry & 0xffff_ffff_0000_0000) | fynd(cry & 0xffff_ffff)
+        } else {
+            cry
+        }
+    }
+
+    /// Permute a 32-bit value below 0xffff.0000 (+feis)
+    pub fn feis(m: u64) -> u64 {
+        let c = fe(m);
+        if c < K {
+            c
+        } else {
+            fe(c)
+        }
+    }
+
+    /// Invert +feis (+tail)
+    pub fn tail(m: u64) -> u64 {
+        let c = fen(m);
+        if c < K {
+            c
+        } else {
+            fen(c)
+        }
+    }
+
+    /// Round function (+eff)
+    fn eff(j: u64, r: u64) -> u64 {
+        murmur3_32_of_slice(&r.to_le_bytes()[0..2], RAKU[j as usize]) as u64
+    }
+
+    /// Forward Feistel network (+fe)
+    fn fe(m: u64) -> u64 {
+        let mut ell = m % A;
+        let mut arr = m / A;
+        for j in 1..=R {
+            let f = eff(j - 1, arr);
+            let tmp = if j % 2 != 0 {
+                (f + ell) % A
+            } else {
+                (f + ell) % B
+            };
+            ell = arr;
+            arr = tmp;
+        }
+        if R % 2 != 0 || arr == A {
+            arr * A + ell
+        } else {
+            ell * A + arr
+        }
+    }
+
+    /// Reverse Feistel network (+fen)
+    fn fen(m: u64) -> u64 {
+        let (ahh, ale) = if R % 2 != 0 {
+            (m / A, m % A)
+        } else {
+            (m % A, m / A)
+        };
+        let (mut ell, mut arr) = if ale == A { (ahh, ale) } else { (ale, ahh) };
+        for j in (1..=R).rev() {
+            let f = eff(j - 1, ell);
+            let tmp = if j % 2 != 0 {
+                (arr + A - f % A) % A
+            } else {
+                (arr + B - f % B) % B
+            };
+            arr = ell;
+            ell = tmp;
+        }
+        arr * A + ell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::{assert_jet, assert_jet_err, init_context, A};
+    use crate::noun::D;
+    use ibig::ubig;
+    use util::{fein, fynd};
+
+    #[test]
+    fn test_fein_fynd() {
+        // ~dapnep-ronmyl
+        assert_eq!(fein(0x1_0000), 0x423e_60bf);
+        assert_eq!(fynd(0x423e_60bf), 0x1_0000);
+        // ~sampel-palnet
+        assert_eq!(fynd(0x046f_7f4f), 1_624_961_343);
+        for pyn in [0, 0xff, 0x1_0000, 0x1234_5678, 0xffff_ffff, 0x1_0001_0000, u64::MAX] {
+            assert_eq!(fynd(fein(pyn)), pyn);
+        }
+    }
+
+    #[test]
+    fn test_ob_jets() {
+        let c = &mut init_context();
+
+        assert_jet(c, jet_fein, D(0), D(0));
+        assert_jet(c, jet_fein, D(0x1_0000), D(0x423e_60bf));
+        assert_jet(c, jet_fynd, D(0x423e_60bf), D(0x1_0000));
+        assert_jet(c, jet_fein, D(0x1_0000_0000), D(0x1_0000_0000));
+        let comet = A(&mut c.stack, &ubig!(_0x100000000000000000000));
+        assert_jet(c, jet_fein, comet, comet);
+        assert_jet(c, jet_fynd, comet, comet);
+
+        assert_jet(c, jet_feis, D(0), D(0x423e_60bf - 0x1_0000));
+        assert_jet(c, jet_tail, D(0x423e_60bf - 0x1_0000), D(0));
+        assert_jet_err(c, jet_feis, D(0xffff_0000), BAIL_EXIT);
+        assert_jet_err(c, jet_tail, D(0xffff_0000), BAIL_EXIT);
+    }
+}
@@ -0,0 +1,891 @@
+/** Map and set jets
+ *
+ * Maps and sets are treaps, ordered by +gor on their keys and heaped by +mor. A set's key is its
+ * whole node value; a map's is the head of its node value. The jets follow the hoon arm for arm,
+ * so that they build the same nouns even from trees which are not valid treaps.
+ */
+use crate::interpreter::Context;
+use crate::jets::util::slot;
+use crate::jets::Result;
+use crate::noun::{Noun, D, NO, T, YES};
+use crate::site::Site;
+use util::Treap::{Map, Set};
+
+crate::gdb!();
+
+// +in: the door's sample (the set) is at +30 from a gate or trap inside it
+
+pub fn jet_in_put(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::put(&mut context.stack, Set, a, b)
+}
+
+pub fn jet_in_has(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    Ok(if util::has(&mut context.stack, Set, a, b)? {
+        YES
+    } else {
+        NO
+    })
+}
+
+pub fn jet_in_del(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::del(&mut context.stack, Set, a, b)
+}
+
+pub fn jet_in_gas(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::gas(&mut context.stack, Set, a, b)
+}
+
+pub fn jet_in_tap(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::tap(&mut context.stack, a, b)
+}
+
+pub fn jet_in_uni(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::uni(&mut context.stack, Set, a, b)
+}
+
+pub fn jet_in_int(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::int(&mut context.stack, Set, a, b)
+}
+
+pub fn jet_in_dif(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::dif(&mut context.stack, Set, a, b)
+}
+
+/// +wyt is a trap directly inside the door, so the set is at +14
+pub fn jet_in_wyt(_context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 14)?;
+    util::wyt(a).map(|x| D(x as u64))
+}
+
+// +by: as for +in
+
+pub fn jet_by_put(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 12)?;
+    let c = slot(subject, 13)?;
+    let item = T(&mut context.stack, &[b, c]);
+    util::put(&mut context.stack, Map, a, item)
+}
+
+pub fn jet_by_get(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    match util::get(&mut context.stack, a, b)? {
+        Some(value) => Ok(T(&mut context.stack, &[D(0), value])),
+        None => Ok(D(0)),
+    }
+}
+
+pub fn jet_by_has(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    Ok(if util::get(&mut context.stack, a, b)?.is_some() {
+        YES
+    } else {
+        NO
+    })
+}
+
+pub fn jet_by_del(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::del(&mut context.stack, Map, a, b)
+}
+
+pub fn jet_by_gas(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::gas(&mut context.stack, Map, a, b)
+}
+
+pub fn jet_by_tap(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::tap(&mut context.stack, a, b)
+}
+
+pub fn jet_by_uni(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::uni(&mut context.stack, Map, a, b)
+}
+
+pub fn jet_by_int(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::int(&mut context.stack, Map, a, b)
+}
+
+pub fn jet_by_dif(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let b = slot(subject, 6)?;
+    util::dif(&mut context.stack, Map, a, b)
+}
+
+pub fn jet_by_run(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let mut gate = slot(subject, 6)?;
+
+    // As in jet_turn, the gate doesn't change, so check for its jet once
+    let site = Site::new(context, &mut gate);
+    util::run(context, &site, a)
+}
+
+pub fn jet_by_apt(context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 30)?;
+    let l = util::unit(slot(subject, 12)?)?;
+    let r = util::unit(slot(subject, 13)?)?;
+    Ok(if util::apt(&mut context.stack, a, l, r)? {
+        YES
+    } else {
+        NO
+    })
+}
+
+/// As for +in
+pub fn jet_by_wyt(_context: &mut Context, subject: Noun) -> Result {
+    let a = slot(subject, 14)?;
+    util::wyt(a).map(|x| D(x as u64))
+}
+
+pub mod util {
+    use crate::interpreter::Context;
+    use crate::jets::util::slot;
+    use crate::jets::{sort, JetErr, Result};
+    use crate::mem::NockStack;
+    use crate::noun::{Noun, D, T, YES};
+    use crate::site::{site_slam, Site};
+    use crate::unifying_equality::unifying_equality;
+    use std::result;
+
+    /// Whether a treap is a set, keyed by its whole node values, or a map, keyed by their heads
+    #[derive(Copy, Clone, PartialEq, Eq)]
+    pub enum Treap {
+        Set,
+        Map,
+    }
+
+    impl Treap {
+        fn key(self, n: Noun) -> Result {
+            match self {
+                Treap::Set => Ok(n),
+                Treap::Map => slot(n, 2),
+            }
+        }
+    }
+
+    /// How deep the recursive arms may go on the native stack before punting to the hoon
+    ///
+    /// A treap's depth is logarithmic in its size, with high probability; only a degenerate tree
+    /// built by hand gets near this.
+    const MAX_DEPTH: usize = 512;
+
+    /// One level deeper, or a punt past `MAX_DEPTH`
+    fn deeper(depth: usize) -> result::Result<usize, JetErr> {
+        if depth < MAX_DEPTH {
+            Ok(depth + 1)
+        } else {
+            Err(JetErr::Punt)
+        }
+    }
+
+    fn is_null(a: Noun) -> bool {
+        unsafe { a.raw_equals(D(0)) }
+    }
+
+    /// Split a treap node into its value and its left and right subtrees
+    fn node(a: Noun) -> result::Result<(Noun, Noun, Noun), JetErr> {
+        let cell = a.as_cell()?;
+        let kids = cell.tail().as_cell()?;
+        Ok((cell.head(), kids.head(), kids.tail()))
+    }
+
+    fn same(stack: &mut NockStack, mut a: Noun, mut b: Noun) -> bool {
+        unsafe { unifying_equality(stack, &mut a, &mut b) }
+    }
+
+    fn gor(stack: &mut NockStack, a: Noun, b: Noun) -> bool {
+        unsafe { sort::util::gor(stack, a, b).raw_equals(YES) }
+    }
+
+    fn mor(stack: &mut NockStack, a: Noun, b: Noun) -> bool {
+        unsafe { sort::util::mor(stack, a, b).raw_equals(YES) }
+    }
+
+    /// Read a unit as an Option
+    pub fn unit(u: Noun) -> result::Result<Option<Noun>, JetErr> {
+        if is_null(u) {
+            Ok(None)
+        } else {
+            Ok(Some(slot(u, 3)?))
+        }
+    }
+
+    /// Insert `item`, a key for a set or a key-value pair for a map
+    pub fn put(stack: &mut NockStack, treap: Treap, a: Noun, item: Noun) -> Result {
+        put_at(stack, treap, a, item, 0)
+    }
+
+    fn put_at(stack: &mut NockStack, treap: Treap, a: Noun, item: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(T(stack, &[item, D(0), D(0)]));
+        }
+        let (n, l, r) = node(a)?;
+        let b = treap.key(item)?;
+        let p = treap.key(n)?;
+        if same(stack, b, p) {
+            return Ok(if same(stack, item, n) {
+                a
+            } else {
+                T(stack, &[item, l, r])
+            });
+        }
+        if gor(stack, b, p) {
+            let c = put_at(stack, treap, l, item, depth)?;
+            let (cn, cl, cr) = node(c)?;
+            let cp = treap.key(cn)?;
+            if mor(stack, p, cp) {
+                Ok(T(stack, &[n, c, r]))
+            } else {
+                let a = T(stack, &[n, cr, r]);
+                Ok(T(stack, &[cn, cl, a]))
+            }
+        } else {
+            let c = put_at(stack, treap, r, item, depth)?;
+            let (cn, cl, cr) = node(c)?;
+            let cp = treap.key(cn)?;
+            if mor(stack, p, cp) {
+                Ok(T(stack, &[n, l, c]))
+            } else {
+                let a = T(stack, &[n, l, cl]);
+                Ok(T(stack, &[cn, a, cr]))
+            }
+        }
+    }
+
+    /// Insert `item` at the root, as +bif does before taking the subtrees
+    fn bif(stack: &mut NockStack, treap: Treap, a: Noun, item: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(T(stack, &[item, D(0), D(0)]));
+        }
+        let (n, l, r) = node(a)?;
+        let b = treap.key(item)?;
+        let p = treap.key(n)?;
+        if same(stack, b, p) {
+            return Ok(if same(stack, item, n) {
+                a
+            } else {
+                T(stack, &[item, l, r])
+            });
+        }
+        if gor(stack, b, p) {
+            let d = bif(stack, treap, l, item, depth)?;
+            let (dn, dl, dr) = node(d)?;
+            let a = T(stack, &[n, dr, r]);
+            Ok(T(stack, &[dn, dl, a]))
+        } else {
+            let d = bif(stack, treap, r, item, depth)?;
+            let (dn, dl, dr) = node(d)?;
+            let a = T(stack, &[n, l, dl]);
+            Ok(T(stack, &[dn, a, dr]))
+        }
+    }
+
+    /// Whether a set has `b`
+    pub fn has(
+        stack: &mut NockStack,
+        treap: Treap,
+        mut a: Noun,
+        b: Noun,
+    ) -> result::Result<bool, JetErr> {
+        loop {
+            if is_null(a) {
+                return Ok(false);
+            }
+            let (n, l, r) = node(a)?;
+            let p = treap.key(n)?;
+            if same(stack, b, p) {
+                return Ok(true);
+            }
+            a = if gor(stack, b, p) { l } else { r };
+        }
+    }
+
+    /// The value for key `b` in a map
+    pub fn get(
+        stack: &mut NockStack,
+        mut a: Noun,
+        b: Noun,
+    ) -> result::Result<Option<Noun>, JetErr> {
+        loop {
+            if is_null(a) {
+                return Ok(None);
+            }
+            let (n, l, r) = node(a)?;
+            let p = slot(n, 2)?;
+            if same(stack, b, p) {
+                return Ok(Some(slot(n, 3)?));
+            }
+            a = if gor(stack, b, p) { l } else { r };
+        }
+    }
+
+    /// Remove key `b`
+    pub fn del(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun) -> Result {
+        del_at(stack, treap, a, b, 0)
+    }
+
+    fn del_at(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(D(0));
+        }
+        let (n, l, r) = node(a)?;
+        let p = treap.key(n)?;
+        if !same(stack, b, p) {
+            if gor(stack, b, p) {
+                let l = del_at(stack, treap, l, b, depth)?;
+                Ok(T(stack, &[n, l, r]))
+            } else {
+                let r = del_at(stack, treap, r, b, depth)?;
+                Ok(T(stack, &[n, l, r]))
+            }
+        } else {
+            join(stack, treap, l, r, depth)
+        }
+    }
+
+    /// Join two treaps whose keys are all ordered before those of the other, as +del and +dif do
+    fn join(stack: &mut NockStack, treap: Treap, l: Noun, r: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(l) {
+            return Ok(r);
+        }
+        if is_null(r) {
+            return Ok(l);
+        }
+        let (ln, ll, lr) = node(l)?;
+        let (rn, rl, rr) = node(r)?;
+        let lp = treap.key(ln)?;
+        let rp = treap.key(rn)?;
+        if mor(stack, lp, rp) {
+            let lr = join(stack, treap, lr, r, depth)?;
+            Ok(T(stack, &[ln, ll, lr]))
+        } else {
+            let rl = join(stack, treap, l, rl, depth)?;
+            Ok(T(stack, &[rn, rl, rr]))
+        }
+    }
+
+    /// Insert every item of the list `b`, in order
+    pub fn gas(stack: &mut NockStack, treap: Treap, mut a: Noun, mut b: Noun) -> Result {
+        while !is_null(b) {
+            let cell = b.as_cell()?;
+            a = put(stack, treap, a, cell.head())?;
+            b = cell.tail();
+        }
+        Ok(a)
+    }
+
+    /// Prepend the node values to the list `b`, right subtree first
+    pub fn tap(stack: &mut NockStack, a: Noun, b: Noun) -> Result {
+        tap_at(stack, a, b, 0)
+    }
+
+    fn tap_at(stack: &mut NockStack, a: Noun, b: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(b);
+        }
+        let (n, l, r) = node(a)?;
+        let left = tap_at(stack, l, b, depth)?;
+        let b = T(stack, &[n, left]);
+        tap_at(stack, r, b, depth)
+    }
+
+    /// Count the nodes
+    pub fn wyt(a: Noun) -> result::Result<usize, JetErr> {
+        wyt_at(a, 0)
+    }
+
+    fn wyt_at(a: Noun, depth: usize) -> result::Result<usize, JetErr> {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(0);
+        }
+        let (_, l, r) = node(a)?;
+        Ok(1 + wyt_at(l, depth)? + wyt_at(r, depth)?)
+    }
+
+    /// Union, taking the node from `b` where both have a key
+    pub fn uni(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun) -> Result {
+        uni_at(stack, treap, a, b, 0)
+    }
+
+    fn uni_at(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun, depth: usize) -> Result {
+        if same(stack, a, b) {
+            Ok(a)
+        } else {
+            uni_in(stack, treap, a, b, depth)
+        }
+    }
+
+    fn uni_in(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(b) {
+            return Ok(a);
+        }
+        if is_null(a) {
+            return Ok(b);
+        }
+        let (na, la, ra) = node(a)?;
+        let (nb, lb, rb) = node(b)?;
+        let pa = treap.key(na)?;
+        let pb = treap.key(nb)?;
+        if same(stack, pb, pa) {
+            let l = uni_in(stack, treap, la, lb, depth)?;
+            let r = uni_in(stack, treap, ra, rb, depth)?;
+            Ok(T(stack, &[nb, l, r]))
+        } else if mor(stack, pa, pb) {
+            if gor(stack, pb, pa) {
+                let b_l = T(stack, &[nb, lb, D(0)]);
+                let l = uni_in(stack, treap, la, b_l, depth)?;
+                let a = T(stack, &[na, l, ra]);
+                uni_in(stack, treap, a, rb, depth)
+            } else {
+                let b_r = T(stack, &[nb, D(0), rb]);
+                let r = uni_in(stack, treap, ra, b_r, depth)?;
+                let a = T(stack, &[na, la, r]);
+                uni_in(stack, treap, a, lb, depth)
+            }
+        } else if gor(stack, pa, pb) {
+            let a_l = T(stack, &[na, la, D(0)]);
+            let l = uni_in(stack, treap, a_l, lb, depth)?;
+            let b = T(stack, &[nb, l, rb]);
+            uni_in(stack, treap, ra, b, depth)
+        } else {
+            let a_r = T(stack, &[na, D(0), ra]);
+            let r = uni_in(stack, treap, a_r, rb, depth)?;
+            let b = T(stack, &[nb, lb, r]);
+            uni_in(stack, treap, la, b, depth)
+        }
+    }
+
+    /// Intersection
+    ///
+    /// The set and map arms are written differently: the set swaps its arguments to keep the
+    /// +mor-greater root in `a`, while the map spells out both cases and takes nodes from `b`.
+    pub fn int(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun) -> Result {
+        int_at(stack, treap, a, b, 0)
+    }
+
+    fn int_at(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(b) || is_null(a) {
+            return Ok(D(0));
+        }
+        let (na, la, ra) = node(a)?;
+        let (nb, lb, rb) = node(b)?;
+        let pa = treap.key(na)?;
+        let pb = treap.key(nb)?;
+        if treap == Treap::Set {
+            if !mor(stack, pa, pb) {
+                return int_at(stack, treap, b, a, depth);
+            }
+            return int_split(stack, treap, a, b, na, depth);
+        }
+        if mor(stack, pa, pb) {
+            int_split(stack, treap, a, b, nb, depth)
+        } else if same(stack, pa, pb) {
+            let l = int_at(stack, treap, la, lb, depth)?;
+            let r = int_at(stack, treap, ra, rb, depth)?;
+            Ok(T(stack, &[nb, l, r]))
+        } else if gor(stack, pa, pb) {
+            let a_l = T(stack, &[na, la, D(0)]);
+            let l = int_at(stack, treap, a_l, lb, depth)?;
+            let r = int_at(stack, treap, ra, b, depth)?;
+            uni_at(stack, treap, l, r, depth)
+        } else {
+            let a_r = T(stack, &[na, D(0), ra]);
+            let l = int_at(stack, treap, a_r, rb, depth)?;
+            let r = int_at(stack, treap, la, b, depth)?;
+            uni_at(stack, treap, l, r, depth)
+        }
+    }
+
+    /// The case of +int where the root of `a` is +mor-greater, keeping `keep` for a shared key
+    fn int_split(
+        stack: &mut NockStack,
+        treap: Treap,
+        a: Noun,
+        b: Noun,
+        keep: Noun,
+        depth: usize,
+    ) -> Result {
+        let (na, la, ra) = node(a)?;
+        let (nb, lb, rb) = node(b)?;
+        let pa = treap.key(na)?;
+        let pb = treap.key(nb)?;
+        if same(stack, pb, pa) {
+            let l = int_at(stack, treap, la, lb, depth)?;
+            let r = int_at(stack, treap, ra, rb, depth)?;
+            Ok(T(stack, &[keep, l, r]))
+        } else if gor(stack, pb, pa) {
+            let b_l = T(stack, &[nb, lb, D(0)]);
+            let l = int_at(stack, treap, la, b_l, depth)?;
+            let r = int_at(stack, treap, a, rb, depth)?;
+            uni_at(stack, treap, l, r, depth)
+        } else {
+            let b_r = T(stack, &[nb, D(0), rb]);
+            let l = int_at(stack, treap, ra, b_r, depth)?;
+            let r = int_at(stack, treap, a, lb, depth)?;
+            uni_at(stack, treap, l, r, depth)
+        }
+    }
+
+    /// Difference: the items of `a` whose keys are not in `b`
+    pub fn dif(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun) -> Result {
+        dif_at(stack, treap, a, b, 0)
+    }
+
+    fn dif_at(stack: &mut NockStack, treap: Treap, a: Noun, b: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(b) {
+            return Ok(a);
+        }
+        let (nb, lb, rb) = node(b)?;
+        let c = bif(stack, treap, a, nb, depth)?;
+        let (_, cl, cr) = node(c)?;
+        let d = dif_at(stack, treap, cl, lb, depth)?;
+        let e = dif_at(stack, treap, cr, rb, depth)?;
+        join(stack, treap, d, e, depth)
+    }
+
+    /// Apply the gate at `site` to every value of a map
+    pub fn run(context: &mut Context, site: &Site, a: Noun) -> Result {
+        run_at(context, site, a, 0)
+    }
+
+    fn run_at(context: &mut Context, site: &Site, a: Noun, depth: usize) -> Result {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(a);
+        }
+        let (n, l, r) = node(a)?;
+        let pair = n.as_cell()?;
+        let q = site_slam(context, site, pair.tail())?;
+        let n = T(&mut context.stack, &[pair.head(), q]);
+        let l = run_at(context, site, l, depth)?;
+        let r = run_at(context, site, r, depth)?;
+        Ok(T(&mut context.stack, &[n, l, r]))
+    }
+
+    /// Whether a map is a valid treap, with keys bounded by `l` and `r`
+    pub fn apt(
+        stack: &mut NockStack,
+        a: Noun,
+        l: Option<Noun>,
+        r: Option<Noun>,
+    ) -> result::Result<bool, JetErr> {
+        apt_at(stack, a, l, r, 0)
+    }
+
+    fn apt_at(
+        stack: &mut NockStack,
+        a: Noun,
+        l: Option<Noun>,
+        r: Option<Noun>,
+        depth: usize,
+    ) -> result::Result<bool, JetErr> {
+        let depth = deeper(depth)?;
+        if is_null(a) {
+            return Ok(true);
+        }
+        let (n, la, ra) = node(a)?;
+        let p = slot(n, 2)?;
+        if let Some(l) = l {
+            if !gor(stack, p, l) || same(stack, p, l) {
+                return Ok(false);
+            }
+        }
+        if let Some(r) = r {
+            if !gor(stack, r, p) || same(stack, r, p) {
+                return Ok(false);
+            }
+        }
+        if !is_null(la) {
+            let pl = slot(la, 4)?;
+            if !(mor(stack, p, pl) && !same(stack, p, pl) && apt_at(stack, la, Some(p), r, depth)?)
+            {
+                return Ok(false);
+            }
+        }
+        if !is_null(ra) {
+            let pr = slot(ra, 4)?;
+            if !(mor(stack, p, pr) && !same(stack, p, pr) && apt_at(stack, ra, l, Some(p), depth)?)
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::util::Treap::{self, Map, Set};
+    use super::*;
+    use crate::jets::util::test::*;
+    use crate::jets::JetErr;
+    use crate::noun::{D, NO, T, YES};
+
+    /// Build a treap by inserting `items` in order, as +gas does
+    fn gas(c: &mut Context, treap: Treap, items: &[Noun]) -> Noun {
+        let mut list = D(0);
+        for item in items.iter().rev() {
+            list = T(&mut c.stack, &[*item, list]);
+        }
+        util::gas(&mut c.stack, treap, D(0), list).unwrap()
+    }
+
+    fn set(c: &mut Context, keys: std::ops::RangeInclusive<u64>) -> Noun {
+        let items: Vec<Noun> = keys.map(D).collect();
+        gas(c, Set, &items)
+    }
+
+    /// A map from each key to ten times itself, or to `value` if given
+    fn map(c: &mut Context, keys: std::ops::RangeInclusive<u64>, value: Option<u64>) -> Noun {
+        let items: Vec<Noun> = keys
+            .map(|k| T(&mut c.stack, &[D(k), D(value.unwrap_or(k * 10))]))
+            .collect();
+        gas(c, Map, &items)
+    }
+
+    /// Build a treap from the preorder walk of its keys, with 0 for an empty subtree, and with
+    /// `value` giving a map's value for each key
+    ///
+    /// The shapes are worked out by hand from the hoon +put, so that the tests don't build their
+    /// expected trees with the arms they test.
+    fn tree(c: &mut Context, keys: &[u64], value: Option<fn(u64) -> u64>) -> Noun {
+        fn walk(
+            c: &mut Context,
+            keys: &[u64],
+            value: Option<fn(u64) -> u64>,
+            pos: &mut usize,
+        ) -> Noun {
+            let k = keys[*pos];
+            *pos += 1;
+            if k == 0 {
+                return D(0);
+            }
+            let n = match value {
+                None => D(k),
+                Some(f) => T(&mut c.stack, &[D(k), D(f(k))]),
+            };
+            let l = walk(c, keys, value, pos);
+            let r = walk(c, keys, value, pos);
+            T(&mut c.stack, &[n, l, r])
+        }
+        let mut pos = 0;
+        let a = walk(c, keys, value, &mut pos);
+        assert_eq!(pos, keys.len());
+        a
+    }
+
+    fn tens(k: u64) -> u64 {
+        k * 10
+    }
+
+    fn zero(_: u64) -> u64 {
+        0
+    }
+
+    /// The keys 1 to 8, 1 to 9, 1 to 10, 1 to 8 but 4, 1 to 6, 4 to 10, 1 to 3 and 4 to 6
+    const K1_8: &[u64] = &[6, 8, 0, 7, 5, 0, 0, 0, 4, 2, 1, 0, 0, 3, 0, 0, 0];
+    const K1_9: &[u64] = &[6, 8, 0, 7, 5, 0, 0, 0, 4, 2, 9, 0, 1, 0, 0, 3, 0, 0, 0];
+    const K1_10: &[u64] = &[6, 10, 8, 0, 7, 5, 0, 0, 0, 0, 4, 2, 9, 0, 1, 0, 0, 3, 0, 0, 0];
+    const K1_8_4: &[u64] = &[6, 8, 0, 7, 5, 0, 0, 0, 2, 1, 0, 0, 3, 0, 0];
+    const K1_6: &[u64] = &[6, 5, 0, 0, 4, 2, 1, 0, 0, 3, 0, 0, 0];
+    const K4_10: &[u64] = &[6, 10, 8, 0, 7, 5, 0, 0, 0, 0, 4, 9, 0, 0, 0];
+    const K1_3: &[u64] = &[2, 1, 0, 0, 3, 0, 0];
+    const K4_6: &[u64] = &[6, 5, 0, 0, 4, 0, 0];
+
+    /// The payload of a gate inside a door with sample `a`
+    fn door(c: &mut Context, a: Noun) -> Noun {
+        T(&mut c.stack, &[D(0), a, D(0)])
+    }
+
+    #[test]
+    fn test_gas() {
+        let c = &mut init_context();
+        let s = set(c, 1..=8);
+        let res = tree(c, K1_8, None);
+        assert_noun_eq(&mut c.stack, s, res);
+        let s = set(c, 1..=6);
+        let res = tree(c, K1_6, None);
+        assert_noun_eq(&mut c.stack, s, res);
+        let m = map(c, 4..=10, Some(0));
+        let res = tree(c, K4_10, Some(zero));
+        assert_noun_eq(&mut c.stack, m, res);
+    }
+
+    #[test]
+    fn test_in_put_has_del() {
+        let c = &mut init_context();
+        let s = set(c, 1..=8);
+        let pay = door(c, s);
+
+        assert_jet_door(c, jet_in_put, D(5), pay, s);
+        let res = tree(c, K1_9, None);
+        assert_jet_door(c, jet_in_put, D(9), pay, res);
+
+        assert_jet_door(c, jet_in_has, D(3), pay, YES);
+        assert_jet_door(c, jet_in_has, D(20), pay, NO);
+
+        let res = tree(c, K1_8_4, None);
+        assert_jet_door(c, jet_in_del, D(4), pay, res);
+        assert_jet_door(c, jet_in_del, D(20), pay, s);
+    }
+
+    #[test]
+    fn test_in_tap_wyt() {
+        let c = &mut init_context();
+        let s = set(c, 1..=8);
+        let pay = door(c, s);
+
+        // The right subtree, then the node, then the left, each walked the same way
+        let subject = T(&mut c.stack, &[D(0), D(0), pay]);
+        let list = jet_in_tap(c, subject).unwrap();
+        let res = T(
+            &mut c.stack,
+            &[D(4), D(3), D(2), D(1), D(6), D(7), D(5), D(8), D(0)],
+        );
+        assert_noun_eq(&mut c.stack, list, res);
+
+        let pay = T(&mut c.stack, &[s, D(0)]);
+        assert_jet_door(c, jet_in_wyt, D(0), pay, D(8));
+        let pay = T(&mut c.stack, &[D(0), D(0)]);
+        assert_jet_door(c, jet_in_wyt, D(0), pay, D(0));
+    }
+
+    #[test]
+    fn test_in_uni_int_dif() {
+        let c = &mut init_context();
+        let a = set(c, 1..=6);
+        let b = set(c, 4..=10);
+        let pay = door(c, a);
+
+        let res = tree(c, K1_10, None);
+        assert_jet_door(c, jet_in_uni, b, pay, res);
+        assert_jet_door(c, jet_in_uni, D(0), pay, a);
+        let res = tree(c, K4_6, None);
+        assert_jet_door(c, jet_in_int, b, pay, res);
+        assert_jet_door(c, jet_in_int, D(0), pay, D(0));
+        let res = tree(c, K1_3, None);
+        assert_jet_door(c, jet_in_dif, b, pay, res);
+        assert_jet_door(c, jet_in_dif, D(0), pay, a);
+    }
+
+    #[test]
+    fn test_deep_tree_punts() {
+        let c = &mut init_context();
+
+        // A left spine far deeper than any treap would grow
+        let mut a = D(0);
+        for k in 1..=2000 {
+            a = T(&mut c.stack, &[D(k), a, D(0)]);
+        }
+        let pay = T(&mut c.stack, &[a, D(0)]);
+        let subject = T(&mut c.stack, &[D(0), D(0), pay]);
+        assert!(matches!(jet_in_wyt(c, subject), Err(JetErr::Punt)));
+        let pay = door(c, a);
+        let subject = T(&mut c.stack, &[D(0), D(0), pay]);
+        assert!(matches!(jet_in_tap(c, subject), Err(JetErr::Punt)));
+    }
+
+    #[test]
+    fn test_by_put_get_del() {
+        let c = &mut init_context();
+        let m = map(c, 1..=8, None);
+        let pay = door(c, m);
+
+        let res = T(&mut c.stack, &[D(0), D(30)]);
+        assert_jet_door(c, jet_by_get, D(3), pay, res);
+        assert_jet_door(c, jet_by_get, D(20), pay, D(0));
+        assert_jet_door(c, jet_by_has, D(3), pay, YES);
+        assert_jet_door(c, jet_by_has, D(20), pay, NO);
+
+        let sam = T(&mut c.stack, &[D(3), D(30)]);
+        assert_jet_door(c, jet_by_put, sam, pay, m);
+        let sam = T(&mut c.stack, &[D(9), D(90)]);
+        let res = tree(c, K1_9, Some(tens));
+        assert_jet_door(c, jet_by_put, sam, pay, res);
+
+        let sam = T(&mut c.stack, &[D(3), D(31)]);
+        let subject = T(&mut c.stack, &[D(0), sam, pay]);
+        let put = jet_by_put(c, subject).unwrap();
+        let pay = door(c, put);
+        let res = T(&mut c.stack, &[D(0), D(31)]);
+        assert_jet_door(c, jet_by_get, D(3), pay, res);
+
+        let pay = door(c, m);
+        let res = tree(c, K1_8_4, Some(tens));
+        assert_jet_door(c, jet_by_del, D(4), pay, res);
+    }
+
+    #[test]
+    fn test_by_uni_int_dif_wyt() {
+        let c = &mut init_context();
+        let a = map(c, 1..=6, None);
+        let b = map(c, 4..=10, Some(0));
+        let pay = door(c, a);
+
+        // Where both have a key, the value comes from b
+        let res = tree(c, K1_10, Some(|k| if k < 4 { k * 10 } else { 0 }));
+        assert_jet_door(c, jet_by_uni, b, pay, res);
+        let res = tree(c, K4_6, Some(zero));
+        assert_jet_door(c, jet_by_int, b, pay, res);
+        let res = tree(c, K1_3, Some(tens));
+        assert_jet_door(c, jet_by_dif, b, pay, res);
+
+        let pay = T(&mut c.stack, &[a, D(0)]);
+        assert_jet_door(c, jet_by_wyt, D(0), pay, D(6));
+    }
+
+    #[test]
+    fn test_by_apt() {
+        let c = &mut init_context();
+        let m = map(c, 1..=8, None);
+        let pay = door(c, m);
+        let sam = T(&mut c.stack, &[D(0), D(0)]);
+        assert_jet_door(c, jet_by_apt, sam, pay, YES);
+
+        // Swapping the subtrees of the root breaks the ordering
+        let (n, l, r) = {
+            let cell = m.as_cell().unwrap();
+            let kids = cell.tail().as_cell().unwrap();
+            (cell.head(), kids.head(), kids.tail())
+        };
+        let bad = T(&mut c.stack, &[n, r, l]);
+        let pay = door(c, bad);
+        assert_jet_door(c, jet_by_apt, sam, pay, NO);
+    }
+}
@@ -2,15 +2,23 @@ extern crate lazy_static;
 extern crate num_derive;
 #[macro_use]
 extern crate static_assertions;
+pub mod event_log;
 pub mod flog;
 pub mod hamt;
 pub mod interpreter;
 pub mod jets;
 pub mod mem;
+pub mod memo;
 pub mod mug;
+pub mod newt;
 pub mod noun;
+pub mod pma;
+pub mod profile;
+pub mod replay;
+pub mod serf;
 pub mod serialization;
 pub mod site;
+pub mod snapshot;
 pub mod trace;
 pub mod unifying_equality;
 
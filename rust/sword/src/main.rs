@@ -0,0 +1,59 @@
+use std::io;
+use sword::jets::hot::URBIT_HOT_STATE;
+use sword::serf::serf;
+
+fn main() -> io::Result<()> {
+    // debug
+    #[cfg(feature = "stop_for_debug")]
+    {
+        eprintln!("serf: pid {}", std::process::id());
+        if unsafe { libc::kill(std::process::id() as i32, libc::SIGSTOP) } != 0 {
+            panic!("Could not stop ourselves.");
+        };
+    }
+
+    let filename = std::env::args()
+        .nth(1)
+        .expect("Must provide input filename");
+
+    if filename == "see gdb! definition in lib.rs about this" {
+        sword::event_log::use_gdb();
+        sword::interpreter::use_gdb();
+        sword::jets::use_gdb();
+        sword::jets::bits::use_gdb();
+        sword::jets::float::use_gdb();
+        sword::jets::form::use_gdb();
+        sword::jets::hash::use_gdb();
+        sword::jets::list::use_gdb();
+        sword::jets::lock::aes::use_gdb();
+        sword::jets::lock::ed::use_gdb();
+        sword::jets::lock::sha::use_gdb();
+        sword::jets::lute::use_gdb();
+        sword::jets::math::use_gdb();
+        sword::jets::nock::use_gdb();
+        sword::jets::ob::use_gdb();
+        sword::jets::parse::use_gdb();
+        sword::jets::serial::use_gdb();
+        sword::jets::sort::use_gdb();
+        sword::jets::treap::use_gdb();
+        sword::jets::tree::use_gdb();
+        sword::mem::use_gdb();
+        sword::memo::use_gdb();
+        sword::mug::use_gdb();
+        sword::newt::use_gdb();
+        sword::noun::use_gdb();
+        sword::pma::use_gdb();
+        sword::profile::use_gdb();
+        sword::replay::use_gdb();
+        sword::serf::use_gdb();
+        sword::serialization::use_gdb();
+        sword::snapshot::use_gdb();
+        sword::trace::use_gdb();
+    }
+
+    if filename == "serf" {
+        return serf(URBIT_HOT_STATE);
+    }
+
+    panic!("sword: unknown subcommand {}", filename);
+}
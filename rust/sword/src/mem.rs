@@ -171,6 +171,8 @@ pub struct NockStack {
     /// PMA from which we will copy into the [NockStack]
     /// Whether or not [`Self::pre_copy()`] has been called on the current stack frame.
     pc: bool,
+    /// Words allocated in any frame since the stack was made, for profiling
+    allocated: u64,
 }
 
 impl NockStack {
@@ -220,6 +222,7 @@ impl NockStack {
                 alloc_pointer,
                 memory,
                 pc: false,
+                allocated: 0,
             },
             free,
         ))
@@ -523,6 +526,11 @@ impl NockStack {
         self.alloc_pointer
     }
 
+    /** Words allocated in any frame since this NockStack was made, whether or not since freed */
+    pub fn words_allocated(&self) -> u64 {
+        self.allocated
+    }
+
     /** Current stack pointer of this NockStack */
     pub fn get_stack_pointer_pointer(&self) -> *const *mut u64 {
         &self.stack_pointer
@@ -741,6 +749,7 @@ impl NockStack {
         if self.pc {
             panic!("Allocation during cleanup phase is prohibited.");
         }
+        self.allocated += words as u64;
         self.alloc_pointer = self.alloc_pointer.sub(words);
         self.alloc_pointer
     }
@@ -751,6 +760,7 @@ impl NockStack {
         if self.pc {
             panic!("Allocation during cleanup phase is prohibited.");
         }
+        self.allocated += words as u64;
         let alloc = self.alloc_pointer;
         self.alloc_pointer = self.alloc_pointer.add(words);
         alloc
@@ -0,0 +1,239 @@
+/** Memo cache: results of %memo hints and the ++ut jets
+ *
+ * Entries live in two generations of HAMTs on the NockStack. New entries go into the young
+ * generation; once it holds half the capacity, it becomes the old generation and the previous old
+ * generation is dropped. A hit in the old generation is copied back into the young one, so entries
+ * in use survive the turnover. This bounds the cache at its capacity while approximating LRU, and
+ * needs nothing from the HAMT but lookup and insertion: dropped generations are reclaimed when
+ * the cache is next preserved out of a frame.
+ */
+use crate::hamt::Hamt;
+use crate::mem::{NockStack, Preserve};
+use crate::noun::Noun;
+
+crate::gdb!();
+
+/** Entries a [crate::interpreter::ContextBuilder] bounds the cache at unless told otherwise */
+pub const DEFAULT_CAPACITY: usize = 1 << 16;
+
+/** Counters an embedder can query to see how well the cache is doing */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /** Entries dropped with an old generation */
+    pub evictions: u64,
+    /** Entries currently held, in both generations */
+    pub entries: usize,
+}
+
+#[derive(Copy, Clone)]
+pub struct MemoCache {
+    young: Hamt<Noun>,
+    old: Hamt<Noun>,
+    young_len: usize,
+    old_len: usize,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl MemoCache {
+    /** An empty cache holding at most `capacity` entries (usize::MAX for no bound) */
+    pub fn new(stack: &mut NockStack, capacity: usize) -> Self {
+        MemoCache {
+            young: Hamt::new(stack),
+            old: Hamt::new(stack),
+            young_len: 0,
+            old_len: 0,
+            capacity,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            entries: self.young_len + self.old_len,
+        }
+    }
+
+    pub fn lookup(&mut self, stack: &mut NockStack, key: &mut Noun) -> Option<Noun> {
+        if let Some(value) = self.young.lookup(stack, key) {
+            self.hits += 1;
+            return Some(value);
+        }
+        match self.old.lookup(stack, key) {
+            Some(value) => {
+                self.hits += 1;
+                self.put(stack, key, value, false, true);
+                Some(value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, stack: &mut NockStack, key: &mut Noun, value: Noun) {
+        let in_young = self.young.lookup(stack, key).is_some();
+        let in_old = !in_young && self.old.lookup(stack, key).is_some();
+        self.put(stack, key, value, in_young, in_old);
+    }
+
+    /** Insert into the young generation, given which generation already has the key, if any
+     *
+     * A key moved up from the old generation is no longer counted there: its stale copy goes
+     * with the old generation without counting as an eviction.
+     */
+    fn put(
+        &mut self,
+        stack: &mut NockStack,
+        key: &mut Noun,
+        value: Noun,
+        in_young: bool,
+        in_old: bool,
+    ) {
+        if in_young {
+            self.young = self.young.insert(stack, key, value);
+            return;
+        }
+        if in_old {
+            self.old_len -= 1;
+        }
+        if self.young_len >= (self.capacity / 2).max(1) {
+            self.evictions += self.old_len as u64;
+            self.old = self.young;
+            self.old_len = self.young_len;
+            self.young = Hamt::new(stack);
+            self.young_len = 0;
+        }
+        self.young = self.young.insert(stack, key, value);
+        self.young_len += 1;
+    }
+
+    /** Every entry, oldest generation first */
+    pub fn entries(&self) -> impl Iterator<Item = (Noun, Noun)> + '_ {
+        self.old
+            .iter()
+            .chain(self.young.iter())
+            .flat_map(|slice| slice.iter().copied())
+    }
+
+    /** Drop every entry, keeping the capacity and statistics */
+    pub fn clear(&mut self, stack: &mut NockStack) {
+        self.young = Hamt::new(stack);
+        self.old = Hamt::new(stack);
+        self.young_len = 0;
+        self.old_len = 0;
+    }
+
+    /** Go back to the entries of an earlier copy of this cache, keeping the statistics */
+    pub fn rewind(&mut self, saved: MemoCache) {
+        self.young = saved.young;
+        self.old = saved.old;
+        self.young_len = saved.young_len;
+        self.old_len = saved.old_len;
+    }
+}
+
+impl Preserve for MemoCache {
+    unsafe fn assert_in_stack(&self, stack: &NockStack) {
+        self.young.assert_in_stack(stack);
+        self.old.assert_in_stack(stack);
+    }
+
+    unsafe fn preserve(&mut self, stack: &mut NockStack) {
+        self.young.preserve(stack);
+        self.old.preserve(stack);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noun::D;
+
+    fn value(noun: Noun) -> u64 {
+        noun.as_direct().unwrap().data()
+    }
+
+    #[test]
+    fn test_memo_cache_evicts_oldest() {
+        let stack = &mut NockStack::new(8 << 10 << 10, 0);
+        let mut cache = MemoCache::new(stack, 4);
+
+        for n in 0..4 {
+            cache.insert(stack, &mut D(n), D(n + 100));
+        }
+        // Keep 0 alive by using it, then push 1 out
+        assert_eq!(cache.lookup(stack, &mut D(0)).map(value), Some(100));
+        cache.insert(stack, &mut D(4), D(104));
+
+        assert!(cache.lookup(stack, &mut D(1)).is_none());
+        assert_eq!(cache.lookup(stack, &mut D(0)).map(value), Some(100));
+        assert_eq!(cache.lookup(stack, &mut D(4)).map(value), Some(104));
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 3,
+                misses: 1,
+                // 0 moved up before its generation went, so only 1 was lost
+                evictions: 1,
+                entries: 4,
+            }
+        );
+
+        cache.clear(stack);
+        assert!(cache.lookup(stack, &mut D(4)).is_none());
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().misses, 2);
+    }
+
+    #[test]
+    fn test_memo_cache_replace_and_promote() {
+        let stack = &mut NockStack::new(8 << 10 << 10, 0);
+        let mut cache = MemoCache::new(stack, 4);
+
+        // Replacing a young entry doesn't count it twice, nor turn the generation over
+        cache.insert(stack, &mut D(0), D(100));
+        cache.insert(stack, &mut D(1), D(101));
+        cache.insert(stack, &mut D(0), D(200));
+        assert_eq!(cache.lookup(stack, &mut D(0)).map(value), Some(200));
+        assert_eq!(cache.stats().entries, 2);
+
+        // 0 and 1 go old; using 1 moves it up rather than copying it
+        cache.insert(stack, &mut D(2), D(102));
+        assert_eq!(cache.lookup(stack, &mut D(1)).map(value), Some(101));
+        assert_eq!(cache.stats().entries, 3);
+
+        // So does inserting over 0, and the turnover this causes loses nothing
+        cache.insert(stack, &mut D(0), D(300));
+        assert_eq!(cache.stats().entries, 3);
+        assert_eq!(cache.stats().evictions, 0);
+        for (n, v) in [(0, 300), (1, 101), (2, 102)] {
+            assert_eq!(cache.lookup(stack, &mut D(n)).map(value), Some(v));
+        }
+
+        // Moving 2 up turned the generation over again, so now 0 and 1 are old, and go with the
+        // next turnover
+        cache.insert(stack, &mut D(3), D(103));
+        assert_eq!(cache.stats().entries, 4);
+        cache.insert(stack, &mut D(5), D(105));
+        assert_eq!(cache.stats().entries, 3);
+        assert_eq!(cache.stats().evictions, 2);
+        assert!(cache.lookup(stack, &mut D(0)).is_none());
+        assert!(cache.lookup(stack, &mut D(1)).is_none());
+        assert_eq!(cache.lookup(stack, &mut D(2)).map(value), Some(102));
+    }
+}
@@ -0,0 +1,274 @@
+/** Newt: IPC to the king
+ *
+ * This manages an IPC connection to the king over stdin and stdout. Each message is a jammed noun
+ * behind a five-byte header: a zero version byte followed by the little-endian byte length of the
+ * jammed atom. The schema is:
+ *
+ * |%
+ * ::  +writ: from king to serf
+ * ::
+ * +$  writ
+ *   $%  $:  %live
+ *           $%  [%cram eve=@]
+ *               [%exit cod=@]
+ *               [%save eve=@]
+ *               [%meld ~]
+ *               [%pack ~]
+ *       ==  ==
+ *       [%peek mil=@ sam=*]  :: gang (each path $%([%once @tas @tas path] [%beam @tas beam]))
+ *       [%play eve=@ lit=(list ?((pair @da ovum) *))]
+ *       [%work mil=@ job=(pair @da ovum)]
+ *   ==
+ * ::  +plea: from serf to king
+ * ::
+ * +$  plea
+ *   $%  [%live ~]
+ *       [%ripe [pro=%1 hon=@ nok=@] eve=@ mug=@]
+ *       [%slog pri=@ tank]
+ *       [%flog cord]
+ *       $:  %peek
+ *           $%  [%done dat=(unit (cask))]
+ *               [%bail dud=goof]
+ *       ==  ==
+ *       $:  %play
+ *           $%  [%done mug=@]
+ *               [%bail eve=@ mug=@ dud=goof]
+ *       ==  ==
+ *       $:  %work
+ *           $%  [%done eve=@ mug=@ fec=(list ovum)]
+ *               [%swap eve=@ mug=@ job=(pair @da ovum) fec=(list ovum)]
+ *               [%bail lud=(list goof)]
+ *       ==  ==
+ *   ==
+ * --
+ *
+ * NB: stdin and stdout are generally buffered, and there's no officially supported way to work
+ * around that: https://github.com/rust-lang/rust/issues/58326.
+ *
+ * We use stdin and stdout with File::from_raw_fd(0) and File::from_raw_fd(1), which gets around
+ * this. It's important to not use io::Stdin and io::Stdout directly. All printing should go to
+ * stderr.
+ */
+use crate::interpreter::Slogger;
+use crate::mem::NockStack;
+use crate::noun::{IndirectAtom, Noun, D, T};
+use crate::serialization::{cue, jam_to_vec};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::prelude::FromRawFd;
+use sword_macros::tas;
+
+crate::gdb!();
+
+/** Size of the newt frame header: one version byte and four length bytes */
+const HEADER_SIZE: usize = 5;
+
+pub struct Newt {
+    input: File,
+    output: File,
+}
+
+impl Newt {
+    /** Connect to the king over stdin and stdout */
+    pub fn new() -> Newt {
+        unsafe { Newt::from_files(File::from_raw_fd(0), File::from_raw_fd(1)) }
+    }
+
+    /** Speak the newt protocol over arbitrary files (e.g. pipes to a fake king) */
+    pub fn from_files(input: File, output: File) -> Newt {
+        Newt { input, output }
+    }
+
+    /** A slogger which writes %slog and %flog pleas to the same output as this newt */
+    pub fn slogger(&self) -> io::Result<NewtSlogger> {
+        Ok(NewtSlogger {
+            output: self.output.try_clone()?,
+        })
+    }
+
+    pub fn live(&mut self, stack: &mut NockStack) {
+        let live = T(stack, &[D(tas!(b"live")), D(0)]);
+        write_noun(&mut self.output, stack, live);
+    }
+
+    pub fn ripe(&mut self, stack: &mut NockStack, eve: u64, mug: u64) {
+        let version = T(
+            stack,
+            &[
+                D(1),   // newt protocol
+                D(139), // hoon kelvin
+                D(4),   // nock kelvin
+            ],
+        );
+        let ripe = T(stack, &[D(tas!(b"ripe")), version, D(eve), D(mug)]);
+        write_noun(&mut self.output, stack, ripe);
+    }
+
+    pub fn slog(&mut self, stack: &mut NockStack, pri: u64, tank: Noun) {
+        let slog = T(stack, &[D(tas!(b"slog")), D(pri), tank]);
+        write_noun(&mut self.output, stack, slog);
+    }
+
+    pub fn flog(&mut self, stack: &mut NockStack, cord: Noun) {
+        let flog = T(stack, &[D(tas!(b"flog")), cord]);
+        write_noun(&mut self.output, stack, flog);
+    }
+
+    /** Send %peek %done, the result of a successful peek */
+    pub fn peek_done(&mut self, stack: &mut NockStack, dat: Noun) {
+        let peek = T(stack, &[D(tas!(b"peek")), D(tas!(b"done")), dat]);
+        write_noun(&mut self.output, stack, peek);
+    }
+
+    /** Send %peek %bail, the goof from a crashed peek */
+    pub fn peek_bail(&mut self, stack: &mut NockStack, dud: Noun) {
+        let peek = T(stack, &[D(tas!(b"peek")), D(tas!(b"bail")), dud]);
+        write_noun(&mut self.output, stack, peek);
+    }
+
+    /** Send %play %done, with the mug of the state after replaying the batch */
+    pub fn play_done(&mut self, stack: &mut NockStack, mug: u64) {
+        let play = T(stack, &[D(tas!(b"play")), D(tas!(b"done")), D(mug)]);
+        write_noun(&mut self.output, stack, play);
+    }
+
+    /** Send %play %bail, with the number of the event which failed to replay */
+    pub fn play_bail(&mut self, stack: &mut NockStack, eve: u64, mug: u64, dud: Noun) {
+        let play = T(
+            stack,
+            &[D(tas!(b"play")), D(tas!(b"bail")), D(eve), D(mug), dud],
+        );
+        write_noun(&mut self.output, stack, play);
+    }
+
+    /** Send %work %done, with the new event number, state mug, and effects */
+    pub fn work_done(&mut self, stack: &mut NockStack, eve: u64, mug: u64, fec: Noun) {
+        let work = T(
+            stack,
+            &[D(tas!(b"work")), D(tas!(b"done")), D(eve), D(mug), fec],
+        );
+        write_noun(&mut self.output, stack, work);
+    }
+
+    /** Send %work %swap, when the event crashed and was replaced by a %crud */
+    pub fn work_swap(&mut self, stack: &mut NockStack, eve: u64, mug: u64, job: Noun, fec: Noun) {
+        let work = T(
+            stack,
+            &[D(tas!(b"work")), D(tas!(b"swap")), D(eve), D(mug), job, fec],
+        );
+        write_noun(&mut self.output, stack, work);
+    }
+
+    /** Send %work %bail, when both the event and its %crud crashed */
+    pub fn work_bail(&mut self, stack: &mut NockStack, lud: Noun) {
+        let work = T(stack, &[D(tas!(b"work")), D(tas!(b"bail")), lud]);
+        write_noun(&mut self.output, stack, work);
+    }
+
+    /** Send any noun, as a fake king sends writs in tests */
+    pub fn send(&mut self, stack: &mut NockStack, noun: Noun) {
+        write_noun(&mut self.output, stack, noun);
+    }
+
+    /** Fetch the next message from the king. Returns None when the king hangs up. */
+    pub fn next(&mut self, stack: &mut NockStack) -> Option<Noun> {
+        let mut header = [0u8; HEADER_SIZE];
+        if let Err(err) = self.input.read_exact(&mut header) {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                return None;
+            } else {
+                panic!("newt: error reading header: {}", err);
+            }
+        }
+        if header[0] != 0 {
+            panic!("newt: unknown protocol version {}", header[0]);
+        }
+        let size = u32::from_le_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if size == 0 {
+            return Some(D(0));
+        }
+
+        let atom = unsafe {
+            let (mut atom, dest) = IndirectAtom::new_raw_mut_bytes(stack, size);
+            if let Err(err) = self.input.read_exact(dest) {
+                if err.kind() == io::ErrorKind::UnexpectedEof {
+                    return None;
+                } else {
+                    panic!("newt: error reading body: {}", err);
+                }
+            }
+            atom.normalize_as_atom()
+        };
+        Some(cue(stack, atom).expect("newt: could not cue message"))
+    }
+}
+
+impl Default for Newt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/** Writes %slog and %flog pleas to the king on behalf of the interpreter */
+pub struct NewtSlogger {
+    output: File,
+}
+
+impl Slogger for NewtSlogger {
+    fn slog(&mut self, stack: &mut NockStack, pri: u64, tank: Noun) {
+        let slog = T(stack, &[D(tas!(b"slog")), D(pri), tank]);
+        write_noun(&mut self.output, stack, slog);
+    }
+
+    fn flog(&mut self, stack: &mut NockStack, cord: Noun) {
+        let flog = T(stack, &[D(tas!(b"flog")), cord]);
+        write_noun(&mut self.output, stack, flog);
+    }
+}
+
+/** Jam a noun and write it to the output with a newt header. */
+fn write_noun(output: &mut File, stack: &mut NockStack, noun: Noun) {
+    let bytes = jam_to_vec(stack, noun);
+    let size = bytes.len();
+    if size > u32::MAX as usize {
+        panic!("newt: message of {} bytes is too large to send", size);
+    }
+
+    let mut header = [0u8; HEADER_SIZE];
+    header[1..].copy_from_slice(&(size as u32).to_le_bytes());
+    output
+        .write_all(&header)
+        .and_then(|_| output.write_all(&bytes))
+        .expect("newt: could not write message");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::{assert_noun_eq, init_context};
+    use std::fs::OpenOptions;
+
+    #[test]
+    fn test_newt_round_trip() {
+        let mut c = init_context();
+        let stack = &mut c.stack;
+
+        let path = std::env::temp_dir().join(format!("sword-newt-{}", std::process::id()));
+        let output = File::create(&path).unwrap();
+        let input = OpenOptions::new().read(true).open(&path).unwrap();
+        let mut newt = Newt::from_files(input, output);
+
+        newt.live(stack);
+        newt.play_done(stack, 0xdeadbeef);
+
+        let live = T(stack, &[D(tas!(b"live")), D(0)]);
+        let play = T(stack, &[D(tas!(b"play")), D(tas!(b"done")), D(0xdeadbeef)]);
+        let got = newt.next(stack).unwrap();
+        assert_noun_eq(stack, got, live);
+        let got = newt.next(stack).unwrap();
+        assert_noun_eq(stack, got, play);
+        assert!(newt.next(stack).is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,799 @@
+/** Persistent memory arena
+ *
+ * A file-backed, copy-on-write arena for nouns which outlive a computation. See
+ * docs/persistence.md for the design.
+ *
+ * The arena is a fixed range of virtual memory. Each mapped page of the range is backed by a
+ * page-sized block of the backing file. The first two blocks of the file are metadata pages, which
+ * are written alternately by [Pma::commit]. The metadata page with a valid checksum and the higher
+ * counter describes the committed state: the page directory (which block backs each page), the
+ * allocation high-water mark, and a root noun.
+ *
+ * Committed pages are mapped read-only. The first write to such a page faults, and our SIGSEGV
+ * handler copies the page to a fresh block of the file and maps that block read-write over the same
+ * address. The committed block is left untouched until a later commit stops referencing it.
+ *
+ * XX: allocation is a bump pointer; nothing is ever freed. The phkmalloc-style allocator in the
+ * design document is not yet implemented.
+ *
+ * The arena is single-writer: pages must only be dirtied from one thread at a time.
+ */
+pub mod btree;
+
+use crate::mem::word_size_of;
+use crate::noun::{Cell, CellMemory, IndirectAtom, Noun, NounAllocator, D};
+use either::Either::*;
+use murmur3::murmur3_32_of_slice;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::FileExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr::{self, copy_nonoverlapping, null_mut};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Once;
+use thiserror::Error;
+
+crate::gdb!();
+
+/** Size of an arena page and a file block, in bytes */
+pub const PMA_PAGE_SIZE: usize = 4096;
+
+/** Default base address of the arena. Nouns hold absolute pointers, so this must not change. */
+pub const PMA_DEFAULT_BASE: usize = 0x100_0000_0000;
+
+/** Default size of the arena's virtual address range, in pages (16GiB) */
+pub const PMA_DEFAULT_PAGES: usize = 1 << 22;
+
+const PMA_MAGIC: u64 = u64::from_le_bytes(*b"SWORDPMA");
+const PMA_VERSION: u64 = 1;
+
+/** Blocks 0 and 1 of the file are metadata pages */
+const METADATA_BLOCKS: u64 = 2;
+
+/** Maximum number of arenas open at once in a process */
+const MAX_ARENAS: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum PmaError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("no valid metadata page")]
+    BadMetadata,
+    #[error("arena was created at base {0:#x}, not {1:#x}")]
+    BaseMismatch(usize, usize),
+    #[error("address range for arena is unavailable")]
+    AddressInUse,
+    #[error("too many arenas open")]
+    TooManyArenas,
+    #[error("arena is full")]
+    Full,
+}
+
+pub type Result<T> = std::result::Result<T, PmaError>;
+
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+struct Metadata {
+    magic: u64,
+    version: u64,
+    /** Incremented on every commit; the higher counter wins */
+    counter: u64,
+    /** Base address of the arena */
+    base: u64,
+    /** Bytes of the arena in use */
+    alloc_offset: u64,
+    /** Pages of the arena which are mapped, and thus entries in the directory */
+    mapped_pages: u64,
+    /** First block past the end of the used portion of the file */
+    next_block: u64,
+    /** First block of the directory, which is contiguous */
+    directory_block: u64,
+    /** Raw root noun */
+    root: u64,
+    checksum: u64,
+}
+
+const METADATA_WORDS: usize = word_size_of::<Metadata>();
+
+impl Metadata {
+    fn words(&self) -> &[u64; METADATA_WORDS] {
+        unsafe { &*(self as *const Metadata as *const [u64; METADATA_WORDS]) }
+    }
+
+    fn compute_checksum(&self) -> u64 {
+        let mut bytes = [0u8; (METADATA_WORDS - 1) << 3];
+        for (i, word) in self.words()[..METADATA_WORDS - 1].iter().enumerate() {
+            bytes[i << 3..(i + 1) << 3].copy_from_slice(&word.to_le_bytes());
+        }
+        murmur3_32_of_slice(&bytes, 0xcafe) as u64
+    }
+
+    fn to_bytes(mut self) -> [u8; METADATA_WORDS << 3] {
+        self.checksum = self.compute_checksum();
+        let mut bytes = [0u8; METADATA_WORDS << 3];
+        for (i, word) in self.words().iter().enumerate() {
+            bytes[i << 3..(i + 1) << 3].copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; METADATA_WORDS << 3]) -> Option<Metadata> {
+        let mut meta = Metadata::default();
+        let words = unsafe { &mut *(&mut meta as *mut Metadata as *mut [u64; METADATA_WORDS]) };
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = u64::from_le_bytes(bytes[i << 3..(i + 1) << 3].try_into().unwrap());
+        }
+        if meta.magic == PMA_MAGIC
+            && meta.version == PMA_VERSION
+            && meta.checksum == meta.compute_checksum()
+        {
+            Some(meta)
+        } else {
+            None
+        }
+    }
+
+    fn directory_blocks(&self) -> u64 {
+        directory_blocks(self.mapped_pages as usize)
+    }
+}
+
+fn directory_blocks(pages: usize) -> u64 {
+    ((pages << 3).div_ceil(PMA_PAGE_SIZE)) as u64
+}
+
+/** State shared with the SIGSEGV handler.
+ *
+ * All of the vectors are allocated with enough capacity up front that the handler never allocates.
+ */
+struct Arena {
+    fd: i32,
+    base: usize,
+    pages: usize,
+    /** File block backing each page; 0 if the page is unmapped */
+    blocks: Vec<u64>,
+    /** Whether each page has been copied since the last commit */
+    dirty: Vec<bool>,
+    /** Pages copied since the last commit */
+    dirty_list: Vec<usize>,
+    /** Blocks available for copying into */
+    free: Vec<u64>,
+    /** Blocks which become free once the next commit succeeds */
+    retired: Vec<u64>,
+    /** First block past the end of the used portion of the file */
+    next_block: u64,
+    /** Blocks in the file */
+    file_blocks: u64,
+}
+
+impl Arena {
+    fn contains(&self, addr: usize) -> bool {
+        addr >= self.base && addr < self.base + self.pages * PMA_PAGE_SIZE
+    }
+
+    /** Take a block to back a dirty page, extending the file if necessary.
+     *
+     * Called from the signal handler, so this must not allocate.
+     */
+    unsafe fn take_block(&mut self) -> Option<u64> {
+        if let Some(block) = self.free.pop() {
+            return Some(block);
+        }
+        let block = self.next_block;
+        if block >= self.file_blocks {
+            let new_blocks = (self.file_blocks * 2).max(block + 1);
+            if libc::ftruncate(
+                self.fd,
+                (new_blocks as usize * PMA_PAGE_SIZE) as libc::off_t,
+            ) != 0
+            {
+                return None;
+            }
+            self.file_blocks = new_blocks;
+        }
+        self.next_block += 1;
+        Some(block)
+    }
+
+    /** Copy a clean page to a fresh block and map it read-write in place.
+     *
+     * Called from the signal handler, so this must not allocate.
+     */
+    unsafe fn copy_on_write(&mut self, page: usize) -> bool {
+        if self.dirty[page] || self.blocks[page] == 0 {
+            return false;
+        }
+        let block = match self.take_block() {
+            Some(block) => block,
+            None => return false,
+        };
+        let addr = (self.base + page * PMA_PAGE_SIZE) as *mut libc::c_void;
+        let offset = (block as usize * PMA_PAGE_SIZE) as libc::off_t;
+        if libc::pwrite(self.fd, addr, PMA_PAGE_SIZE, offset) != PMA_PAGE_SIZE as isize {
+            return false;
+        }
+        let mapped = libc::mmap(
+            addr,
+            PMA_PAGE_SIZE,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED | libc::MAP_FIXED,
+            self.fd,
+            offset,
+        );
+        if mapped != addr {
+            return false;
+        }
+        self.retired.push(self.blocks[page]);
+        self.blocks[page] = block;
+        self.dirty[page] = true;
+        self.dirty_list.push(page);
+        true
+    }
+}
+
+static ARENAS: [AtomicPtr<Arena>; MAX_ARENAS] = [const { AtomicPtr::new(null_mut()) }; MAX_ARENAS];
+static INSTALL_HANDLER: Once = Once::new();
+static mut PREVIOUS_HANDLER: Option<libc::sigaction> = None;
+
+unsafe extern "C" fn handle_segv(
+    sig: libc::c_int,
+    info: *mut libc::siginfo_t,
+    context: *mut libc::c_void,
+) {
+    let addr = (*info).si_addr() as usize;
+    for slot in ARENAS.iter() {
+        let arena = slot.load(Ordering::Acquire);
+        if !arena.is_null() && (*arena).contains(addr) {
+            let page = (addr - (*arena).base) / PMA_PAGE_SIZE;
+            if (*arena).copy_on_write(page) {
+                return;
+            }
+            break;
+        }
+    }
+
+    // Not a write to a clean arena page: defer to whoever was handling SIGSEGV before us
+    match *ptr::addr_of!(PREVIOUS_HANDLER) {
+        Some(previous)
+            if previous.sa_sigaction != libc::SIG_DFL && previous.sa_sigaction != libc::SIG_IGN =>
+        {
+            if previous.sa_flags & libc::SA_SIGINFO != 0 {
+                let handler: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                    std::mem::transmute(previous.sa_sigaction);
+                handler(sig, info, context);
+            } else {
+                let handler: extern "C" fn(libc::c_int) =
+                    std::mem::transmute(previous.sa_sigaction);
+                handler(sig);
+            }
+        }
+        _ => {
+            // Returning re-executes the faulting instruction, which now gets the default action
+            libc::signal(libc::SIGSEGV, libc::SIG_DFL);
+        }
+    }
+}
+
+fn install_handler() {
+    INSTALL_HANDLER.call_once(|| unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handle_segv as usize;
+        action.sa_flags = libc::SA_SIGINFO | libc::SA_NODEFER;
+        libc::sigemptyset(&mut action.sa_mask);
+        let mut previous: libc::sigaction = std::mem::zeroed();
+        if libc::sigaction(libc::SIGSEGV, &action, &mut previous) != 0 {
+            panic!("pma: could not install SIGSEGV handler");
+        }
+        *ptr::addr_of_mut!(PREVIOUS_HANDLER) = Some(previous);
+    });
+}
+
+pub struct Pma {
+    file: File,
+    arena: Box<Arena>,
+    slot: usize,
+    meta: Metadata,
+}
+
+impl Pma {
+    /** Open or create an arena backed by the file at path, at the default base address */
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Pma> {
+        Pma::open_at(path, PMA_DEFAULT_BASE, PMA_DEFAULT_PAGES)
+    }
+
+    /** Open or create an arena backed by the file at path
+     *
+     * base:    address of the arena; must match the address the file was created with
+     * pages:   size of the arena's virtual address range, in pages
+     */
+    pub fn open_at<P: AsRef<Path>>(path: P, base: usize, pages: usize) -> Result<Pma> {
+        assert!(base % PMA_PAGE_SIZE == 0, "pma: unaligned base address");
+        install_handler();
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let file_len = file.metadata()?.len();
+        let file_blocks = file_len / PMA_PAGE_SIZE as u64;
+
+        let fresh = Metadata {
+            magic: PMA_MAGIC,
+            version: PMA_VERSION,
+            counter: 0,
+            base: base as u64,
+            alloc_offset: 0,
+            mapped_pages: 0,
+            next_block: METADATA_BLOCKS,
+            directory_block: 0,
+            root: unsafe { D(0).as_raw() },
+            checksum: 0,
+        };
+        let meta = if file_blocks < METADATA_BLOCKS {
+            // A new arena
+            file.set_len(METADATA_BLOCKS * PMA_PAGE_SIZE as u64)?;
+            fresh
+        } else {
+            let mut best: Option<Metadata> = None;
+            let mut blank = true;
+            for block in 0..METADATA_BLOCKS {
+                let mut bytes = [0u8; METADATA_WORDS << 3];
+                file.read_exact_at(&mut bytes, block * PMA_PAGE_SIZE as u64)?;
+                blank &= bytes.iter().all(|b| *b == 0);
+                if let Some(meta) = Metadata::from_bytes(&bytes) {
+                    if best.is_none_or(|b| meta.counter > b.counter) {
+                        best = Some(meta);
+                    }
+                }
+            }
+            match best {
+                Some(meta) => meta,
+                // Created but never committed
+                None if blank => fresh,
+                None => return Err(PmaError::BadMetadata),
+            }
+        };
+        if meta.base as usize != base {
+            return Err(PmaError::BaseMismatch(meta.base as usize, base));
+        }
+        if meta.mapped_pages as usize > pages {
+            return Err(PmaError::Full);
+        }
+
+        // Read the directory
+        let mut blocks = vec![0u64; pages];
+        if meta.mapped_pages > 0 {
+            let mut bytes = vec![0u8; meta.mapped_pages as usize * 8];
+            file.read_exact_at(&mut bytes, meta.directory_block * PMA_PAGE_SIZE as u64)?;
+            for (i, block) in blocks[..meta.mapped_pages as usize].iter_mut().enumerate() {
+                *block = u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap());
+            }
+        }
+
+        // Anything not referenced by the committed state is free
+        let mut used = vec![false; meta.next_block as usize];
+        for block in 0..METADATA_BLOCKS {
+            used[block as usize] = true;
+        }
+        for i in 0..meta.directory_blocks() {
+            used[(meta.directory_block + i) as usize] = true;
+        }
+        for block in &blocks[..meta.mapped_pages as usize] {
+            used[*block as usize] = true;
+        }
+        let mut free = Vec::with_capacity(pages);
+        free.extend(
+            (0..meta.next_block)
+                .rev()
+                .filter(|block| !used[*block as usize]),
+        );
+
+        // Reserve the address range, then map the committed pages read-only over it
+        unsafe {
+            let reserved = libc::mmap(
+                base as *mut libc::c_void,
+                pages * PMA_PAGE_SIZE,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE
+                    | libc::MAP_ANONYMOUS
+                    | libc::MAP_NORESERVE
+                    | libc::MAP_FIXED_NOREPLACE,
+                -1,
+                0,
+            );
+            if reserved != base as *mut libc::c_void {
+                if reserved != libc::MAP_FAILED {
+                    libc::munmap(reserved, pages * PMA_PAGE_SIZE);
+                }
+                return Err(PmaError::AddressInUse);
+            }
+        }
+
+        let arena = Box::new(Arena {
+            fd: file.as_raw_fd(),
+            base,
+            pages,
+            blocks,
+            dirty: vec![false; pages],
+            dirty_list: Vec::with_capacity(pages),
+            free,
+            retired: Vec::with_capacity(pages),
+            next_block: meta.next_block,
+            file_blocks: file_blocks.max(METADATA_BLOCKS),
+        });
+
+        let mut pma = Pma {
+            file,
+            arena,
+            slot: MAX_ARENAS,
+            meta,
+        };
+
+        let mut page = 0;
+        while page < meta.mapped_pages as usize {
+            // Map runs of contiguous blocks at once
+            let first = pma.arena.blocks[page];
+            let mut run = 1;
+            while page + run < meta.mapped_pages as usize
+                && pma.arena.blocks[page + run] == first + run as u64
+            {
+                run += 1;
+            }
+            pma.map(page, run, first, libc::PROT_READ)?;
+            page += run;
+        }
+
+        let arena_ptr = &mut *pma.arena as *mut Arena;
+        for (i, slot) in ARENAS.iter().enumerate() {
+            if slot
+                .compare_exchange(null_mut(), arena_ptr, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                pma.slot = i;
+                return Ok(pma);
+            }
+        }
+        Err(PmaError::TooManyArenas)
+    }
+
+    fn map(&self, page: usize, count: usize, block: u64, prot: libc::c_int) -> Result<()> {
+        unsafe {
+            let addr = (self.arena.base + page * PMA_PAGE_SIZE) as *mut libc::c_void;
+            let mapped = libc::mmap(
+                addr,
+                count * PMA_PAGE_SIZE,
+                prot,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                self.arena.fd,
+                (block as usize * PMA_PAGE_SIZE) as libc::off_t,
+            );
+            if mapped != addr {
+                return Err(std::io::Error::last_os_error().into());
+            }
+        }
+        Ok(())
+    }
+
+    /** Whether a pointer is into this arena */
+    pub fn contains<T>(&self, ptr: *const T) -> bool {
+        self.arena.contains(ptr as usize)
+    }
+
+    /** Number of pages dirtied since the last commit */
+    pub fn dirty_pages(&self) -> usize {
+        self.arena.dirty_list.len()
+    }
+
+    /** The root noun as of the last call to [Pma::set_root]; the atom 0 in a new arena */
+    pub fn root(&self) -> Noun {
+        unsafe { Noun::from_raw(self.meta.root) }
+    }
+
+    /** Set the root noun, which will be persisted on the next commit. It must be in the arena. */
+    pub fn set_root(&mut self, root: Noun) {
+        if let Ok(allocated) = root.as_allocated() {
+            assert!(
+                self.contains(unsafe { allocated.to_raw_pointer() }),
+                "pma: root is not in the arena"
+            );
+        }
+        self.meta.root = unsafe { root.as_raw() };
+    }
+
+    /** Allocate words in the arena, mapping new pages as needed */
+    pub unsafe fn alloc_words(&mut self, words: usize) -> Result<*mut u64> {
+        let start = self.meta.alloc_offset as usize;
+        let end = start + (words << 3);
+        let needed_pages = end.div_ceil(PMA_PAGE_SIZE);
+        if needed_pages > self.arena.pages {
+            return Err(PmaError::Full);
+        }
+        while (self.meta.mapped_pages as usize) < needed_pages {
+            let page = self.meta.mapped_pages as usize;
+            let block = self
+                .arena
+                .take_block()
+                .ok_or_else(std::io::Error::last_os_error)?;
+            self.map(page, 1, block, libc::PROT_READ | libc::PROT_WRITE)?;
+            self.arena.blocks[page] = block;
+            self.arena.dirty[page] = true;
+            self.arena.dirty_list.push(page);
+            self.meta.mapped_pages += 1;
+        }
+        self.meta.alloc_offset = end as u64;
+        Ok((self.arena.base + start) as *mut u64)
+    }
+
+    /** Copy a noun into the arena, preserving structure sharing.
+     *
+     * Any part of the noun which is already in the arena is not copied.
+     */
+    pub fn copy_noun(&mut self, noun: Noun) -> Result<Noun> {
+        let mut copied: HashMap<u64, Noun> = HashMap::new();
+        let mut res = noun;
+        let mut work: Vec<(Noun, *mut Noun)> = vec![(noun, &mut res as *mut Noun)];
+        unsafe {
+            while let Some((noun, dest)) = work.pop() {
+                let allocated = match noun.as_either_direct_allocated() {
+                    Left(_) => {
+                        *dest = noun;
+                        continue;
+                    }
+                    Right(allocated) => allocated,
+                };
+                if self.contains(allocated.to_raw_pointer()) {
+                    *dest = noun;
+                    continue;
+                }
+                if let Some(copy) = copied.get(&noun.as_raw()) {
+                    *dest = *copy;
+                    continue;
+                }
+
+                let copy = match allocated.as_either() {
+                    Left(indirect) => {
+                        let raw_size = indirect.size() + 2;
+                        let buf = self.alloc_words(raw_size)?;
+                        copy_nonoverlapping(indirect.to_raw_pointer(), buf, raw_size);
+                        IndirectAtom::from_raw_pointer(buf).as_noun()
+                    }
+                    Right(cell) => {
+                        let buf =
+                            self.alloc_words(word_size_of::<CellMemory>())? as *mut CellMemory;
+                        (*buf).metadata = (*cell.to_raw_pointer()).metadata;
+                        work.push((cell.tail(), ptr::addr_of_mut!((*buf).tail)));
+                        work.push((cell.head(), ptr::addr_of_mut!((*buf).head)));
+                        Cell::from_raw_pointer(buf).as_noun()
+                    }
+                };
+                copied.insert(noun.as_raw(), copy);
+                *dest = copy;
+            }
+        }
+        Ok(res)
+    }
+
+    /** Durably commit all changes since the last commit.
+     *
+     * Dirty pages are synced, then a new directory is written to fresh blocks, then the metadata
+     * page not used by the last commit is overwritten. Only when that is durable are blocks which
+     * the last commit referenced and this one does not released for reuse.
+     */
+    pub fn commit(&mut self) -> Result<()> {
+        unsafe {
+            for page in &self.arena.dirty_list {
+                let addr = (self.arena.base + page * PMA_PAGE_SIZE) as *mut libc::c_void;
+                if libc::msync(addr, PMA_PAGE_SIZE, libc::MS_SYNC) != 0 {
+                    return Err(std::io::Error::last_os_error().into());
+                }
+            }
+        }
+
+        // The directory is written to fresh contiguous blocks at the end of the file, so the
+        // committed directory is never overwritten.
+        let mapped_pages = self.meta.mapped_pages as usize;
+        let mut directory_block = 0;
+        let dir_blocks = directory_blocks(mapped_pages);
+        if mapped_pages > 0 {
+            directory_block = self.arena.next_block;
+            let mut bytes = vec![0u8; dir_blocks as usize * PMA_PAGE_SIZE];
+            for (i, block) in self.arena.blocks[..mapped_pages].iter().enumerate() {
+                bytes[i * 8..(i + 1) * 8].copy_from_slice(&block.to_le_bytes());
+            }
+            self.file
+                .write_all_at(&bytes, directory_block * PMA_PAGE_SIZE as u64)?;
+            self.arena.next_block += dir_blocks;
+            self.arena.file_blocks = self.arena.file_blocks.max(self.arena.next_block);
+            self.file.sync_data()?;
+        }
+
+        let old_directory = (self.meta.directory_block, self.meta.directory_blocks());
+        let mut meta = self.meta;
+        meta.counter += 1;
+        meta.next_block = self.arena.next_block;
+        meta.directory_block = directory_block;
+        self.file.write_all_at(
+            &meta.to_bytes(),
+            (meta.counter % METADATA_BLOCKS) * PMA_PAGE_SIZE as u64,
+        )?;
+        self.file.sync_data()?;
+        self.meta = meta;
+
+        // Committed: protect the dirty pages again and release superseded blocks
+        let arena = &mut *self.arena;
+        for page in arena.dirty_list.drain(..) {
+            arena.dirty[page] = false;
+            unsafe {
+                let addr = (arena.base + page * PMA_PAGE_SIZE) as *mut libc::c_void;
+                if libc::mprotect(addr, PMA_PAGE_SIZE, libc::PROT_READ) != 0 {
+                    return Err(std::io::Error::last_os_error().into());
+                }
+            }
+        }
+        arena.free.append(&mut arena.retired);
+        arena
+            .free
+            .extend(old_directory.0..old_directory.0 + old_directory.1);
+        Ok(())
+    }
+}
+
+/** Allocation through this trait can't fail, so it panics when the arena is full. Use
+ * [Pma::copy_noun] or [Pma::alloc_words] to handle that.
+ */
+impl NounAllocator for Pma {
+    unsafe fn alloc_indirect(&mut self, words: usize) -> *mut u64 {
+        self.alloc_words(words + 2).expect("pma: allocation failed")
+    }
+
+    unsafe fn alloc_cell(&mut self) -> *mut CellMemory {
+        self.alloc_words(word_size_of::<CellMemory>())
+            .expect("pma: allocation failed") as *mut CellMemory
+    }
+
+    unsafe fn alloc_struct<T>(&mut self, count: usize) -> *mut T {
+        self.alloc_words(word_size_of::<T>() * count)
+            .expect("pma: allocation failed") as *mut T
+    }
+}
+
+impl Drop for Pma {
+    /** Unmap the arena. Uncommitted changes are discarded. */
+    fn drop(&mut self) {
+        if self.slot < MAX_ARENAS {
+            ARENAS[self.slot].store(null_mut(), Ordering::Release);
+        }
+        unsafe {
+            libc::munmap(
+                self.arena.base as *mut libc::c_void,
+                self.arena.pages * PMA_PAGE_SIZE,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::assert_noun_eq;
+    use crate::mem::NockStack;
+    use crate::noun::T;
+    use std::path::PathBuf;
+
+    const TEST_PAGES: usize = 1 << 10;
+
+    fn test_pma(name: &str, index: usize) -> (PathBuf, usize) {
+        let path = std::env::temp_dir().join(format!("sword-pma-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        (path, PMA_DEFAULT_BASE + (index << 32))
+    }
+
+    fn big_noun(stack: &mut NockStack) -> Noun {
+        let big = unsafe { IndirectAtom::new_raw_bytes_ref(stack, &[0xab; 100]).as_noun() };
+        let shared = T(stack, &[D(1), big, D(2)]);
+        T(stack, &[shared, shared, D(3)])
+    }
+
+    #[test]
+    fn test_commit_and_reopen() {
+        let (path, base) = test_pma("reopen", 1);
+        let mut stack = NockStack::new(1 << 16, 0);
+        let noun = big_noun(&mut stack);
+        {
+            let mut pma = Pma::open_at(&path, base, TEST_PAGES).unwrap();
+            let copy = pma.copy_noun(noun).unwrap();
+            pma.set_root(copy);
+            pma.commit().unwrap();
+            assert_eq!(pma.dirty_pages(), 0);
+        }
+        {
+            let pma = Pma::open_at(&path, base, TEST_PAGES).unwrap();
+            let root = pma.root();
+            assert!(pma.contains(unsafe { root.as_allocated().unwrap().to_raw_pointer() }));
+            // Structure sharing survives the copy
+            let head = root.as_cell().unwrap().head().as_cell().unwrap();
+            let tail = root.as_cell().unwrap().tail().as_cell().unwrap().head();
+            assert!(unsafe { head.as_noun().raw_equals(tail) });
+            assert_noun_eq(&mut stack, root, noun);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_uncommitted_changes_are_discarded() {
+        let (path, base) = test_pma("discard", 2);
+        let mut stack = NockStack::new(1 << 16, 0);
+        let first = big_noun(&mut stack);
+        let second = T(&mut stack, &[first, D(4)]);
+        {
+            let mut pma = Pma::open_at(&path, base, TEST_PAGES).unwrap();
+            let copy = pma.copy_noun(first).unwrap();
+            pma.set_root(copy);
+            pma.commit().unwrap();
+
+            // Writing into the partially-filled committed page copies it
+            let copy = pma.copy_noun(second).unwrap();
+            pma.set_root(copy);
+            assert!(pma.dirty_pages() > 0);
+        }
+        {
+            let pma = Pma::open_at(&path, base, TEST_PAGES).unwrap();
+            assert_noun_eq(&mut stack, pma.root(), first);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_copy_on_write_preserves_committed_blocks() {
+        let (path, base) = test_pma("cow", 3);
+        let mut stack = NockStack::new(1 << 16, 0);
+        let noun = big_noun(&mut stack);
+        let mut pma = Pma::open_at(&path, base, TEST_PAGES).unwrap();
+        let copy = pma.copy_noun(noun).unwrap();
+        pma.set_root(copy);
+        pma.commit().unwrap();
+        let committed_block = pma.arena.blocks[0];
+
+        // Write directly into the committed page
+        let atom = copy.as_cell().unwrap().head().as_cell().unwrap();
+        unsafe {
+            *atom.head_as_mut() = D(5);
+        }
+        assert_eq!(pma.dirty_pages(), 1);
+        assert_ne!(pma.arena.blocks[0], committed_block);
+
+        // The committed block still holds the old contents
+        let mut bytes = [0u8; PMA_PAGE_SIZE];
+        pma.file
+            .read_exact_at(&mut bytes, committed_block * PMA_PAGE_SIZE as u64)
+            .unwrap();
+        let page = unsafe { std::slice::from_raw_parts(base as *const u8, PMA_PAGE_SIZE) };
+        assert_ne!(&bytes[..], page);
+
+        pma.commit().unwrap();
+        assert!(pma.arena.free.contains(&committed_block));
+        drop(pma);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_full_arena() {
+        let (path, base) = test_pma("full", 4);
+        let mut stack = NockStack::new(1 << 16, 0);
+        let big = unsafe { IndirectAtom::new_raw_bytes_ref(&mut stack, &[0xcd; 5000]).as_noun() };
+        let mut pma = Pma::open_at(&path, base, 1).unwrap();
+        assert!(matches!(pma.copy_noun(big), Err(PmaError::Full)));
+
+        // The arena is still usable after a failed copy
+        let noun = big_noun(&mut stack);
+        let copy = pma.copy_noun(noun).unwrap();
+        pma.set_root(copy);
+        pma.commit().unwrap();
+        drop(pma);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
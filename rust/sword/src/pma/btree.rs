@@ -0,0 +1,643 @@
+/** B+ tree page directory
+ *
+ * A durable map from arena pages to page-sized intervals (PSIs) of a backing file. See
+ * docs/b-trees.md for the design.
+ *
+ * The tree is keyed by page number (page base address >> 12). Leaves hold interval starts: a leaf
+ * entry (start, block) maps each page p >= start, up to the next entry's start, to block
+ * block + (p - start). A block of 0 denotes an unmapped interval, since the first two PSIs of the
+ * file are the double-buffered metadata pages. Every leaf but the leftmost begins with an entry at
+ * its separator key, so a lookup never needs to consult a neighboring leaf.
+ *
+ * Keys, children and blocks are stored as 5-byte offsets. Each node occupies one PSI, with an
+ * 8-byte header, leaving room for 408 keys (and 409 children in an internal node).
+ *
+ * Nodes are copy-on-write: modifying a node that is not already dirty writes it to a fresh PSI,
+ * which dirties its parent, and so on up to the root. [PageDirectory::commit] syncs the file and
+ * then writes the new root into whichever metadata page is not current. PSIs referenced only by the
+ * previous commit are reused once the new commit is durable.
+ */
+use crate::pma::PMA_PAGE_SIZE;
+use murmur3::murmur3_32_of_slice;
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use thiserror::Error;
+
+/** Bytes per stored key, child, or block */
+const OFFSET_SIZE: usize = 5;
+const OFFSET_MAX: u64 = (1 << (OFFSET_SIZE * 8)) - 1;
+
+const HEADER_SIZE: usize = 8;
+
+/** Maximum keys in a node */
+const MAX_KEYS: usize = (PMA_PAGE_SIZE - HEADER_SIZE - OFFSET_SIZE) / (2 * OFFSET_SIZE);
+
+const NODE_LEAF: u8 = 1;
+const NODE_INTERNAL: u8 = 2;
+
+const DIR_MAGIC: u64 = u64::from_le_bytes(*b"SWORDDIR");
+const DIR_VERSION: u64 = 1;
+const METADATA_BLOCKS: u64 = 2;
+
+#[derive(Debug, Error)]
+pub enum DirectoryError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("no valid metadata page")]
+    BadMetadata,
+    #[error("malformed node at block {0}")]
+    BadNode(u64),
+    #[error("offset {0:#x} does not fit in 5 bytes")]
+    OffsetTooLarge(u64),
+}
+
+pub type Result<T> = std::result::Result<T, DirectoryError>;
+
+#[derive(Copy, Clone, Debug, Default)]
+struct Metadata {
+    counter: u64,
+    /** Block of the root node, or 0 for an empty tree */
+    root: u64,
+    /** First block past the end of the used portion of the file */
+    next_block: u64,
+}
+
+impl Metadata {
+    fn to_bytes(self) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        let words = [DIR_MAGIC, DIR_VERSION, self.counter, self.root, self.next_block];
+        for (i, word) in words.iter().enumerate() {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&word.to_le_bytes());
+        }
+        let checksum = murmur3_32_of_slice(&bytes[..40], 0xcafe) as u64;
+        bytes[40..].copy_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; 48]) -> Option<Metadata> {
+        let word = |i: usize| u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap());
+        let checksum = murmur3_32_of_slice(&bytes[..40], 0xcafe) as u64;
+        if word(0) == DIR_MAGIC && word(1) == DIR_VERSION && word(5) == checksum {
+            Some(Metadata {
+                counter: word(2),
+                root: word(3),
+                next_block: word(4),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/** A decoded node. In a leaf, vals are blocks (one per key); otherwise they are child blocks (one
+ * more than the keys).
+ */
+#[derive(Clone, Debug)]
+struct Node {
+    leaf: bool,
+    keys: Vec<u64>,
+    vals: Vec<u64>,
+}
+
+impl Node {
+    fn encode(&self) -> [u8; PMA_PAGE_SIZE] {
+        let mut bytes = [0u8; PMA_PAGE_SIZE];
+        bytes[0] = if self.leaf { NODE_LEAF } else { NODE_INTERNAL };
+        bytes[2..4].copy_from_slice(&(self.keys.len() as u16).to_le_bytes());
+        let vals_start = HEADER_SIZE + MAX_KEYS * OFFSET_SIZE;
+        for (i, key) in self.keys.iter().enumerate() {
+            write_offset(&mut bytes[HEADER_SIZE + i * OFFSET_SIZE..], *key);
+        }
+        for (i, val) in self.vals.iter().enumerate() {
+            write_offset(&mut bytes[vals_start + i * OFFSET_SIZE..], *val);
+        }
+        bytes
+    }
+
+    fn decode(block: u64, bytes: &[u8; PMA_PAGE_SIZE]) -> Result<Node> {
+        let leaf = match bytes[0] {
+            NODE_LEAF => true,
+            NODE_INTERNAL => false,
+            _ => return Err(DirectoryError::BadNode(block)),
+        };
+        let count = u16::from_le_bytes([bytes[2], bytes[3]]) as usize;
+        if count > MAX_KEYS {
+            return Err(DirectoryError::BadNode(block));
+        }
+        let vals_count = if leaf { count } else { count + 1 };
+        let vals_start = HEADER_SIZE + MAX_KEYS * OFFSET_SIZE;
+        let keys = (0..count)
+            .map(|i| read_offset(&bytes[HEADER_SIZE + i * OFFSET_SIZE..]))
+            .collect();
+        let vals = (0..vals_count)
+            .map(|i| read_offset(&bytes[vals_start + i * OFFSET_SIZE..]))
+            .collect();
+        Ok(Node { leaf, keys, vals })
+    }
+
+    /** Index of the child to descend into for a key */
+    fn child_index(&self, key: u64) -> usize {
+        self.keys.partition_point(|k| *k <= key)
+    }
+}
+
+fn write_offset(bytes: &mut [u8], offset: u64) {
+    bytes[..OFFSET_SIZE].copy_from_slice(&offset.to_le_bytes()[..OFFSET_SIZE]);
+}
+
+fn read_offset(bytes: &[u8]) -> u64 {
+    let mut word = [0u8; 8];
+    word[..OFFSET_SIZE].copy_from_slice(&bytes[..OFFSET_SIZE]);
+    u64::from_le_bytes(word)
+}
+
+fn check_offset(offset: u64) -> Result<()> {
+    if offset > OFFSET_MAX {
+        Err(DirectoryError::OffsetTooLarge(offset))
+    } else {
+        Ok(())
+    }
+}
+
+pub struct PageDirectory {
+    file: File,
+    meta: Metadata,
+    /** Blocks written since the last commit, which may be modified in place */
+    dirty: HashSet<u64>,
+    /** Blocks available for new nodes */
+    free: Vec<u64>,
+    /** Blocks which become free once the next commit succeeds */
+    retired: Vec<u64>,
+}
+
+impl PageDirectory {
+    /** Open or create a page directory stored in the file at path */
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<PageDirectory> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let blocks = file.metadata()?.len() / PMA_PAGE_SIZE as u64;
+
+        let meta = if blocks < METADATA_BLOCKS {
+            file.set_len(METADATA_BLOCKS * PMA_PAGE_SIZE as u64)?;
+            Metadata {
+                counter: 0,
+                root: 0,
+                next_block: METADATA_BLOCKS,
+            }
+        } else {
+            let mut best: Option<Metadata> = None;
+            let mut blank = true;
+            for block in 0..METADATA_BLOCKS {
+                let mut bytes = [0u8; 48];
+                file.read_exact_at(&mut bytes, block * PMA_PAGE_SIZE as u64)?;
+                blank &= bytes.iter().all(|b| *b == 0);
+                if let Some(meta) = Metadata::from_bytes(&bytes) {
+                    if best.is_none_or(|b| meta.counter > b.counter) {
+                        best = Some(meta);
+                    }
+                }
+            }
+            match best {
+                Some(meta) => meta,
+                // Created but never committed
+                None if blank => Metadata {
+                    counter: 0,
+                    root: 0,
+                    next_block: METADATA_BLOCKS,
+                },
+                None => return Err(DirectoryError::BadMetadata),
+            }
+        };
+
+        let mut dir = PageDirectory {
+            file,
+            meta,
+            dirty: HashSet::new(),
+            free: Vec::new(),
+            retired: Vec::new(),
+        };
+
+        // Any block past the metadata which the committed tree does not use is free
+        let mut used = vec![false; meta.next_block as usize];
+        let mut stack = Vec::new();
+        if meta.root != 0 {
+            stack.push(meta.root);
+        }
+        while let Some(block) = stack.pop() {
+            // Read the node first, which checks the block is in range, and reject a node reached
+            // twice, so that a corrupt child pointer is an error rather than a panic or a cycle
+            let node = dir.read_node(block)?;
+            if std::mem::replace(&mut used[block as usize], true) {
+                return Err(DirectoryError::BadNode(block));
+            }
+            if !node.leaf {
+                stack.extend(node.vals);
+            }
+        }
+        dir.free = (METADATA_BLOCKS..meta.next_block)
+            .rev()
+            .filter(|block| !used[*block as usize])
+            .collect();
+        Ok(dir)
+    }
+
+    fn read_node(&self, block: u64) -> Result<Node> {
+        if block < METADATA_BLOCKS || block >= self.meta.next_block {
+            return Err(DirectoryError::BadNode(block));
+        }
+        let mut bytes = [0u8; PMA_PAGE_SIZE];
+        self.file
+            .read_exact_at(&mut bytes, block * PMA_PAGE_SIZE as u64)?;
+        Node::decode(block, &bytes)
+    }
+
+    /** Write a node, copying it to a fresh block unless it was already dirtied. */
+    fn write_node(&mut self, old_block: Option<u64>, node: &Node) -> Result<u64> {
+        let block = match old_block {
+            Some(block) if self.dirty.contains(&block) => block,
+            _ => {
+                if let Some(block) = old_block {
+                    self.retired.push(block);
+                }
+                let block = self.free.pop().unwrap_or_else(|| {
+                    self.meta.next_block += 1;
+                    self.meta.next_block - 1
+                });
+                check_offset(block)?;
+                self.dirty.insert(block);
+                block
+            }
+        };
+        self.file
+            .write_all_at(&node.encode(), block * PMA_PAGE_SIZE as u64)?;
+        Ok(block)
+    }
+
+    /** Find the interval entry covering a page: (start, block) */
+    fn entry(&self, page: u64) -> Result<Option<(u64, u64)>> {
+        if self.meta.root == 0 {
+            return Ok(None);
+        }
+        let mut node = self.read_node(self.meta.root)?;
+        while !node.leaf {
+            let child = node.vals[node.child_index(page)];
+            node = self.read_node(child)?;
+        }
+        let idx = node.child_index(page);
+        if idx == 0 {
+            Ok(None)
+        } else {
+            Ok(Some((node.keys[idx - 1], node.vals[idx - 1])))
+        }
+    }
+
+    /** The block backing a page, or None if the page is unmapped */
+    pub fn lookup(&self, page: u64) -> Result<Option<u64>> {
+        Ok(match self.entry(page)? {
+            Some((start, block)) if block != 0 => Some(block + (page - start)),
+            _ => None,
+        })
+    }
+
+    /** Map count pages starting at page to the contiguous blocks starting at block.
+     *
+     * A block of 0 unmaps the pages.
+     */
+    pub fn map_range(&mut self, page: u64, count: u64, block: u64) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+        let end = page + count;
+        check_offset(end)?;
+        if block != 0 {
+            check_offset(block + count - 1)?;
+        }
+
+        // Whatever followed the range must still follow it
+        let after = match self.entry(end)? {
+            Some((start, b)) if b != 0 => b + (end - start),
+            _ => 0,
+        };
+
+        // Entries inside the range are rewritten to continue the new interval, so no entries need
+        // to be removed.
+        let inside = self.keys_in(page + 1, end)?;
+        self.upsert(page, block)?;
+        for key in inside {
+            let val = if block == 0 { 0 } else { block + (key - page) };
+            self.upsert(key, val)?;
+        }
+        self.upsert(end, after)
+    }
+
+    /** Mapped intervals, coalesced: (page, count, block) */
+    pub fn ranges(&self) -> Result<Vec<(u64, u64, u64)>> {
+        let mut entries = Vec::new();
+        if self.meta.root != 0 {
+            self.collect(self.meta.root, 0, u64::MAX, &mut entries)?;
+        }
+
+        let mut ranges: Vec<(u64, u64, u64)> = Vec::new();
+        for (i, (start, block)) in entries.iter().enumerate() {
+            if *block == 0 {
+                continue;
+            }
+            let end = match entries.get(i + 1) {
+                Some((next, _)) => *next,
+                None => OFFSET_MAX + 1,
+            };
+            match ranges.last_mut() {
+                Some((page, count, b)) if *page + *count == *start && *b + *count == *block => {
+                    *count += end - start;
+                }
+                _ => ranges.push((*start, end - start, *block)),
+            }
+        }
+        Ok(ranges)
+    }
+
+    /** Durably commit all changes since the last commit */
+    pub fn commit(&mut self) -> Result<()> {
+        self.file.sync_data()?;
+        let mut meta = self.meta;
+        meta.counter += 1;
+        self.file.write_all_at(
+            &meta.to_bytes(),
+            (meta.counter % METADATA_BLOCKS) * PMA_PAGE_SIZE as u64,
+        )?;
+        self.file.sync_data()?;
+        self.meta = meta;
+        self.dirty.clear();
+        self.free.append(&mut self.retired);
+        Ok(())
+    }
+
+    /** Keys of leaf entries in [lo, hi) */
+    fn keys_in(&self, lo: u64, hi: u64) -> Result<Vec<u64>> {
+        let mut entries = Vec::new();
+        if self.meta.root != 0 && lo < hi {
+            self.collect(self.meta.root, lo, hi, &mut entries)?;
+        }
+        Ok(entries.into_iter().map(|(key, _)| key).collect())
+    }
+
+    /** In-order leaf entries with keys in [lo, hi) */
+    fn collect(&self, block: u64, lo: u64, hi: u64, out: &mut Vec<(u64, u64)>) -> Result<()> {
+        let node = self.read_node(block)?;
+        if node.leaf {
+            for (key, val) in node.keys.iter().zip(node.vals.iter()) {
+                if *key >= lo && *key < hi {
+                    out.push((*key, *val));
+                }
+            }
+        } else {
+            let first = node.child_index(lo);
+            let last = node.child_index(hi.saturating_sub(1));
+            for child in &node.vals[first..=last] {
+                self.collect(*child, lo, hi, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /** Insert or replace a leaf entry, dirtying the path to it */
+    fn upsert(&mut self, key: u64, val: u64) -> Result<()> {
+        if self.meta.root == 0 {
+            let leaf = Node {
+                leaf: true,
+                keys: vec![key],
+                vals: vec![val],
+            };
+            self.meta.root = self.write_node(None, &leaf)?;
+            return Ok(());
+        }
+
+        let (root, split) = self.upsert_at(self.meta.root, key, val)?;
+        self.meta.root = match split {
+            None => root,
+            Some((sep, right)) => {
+                let node = Node {
+                    leaf: false,
+                    keys: vec![sep],
+                    vals: vec![root, right],
+                };
+                self.write_node(None, &node)?
+            }
+        };
+        Ok(())
+    }
+
+    /** Returns the node's new block, and the separator and block of a new right sibling if the
+     * node split.
+     */
+    fn upsert_at(&mut self, block: u64, key: u64, val: u64) -> Result<(u64, Option<(u64, u64)>)> {
+        let mut node = self.read_node(block)?;
+        if node.leaf {
+            match node.keys.binary_search(&key) {
+                Ok(idx) => node.vals[idx] = val,
+                Err(idx) => {
+                    node.keys.insert(idx, key);
+                    node.vals.insert(idx, val);
+                }
+            }
+        } else {
+            let idx = node.child_index(key);
+            let (child, split) = self.upsert_at(node.vals[idx], key, val)?;
+            node.vals[idx] = child;
+            if let Some((sep, right)) = split {
+                node.keys.insert(idx, sep);
+                node.vals.insert(idx + 1, right);
+            }
+        }
+
+        if node.keys.len() <= MAX_KEYS {
+            return Ok((self.write_node(Some(block), &node)?, None));
+        }
+
+        let mid = node.keys.len() / 2;
+        let (sep, right) = if node.leaf {
+            let right = Node {
+                leaf: true,
+                keys: node.keys.split_off(mid),
+                vals: node.vals.split_off(mid),
+            };
+            (right.keys[0], right)
+        } else {
+            let right_keys = node.keys.split_off(mid + 1);
+            let sep = node.keys.pop().unwrap();
+            let right = Node {
+                leaf: false,
+                keys: right_keys,
+                vals: node.vals.split_off(mid + 1),
+            };
+            (sep, right)
+        };
+        let left = self.write_node(Some(block), &node)?;
+        let right = self.write_node(None, &right)?;
+        Ok((left, Some((sep, right))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn test_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("sword-btree-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn oracle_map(oracle: &mut BTreeMap<u64, u64>, page: u64, count: u64, block: u64) {
+        for i in 0..count {
+            if block == 0 {
+                oracle.remove(&(page + i));
+            } else {
+                oracle.insert(page + i, block + i);
+            }
+        }
+    }
+
+    fn assert_matches(dir: &PageDirectory, oracle: &BTreeMap<u64, u64>, pages: u64) {
+        for page in 0..pages {
+            assert_eq!(
+                dir.lookup(page).unwrap(),
+                oracle.get(&page).copied(),
+                "page {}",
+                page
+            );
+        }
+        let mut from_ranges = BTreeMap::new();
+        for (page, count, block) in dir.ranges().unwrap() {
+            oracle_map(&mut from_ranges, page, count, block);
+        }
+        assert_eq!(&from_ranges, oracle);
+    }
+
+    #[test]
+    fn test_map_and_lookup() {
+        let path = test_path("lookup");
+        let mut dir = PageDirectory::open(&path).unwrap();
+        assert_eq!(dir.lookup(0).unwrap(), None);
+
+        dir.map_range(10, 5, 100).unwrap();
+        assert_eq!(dir.lookup(9).unwrap(), None);
+        assert_eq!(dir.lookup(10).unwrap(), Some(100));
+        assert_eq!(dir.lookup(14).unwrap(), Some(104));
+        assert_eq!(dir.lookup(15).unwrap(), None);
+
+        dir.map_range(12, 1, 7).unwrap();
+        assert_eq!(dir.lookup(11).unwrap(), Some(101));
+        assert_eq!(dir.lookup(12).unwrap(), Some(7));
+        assert_eq!(dir.lookup(13).unwrap(), Some(103));
+        assert_eq!(
+            dir.ranges().unwrap(),
+            vec![(10, 2, 100), (12, 1, 7), (13, 2, 103)]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_against_btreemap() {
+        const PAGES: u64 = 20000;
+        let path = test_path("oracle");
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let mut dir = PageDirectory::open(&path).unwrap();
+        let mut oracle = BTreeMap::new();
+        let mut committed = oracle.clone();
+
+        for round in 0..4 {
+            for _ in 0..1500 {
+                let page = rng.gen_range(0..PAGES);
+                let count = rng.gen_range(1..8).min(PAGES - page);
+                let block = if rng.gen_ratio(1, 8) {
+                    0
+                } else {
+                    rng.gen_range(2..1 << 30)
+                };
+                dir.map_range(page, count, block).unwrap();
+                oracle_map(&mut oracle, page, count, block);
+            }
+            assert_matches(&dir, &oracle, PAGES);
+
+            if round % 2 == 0 {
+                dir.commit().unwrap();
+                committed = oracle.clone();
+            }
+
+            // Uncommitted changes are discarded on reopen
+            drop(dir);
+            dir = PageDirectory::open(&path).unwrap();
+            oracle = committed.clone();
+            assert_matches(&dir, &oracle, PAGES);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_root_swaps_metadata_pages() {
+        let path = test_path("swap");
+        let mut dir = PageDirectory::open(&path).unwrap();
+        dir.map_range(0, 1, 50).unwrap();
+        dir.commit().unwrap();
+        dir.map_range(0, 1, 60).unwrap();
+        dir.commit().unwrap();
+        drop(dir);
+
+        // Corrupt the current metadata page: we fall back to the previous commit
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.write_all_at(&[0xff; 8], 0).unwrap();
+        drop(file);
+        let dir = PageDirectory::open(&path).unwrap();
+        assert_eq!(dir.lookup(0).unwrap(), Some(50));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_child_pointer() {
+        let path = test_path("corrupt");
+        let mut dir = PageDirectory::open(&path).unwrap();
+        // More entries than fit in one leaf, so that the root is an internal node
+        for i in 0..MAX_KEYS as u64 {
+            dir.map_range(2 * i, 1, 100 + i).unwrap();
+        }
+        dir.commit().unwrap();
+        let root = dir.meta.root;
+        let mut node = dir.read_node(root).unwrap();
+        assert!(!node.leaf);
+        drop(dir);
+
+        // A child past the end of the file
+        node.vals[0] = 1 << 30;
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.write_all_at(&node.encode(), root * PMA_PAGE_SIZE as u64)
+            .unwrap();
+        assert!(matches!(
+            PageDirectory::open(&path),
+            Err(DirectoryError::BadNode(block)) if block == 1 << 30
+        ));
+
+        // A child which is the root itself
+        node.vals[0] = root;
+        file.write_all_at(&node.encode(), root * PMA_PAGE_SIZE as u64)
+            .unwrap();
+        assert!(matches!(
+            PageDirectory::open(&path),
+            Err(DirectoryError::BadNode(block)) if block == root
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+/** Aggregating profiler
+ *
+ * Counts calls, time, and allocation per stack of cold state paths and %spot locations, using the
+ * same trace entries as the Chrome trace (see trace.rs). The result is written out as folded
+ * stacks ("root;child;grandchild weight" per line), as consumed by flamegraph.pl and friends.
+ *
+ * Recursion is folded: a path called directly from itself is counted as a call of the same node,
+ * without counting its time twice.
+ */
+use crate::mem::NockStack;
+use crate::trace::TraceStack;
+use std::collections::HashMap;
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+crate::gdb!();
+
+/** Index of a node in a [Profile] */
+pub type ProfileNode = usize;
+
+/** The root of every stack in a [Profile] */
+pub const ROOT: ProfileNode = 0;
+
+/** What to weigh each stack by when writing folded stacks */
+#[derive(Clone, Copy, Debug)]
+pub enum ProfileWeight {
+    /** Calls into each node */
+    Calls,
+    /** Microseconds spent in each node, excluding its children */
+    Time,
+    /** Words allocated on the stack in each node, excluding its children */
+    Words,
+}
+
+struct Node {
+    name: String,
+    parent: ProfileNode,
+    children: HashMap<String, ProfileNode>,
+    calls: u64,
+    time: Duration,
+    words: u64,
+}
+
+pub struct Profile {
+    nodes: Vec<Node>,
+    current: ProfileNode,
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile {
+            nodes: vec![Node {
+                name: "root".to_string(),
+                parent: ROOT,
+                children: HashMap::new(),
+                calls: 0,
+                time: Duration::ZERO,
+                words: 0,
+            }],
+            current: ROOT,
+        }
+    }
+
+    /** The node calls are currently attributed to */
+    pub fn current(&self) -> ProfileNode {
+        self.current
+    }
+
+    /** Return to a node after unwinding past the trace entries opened below it */
+    pub fn reset(&mut self, node: ProfileNode) {
+        self.current = node;
+    }
+
+    /** Enter a call to `name` from the current node. Returns the node it was entered from. */
+    pub fn open(&mut self, name: &str) -> ProfileNode {
+        let parent = self.current;
+        if self.nodes[parent].name == name {
+            // Fold recursion
+            self.nodes[parent].calls += 1;
+            return parent;
+        }
+
+        let next = self.nodes.len();
+        let node = *self.nodes[parent]
+            .children
+            .entry(name.to_string())
+            .or_insert(next);
+        if node == next {
+            self.nodes.push(Node {
+                name: name.to_string(),
+                parent,
+                children: HashMap::new(),
+                calls: 0,
+                time: Duration::ZERO,
+                words: 0,
+            });
+        }
+        self.nodes[node].calls += 1;
+        self.current = node;
+        parent
+    }
+
+    /** Leave a call entered from `parent` at `start`, when the stack had allocated `allocated`
+     * words
+     */
+    pub fn leave(
+        &mut self,
+        stack: &NockStack,
+        parent: ProfileNode,
+        start: Instant,
+        allocated: u64,
+    ) {
+        let node = self.current;
+        // A folded recursive call was already counted by the call it recursed from
+        if node != parent {
+            let n = &mut self.nodes[node];
+            n.time += Instant::now().saturating_duration_since(start);
+            n.words += stack.words_allocated() - allocated;
+            self.current = parent;
+        }
+    }
+
+    /** Leave every call in a frame's trace stack, as the frame is popped */
+    pub unsafe fn close(&mut self, stack: &NockStack, mut trace_stack: *const TraceStack) {
+        while !trace_stack.is_null() {
+            let entry = &*trace_stack;
+            self.leave(stack, entry.profile_parent, entry.start, entry.allocated);
+            trace_stack = entry.next;
+        }
+    }
+
+    /** Write folded stacks, one line per node with a nonzero weight */
+    pub fn write_folded<W: Write>(&self, out: &mut W, weight: ProfileWeight) -> io::Result<()> {
+        for (idx, node) in self.nodes.iter().enumerate().skip(1) {
+            let children = node.children.values().map(|c| &self.nodes[*c]);
+            let value = match weight {
+                ProfileWeight::Calls => node.calls,
+                ProfileWeight::Time => node
+                    .time
+                    .saturating_sub(children.map(|c| c.time).sum())
+                    .as_micros() as u64,
+                ProfileWeight::Words => node.words.saturating_sub(children.map(|c| c.words).sum()),
+            };
+            if value == 0 {
+                continue;
+            }
+
+            let mut names = vec![];
+            let mut cursor = idx;
+            while cursor != ROOT {
+                names.push(self.nodes[cursor].name.as_str());
+                cursor = self.nodes[cursor].parent;
+            }
+            names.reverse();
+            writeln!(out, "{} {}", names.join(";"), value)?;
+        }
+        Ok(())
+    }
+}
+
+/** Write a profile under the pier, as <pier>/.urb/put/profile/<n>/{calls,time,words}.folded
+ *
+ * Returns the directory written to.
+ */
+pub fn write_profile(pier_path: &Path, profile: &Profile) -> io::Result<PathBuf> {
+    let mut profile_dir = pier_path.join(".urb").join("put").join("profile");
+    create_dir_all(&profile_dir)?;
+    let mut profile_idx = 0u32;
+    while profile_dir.join(profile_idx.to_string()).exists() {
+        profile_idx += 1;
+    }
+    profile_dir.push(profile_idx.to_string());
+    create_dir_all(&profile_dir)?;
+
+    for (name, weight) in [
+        ("calls", ProfileWeight::Calls),
+        ("time", ProfileWeight::Time),
+        ("words", ProfileWeight::Words),
+    ] {
+        let file = File::create(profile_dir.join(format!("{}.folded", name)))?;
+        let mut out = BufWriter::new(file);
+        profile.write_folded(&mut out, weight)?;
+        out.flush()?;
+    }
+    Ok(profile_dir)
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{interpret, Context, ContextBuilder};
+    use crate::jets;
+    use crate::jets::hot::{HotEntry, K_139};
+    use crate::jets::util::test::register_core;
+    use crate::noun::{Noun, D, T};
+    use either::Either::Left;
+    use sword_macros::tas;
+
+    fn jet_foo(_context: &mut Context, _subject: Noun) -> jets::Result {
+        Ok(D(42))
+    }
+
+    const FOO_HOT_STATE: &[HotEntry] = &[(&[K_139, Left(b"foo")], 1, jet_foo)];
+
+    fn folded(context: &Context, weight: ProfileWeight) -> String {
+        let mut out = vec![];
+        context
+            .profile
+            .as_ref()
+            .unwrap()
+            .write_folded(&mut out, weight)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn spot(stack: &mut NockStack, name: u64, row: u64, formula: Noun) -> Noun {
+        let path = T(stack, &[D(name), D(0)]);
+        let start = T(stack, &[D(row), D(1)]);
+        let end = T(stack, &[D(row), D(9)]);
+        let pint = T(stack, &[start, end]);
+        let clue = T(stack, &[D(1), path, pint]);
+        let hint = T(stack, &[D(tas!(b"spot")), clue]);
+        T(stack, &[D(11), hint, formula])
+    }
+
+    #[test]
+    fn test_profile_folds_spots() {
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .profile(true)
+            .build()
+            .expect("could not build test context");
+
+        let inner = T(&mut c.stack, &[D(1), D(42)]);
+        let inner = spot(&mut c.stack, tas!(b"bar"), 7, inner);
+        let inner = spot(&mut c.stack, tas!(b"bar"), 7, inner);
+        let formula = spot(&mut c.stack, tas!(b"foo"), 3, inner);
+        interpret(&mut c, D(0), formula).unwrap();
+        interpret(&mut c, D(0), formula).unwrap();
+
+        let profile = c.profile.as_ref().unwrap();
+        assert_eq!(profile.current(), ROOT);
+        let mut out = vec![];
+        profile
+            .write_folded(&mut out, ProfileWeight::Calls)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "/foo:3 2\n/foo:3;/bar:7 4\n"
+        );
+    }
+
+    #[test]
+    fn test_profile_counts_words() {
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .profile(true)
+            .build()
+            .expect("could not build test context");
+
+        // Each autocons allocates a cell
+        let one = T(&mut c.stack, &[D(1), D(1)]);
+        let two = T(&mut c.stack, &[D(1), D(2)]);
+        let inner = T(&mut c.stack, &[one, two]);
+        let inner = spot(&mut c.stack, tas!(b"bar"), 7, inner);
+        let outer = T(&mut c.stack, &[inner, two]);
+        let formula = spot(&mut c.stack, tas!(b"foo"), 3, outer);
+        interpret(&mut c, D(0), formula).unwrap();
+
+        let profile = c.profile.as_ref().unwrap();
+        let mut out = vec![];
+        profile
+            .write_folded(&mut out, ProfileWeight::Words)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        // /bar:7 has its own cell, while /foo:3 has the outer cell and the hint bookkeeping for
+        // /bar:7
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], "/foo:3;/bar:7 3");
+        assert!(lines[0].starts_with("/foo:3 "));
+    }
+
+    #[test]
+    fn test_profile_siblings_spots() {
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .profile(true)
+            .build()
+            .expect("could not build test context");
+
+        // Both %spots are in the head of a cell, so neither is in tail position
+        let one = T(&mut c.stack, &[D(1), D(1)]);
+        let one = spot(&mut c.stack, tas!(b"foo"), 3, one);
+        let two = T(&mut c.stack, &[D(1), D(2)]);
+        let two = spot(&mut c.stack, tas!(b"bar"), 7, two);
+        let formula = T(&mut c.stack, &[one, two]);
+        interpret(&mut c, D(0), formula).unwrap();
+
+        assert_eq!(folded(&c, ProfileWeight::Calls), "/foo:3 1\n/bar:7 1\n");
+    }
+
+    #[test]
+    fn test_profile_counts_jets() {
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .hot_state(FOO_HOT_STATE)
+            .profile(true)
+            .build()
+            .expect("could not build test context");
+
+        // The jet answers 42 where the arm's Nock would answer 43
+        let battery = T(&mut c.stack, &[D(1), D(43)]);
+        let core = register_core(&mut c, tas!(b"foo"), battery);
+        let arm = T(&mut c.stack, &[D(0), D(1)]);
+        let call = T(&mut c.stack, &[D(9), D(2), arm]);
+        let formula = spot(&mut c.stack, tas!(b"bar"), 7, call);
+        let formula = T(&mut c.stack, &[formula, D(0), D(1)]);
+        for _ in 0..2 {
+            let res = interpret(&mut c, core, formula).unwrap();
+            assert!(unsafe { res.as_cell().unwrap().head().raw_equals(D(42)) });
+        }
+
+        assert_eq!(
+            folded(&c, ProfileWeight::Calls),
+            "/bar:7 2\n/bar:7;/foo/k139 2\n"
+        );
+    }
+
+    #[test]
+    fn test_write_profile() {
+        let pier = std::env::temp_dir().join(format!("sword-profile-{}", std::process::id()));
+        let mut profile = Profile::new();
+        profile.open("/foo:3");
+
+        let first = write_profile(&pier, &profile).unwrap();
+        let second = write_profile(&pier, &profile).unwrap();
+        assert!(first.ends_with(".urb/put/profile/0"));
+        assert!(second.ends_with(".urb/put/profile/1"));
+        let calls = std::fs::read_to_string(first.join("calls.folded")).unwrap();
+        assert_eq!(calls, "/foo:3 1\n");
+        assert!(first.join("time.folded").exists());
+        assert!(first.join("words.folded").exists());
+
+        std::fs::remove_dir_all(&pier).unwrap();
+    }
+}
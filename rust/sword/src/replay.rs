@@ -0,0 +1,361 @@
+/** Replay: rebuild a kernel by running it over the events in an [EventLog]
+ *
+ * A replay starts from a kernel at some event number: either the boot kernel at event 0 or one
+ * saved by [Replay::snapshot], and steps it through every later event in the log. The step is
+ * up to the caller, and produces the effects of the event along with the next kernel, as
+ * `[effects kernel]`; [poke] is the one for Arvo.
+ *
+ * To catch nondeterminism, [Replay::record] takes the mugs of the effects and kernel after each
+ * event, and [Replay::verify] replays against them and stops at the first event which differs.
+ */
+use crate::event_log::{EventLog, LogError};
+use crate::interpreter::{self, interpret, Context, Error};
+use crate::mug::mug_u32;
+use crate::noun::{Noun, Slots, D, T};
+use crate::snapshot::SnapshotError;
+use std::collections::BTreeSet;
+use std::convert::TryInto;
+use std::path::Path;
+use std::{fs, io};
+use thiserror::Error;
+
+crate::gdb!();
+
+/** Axis of the +poke arm in the Arvo core */
+const POKE_AXIS: u64 = 23;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("event log: {0}")]
+    Log(#[from] LogError),
+    #[error("snapshot: {0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error("snapshot does not hold an event number and kernel")]
+    Format,
+    #[error("event {eve} crashed: {error:?}")]
+    Crash { eve: u64, error: Error },
+}
+
+/** Mugs of the effects and kernel after an event */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventMugs {
+    pub eve: u64,
+    pub effects: u32,
+    pub kernel: u32,
+}
+
+/** The first event to replay differently than it was recorded */
+#[derive(Debug)]
+pub struct Divergence {
+    pub expected: EventMugs,
+    pub actual: EventMugs,
+    /** Paths of the jets which ran during the event, the usual suspects */
+    pub jets: BTreeSet<String>,
+}
+
+pub struct Replay {
+    /** Number of the last event applied to the kernel */
+    pub eve: u64,
+    pub kernel: Noun,
+}
+
+impl Replay {
+    pub fn new(eve: u64, kernel: Noun) -> Replay {
+        Replay { eve, kernel }
+    }
+
+    /** Start from a snapshot written by [Replay::snapshot] */
+    pub fn from_snapshot<P: AsRef<Path>>(
+        context: &mut Context,
+        path: P,
+    ) -> Result<Replay, ReplayError> {
+        let saved = context.restore_from(path)?;
+        let eve = saved
+            .slot(2)
+            .and_then(|eve| Ok(eve.as_direct()?.data()))
+            .map_err(|_| ReplayError::Format)?;
+        let kernel = saved.slot(3).map_err(|_| ReplayError::Format)?;
+        Ok(Replay { eve, kernel })
+    }
+
+    /** Snapshot the kernel along with its event number, to restart replay from here */
+    pub fn snapshot<P: AsRef<Path>>(
+        &self,
+        context: &mut Context,
+        path: P,
+        with_cache: bool,
+    ) -> Result<(), SnapshotError> {
+        let saved = T(&mut context.stack, &[D(self.eve), self.kernel]);
+        context.snapshot(path, saved, with_cache)
+    }
+
+    /** Apply every event in the log after the current one, in order
+     *
+     * Between events, the stack is reset to hold only the kernel and the interpreter state, as in
+     * the serf; so this must be called with the stack at its top frame, and any other nouns on it
+     * are lost.
+     */
+    pub fn run<F>(
+        &mut self,
+        context: &mut Context,
+        log: &EventLog,
+        step: F,
+    ) -> Result<(), ReplayError>
+    where
+        F: FnMut(&mut Context, Noun, Noun) -> interpreter::Result,
+    {
+        self.drive(context, log, step, |_, _| None)?;
+        Ok(())
+    }
+
+    /** Like [Replay::run], recording the mugs after each event */
+    pub fn record<F>(
+        &mut self,
+        context: &mut Context,
+        log: &EventLog,
+        step: F,
+    ) -> Result<Vec<EventMugs>, ReplayError>
+    where
+        F: FnMut(&mut Context, Noun, Noun) -> interpreter::Result,
+    {
+        let mut mugs = Vec::new();
+        self.drive(context, log, step, |_, actual| {
+            mugs.push(actual);
+            None
+        })?;
+        Ok(mugs)
+    }
+
+    /** Like [Replay::run], checking the mugs after each event against a recording
+     *
+     * Stops at the first event whose mugs differ, leaving the kernel as that event produced it.
+     * Events missing from the recording are not checked.
+     */
+    pub fn verify<F>(
+        &mut self,
+        context: &mut Context,
+        log: &EventLog,
+        expected: &[EventMugs],
+        step: F,
+    ) -> Result<Option<Divergence>, ReplayError>
+    where
+        F: FnMut(&mut Context, Noun, Noun) -> interpreter::Result,
+    {
+        let saved = context.fired_jets.replace(BTreeSet::new());
+        let res = self.drive(context, log, step, |context, actual| {
+            let jets = context
+                .fired_jets
+                .replace(BTreeSet::new())
+                .unwrap_or_default();
+            let idx = expected
+                .binary_search_by_key(&actual.eve, |mugs| mugs.eve)
+                .ok()?;
+            if expected[idx] == actual {
+                None
+            } else {
+                Some(Divergence {
+                    expected: expected[idx],
+                    actual,
+                    jets,
+                })
+            }
+        });
+        context.fired_jets = saved;
+        res
+    }
+
+    fn drive<F, G>(
+        &mut self,
+        context: &mut Context,
+        log: &EventLog,
+        mut step: F,
+        mut check: G,
+    ) -> Result<Option<Divergence>, ReplayError>
+    where
+        F: FnMut(&mut Context, Noun, Noun) -> interpreter::Result,
+        G: FnMut(&mut Context, EventMugs) -> Option<Divergence>,
+    {
+        let mut events = log.events(self.eve + 1)?;
+        while let Some(next) = events.next(&mut context.stack) {
+            let (eve, event) = next?;
+            context.cache.clear(&mut context.stack);
+            context.scry_stack = D(0);
+            let res = step(context, self.kernel, event)
+                .and_then(|res| Ok(res.as_cell()?))
+                .map_err(|error| ReplayError::Crash { eve, error })?;
+            self.kernel = res.tail();
+            self.eve = eve;
+
+            let stack = &mut context.stack;
+            let mugs = EventMugs {
+                eve,
+                effects: mug_u32(stack, res.head()),
+                kernel: mug_u32(stack, res.tail()),
+            };
+            if let Some(divergence) = check(context, mugs) {
+                return Ok(Some(divergence));
+            }
+
+            unsafe {
+                let stack = &mut context.stack;
+                stack.preserve(&mut self.kernel);
+                stack.preserve(&mut context.cold);
+                stack.preserve(&mut context.warm);
+                stack.preserve(&mut context.hot);
+                stack.flip_top_frame(0);
+            }
+        }
+        Ok(None)
+    }
+}
+
+/** Save recorded mugs, 16 bytes per event */
+pub fn write_mugs<P: AsRef<Path>>(path: P, mugs: &[EventMugs]) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(mugs.len() * 16);
+    for mugs in mugs {
+        bytes.extend_from_slice(&mugs.eve.to_le_bytes());
+        bytes.extend_from_slice(&mugs.effects.to_le_bytes());
+        bytes.extend_from_slice(&mugs.kernel.to_le_bytes());
+    }
+    fs::write(path, bytes)
+}
+
+/** Load mugs saved by [write_mugs] */
+pub fn read_mugs<P: AsRef<Path>>(path: P) -> io::Result<Vec<EventMugs>> {
+    let bytes = fs::read(path)?;
+    if bytes.len() % 16 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "truncated mug file",
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(16)
+        .map(|chunk| EventMugs {
+            eve: u64::from_le_bytes(chunk[0..8].try_into().unwrap()),
+            effects: u32::from_le_bytes(chunk[8..12].try_into().unwrap()),
+            kernel: u32::from_le_bytes(chunk[12..16].try_into().unwrap()),
+        })
+        .collect())
+}
+
+/** Poke Arvo with an event, producing `[effects arvo]` */
+pub fn poke(context: &mut Context, arvo: Noun, event: Noun) -> interpreter::Result {
+    let stack = &mut context.stack;
+    let pul = T(stack, &[D(9), D(POKE_AXIS), D(0), D(2)]);
+    let sam = T(stack, &[D(6), D(0), D(7)]);
+    let fol = T(stack, &[D(8), pul, D(9), D(2), D(10), sam, D(0), D(2)]);
+    let sub = T(stack, &[arvo, event]);
+    interpret(context, sub, fol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_log::EventLog;
+    use crate::interpreter::{interpret, ContextBuilder};
+    use crate::jets;
+    use crate::jets::hot::{HotEntry, K_139};
+    use crate::jets::util::test::{assert_noun_eq, register_core};
+    use either::Either::Left;
+    use std::fs;
+    use sword_macros::tas;
+
+    /** A jet for an arm which produces 0 */
+    fn jet_one(_context: &mut Context, _subject: Noun) -> jets::Result {
+        Ok(D(1))
+    }
+
+    const ONE_HOT_STATE: &[HotEntry] = &[(&[K_139, Left(b"foo")], 1, jet_one)];
+
+    /** A kernel which is just a sum: add each event to it, with no effects */
+    fn add(context: &mut Context, kernel: Noun, event: Noun) -> interpreter::Result {
+        let sum = kernel.as_direct()?.data() + event.as_direct()?.data();
+        Ok(T(&mut context.stack, &[D(0), D(sum)]))
+    }
+
+    #[test]
+    fn test_replay_from_snapshot() {
+        let dir = std::env::temp_dir();
+        let log_path = dir.join(format!("sword-replay-log-{}", std::process::id()));
+        let snap_path = dir.join(format!("sword-replay-snap-{}", std::process::id()));
+        let _ = fs::remove_file(&log_path);
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .build()
+            .unwrap();
+
+        let mut log = EventLog::open(&log_path).unwrap();
+        for n in 1..=4 {
+            log.append(&mut c.stack, D(n)).unwrap();
+        }
+        log.commit().unwrap();
+
+        let mut replay = Replay::new(0, D(0));
+        replay.run(&mut c, &log, add).unwrap();
+        assert_eq!(replay.eve, 4);
+        assert_noun_eq(&mut c.stack, replay.kernel, D(10));
+        replay.snapshot(&mut c, &snap_path, false).unwrap();
+
+        log.append(&mut c.stack, D(5)).unwrap();
+        log.commit().unwrap();
+        let mut replay = Replay::from_snapshot(&mut c, &snap_path).unwrap();
+        assert_eq!(replay.eve, 4);
+        replay.run(&mut c, &log, add).unwrap();
+        assert_eq!(replay.eve, 5);
+        assert_noun_eq(&mut c.stack, replay.kernel, D(15));
+
+        fs::remove_file(&log_path).unwrap();
+        fs::remove_file(&snap_path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_finds_divergence() {
+        let path = std::env::temp_dir().join(format!("sword-verify-log-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut c = ContextBuilder::new()
+            .stack_size(8 << 10 << 10)
+            .hot_state(ONE_HOT_STATE)
+            .build()
+            .unwrap();
+
+        let mut log = EventLog::open(&path).unwrap();
+        for n in 1..=4 {
+            log.append(&mut c.stack, D(n)).unwrap();
+        }
+        log.commit().unwrap();
+        let recorded = Replay::new(0, D(0)).record(&mut c, &log, add).unwrap();
+        assert_eq!(recorded.len(), 4);
+
+        let mut replay = Replay::new(0, D(0));
+        assert!(replay
+            .verify(&mut c, &log, &recorded, add)
+            .unwrap()
+            .is_none());
+
+        // Off by one from the third event on, when a jet which disagrees with its arm runs
+        let flaky = |context: &mut Context, kernel: Noun, event: Noun| {
+            let mut sum = kernel.as_direct()?.data() + event.as_direct()?.data();
+            if event.as_direct()?.data() == 3 {
+                let battery = T(&mut context.stack, &[D(1), D(0)]);
+                let core = register_core(context, tas!(b"foo"), battery);
+                let arm = T(&mut context.stack, &[D(9), D(2), D(0), D(1)]);
+                sum += interpret(context, core, arm)?.as_direct()?.data();
+            }
+            Ok(T(&mut context.stack, &[D(0), D(sum)]))
+        };
+        let mut replay = Replay::new(0, D(0));
+        let divergence = replay
+            .verify(&mut c, &log, &recorded, flaky)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.actual.eve, 3);
+        assert_eq!(divergence.expected.effects, divergence.actual.effects);
+        assert_ne!(divergence.expected.kernel, divergence.actual.kernel);
+        let jets: Vec<&str> = divergence.jets.iter().map(String::as_str).collect();
+        assert_eq!(jets, ["/foo/k139"]);
+        assert_eq!(replay.eve, 3);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
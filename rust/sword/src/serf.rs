@@ -0,0 +1,633 @@
+/** Serf: the worker process driven by the king
+ *
+ * The king (vere) starts us as `sword serf <pier> <key> <wag> <hap> <lom> <eve>` and speaks the
+ * newt protocol (see newt.rs) over stdin and stdout. We hold the Arvo kernel and the event number,
+ * and answer each writ by running the appropriate Arvo arm with the interpreter.
+ */
+use crate::flog;
+use crate::interpreter;
+use crate::interpreter::{
+    inc, interpret, interrupt_at, interrupt_on_sigint, ContextBuilder, Deadline, Error, Mote,
+};
+use crate::jets::hot::HotEntry;
+use crate::jets::list::util::{lent, zing};
+use crate::jets::nock::util::mook;
+use crate::mug::mug_u32;
+use crate::newt::Newt;
+use crate::noun::{Cell, Noun, Slots, D, T};
+use crate::profile::write_profile;
+use crate::trace::{create_trace_file, write_metadata, write_serf_trace_safe, TraceInfo};
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use sword_macros::tas;
+
+crate::gdb!();
+
+/** Runtime config bit: enable the aggregating profiler (vere's -p) */
+const FLAG_PROFILE: u32 = 1 << 1;
+/** Runtime config bit: enable JSON tracing */
+const FLAG_TRACE: u32 = 1 << 8;
+
+/** Axis of the +peek arm in the Arvo core */
+const PEEK_AXIS: u64 = 22;
+/** Axis of the +poke arm in the Arvo core */
+const POKE_AXIS: u64 = 23;
+
+/** Default NockStack size in 64-bit words, used when the king does not pass loom bits (4GiB) */
+const DEFAULT_STACK_SIZE: usize = 1 << 29;
+
+struct Context {
+    event_num: u64,
+    arvo: Noun,
+    mug: u32,
+    newt: Newt,
+    nock_context: interpreter::Context,
+}
+
+impl Context {
+    pub fn new(
+        stack_size: usize,
+        trace_info: Option<TraceInfo>,
+        profile: bool,
+        constant_hot_state: &[HotEntry],
+        newt: Newt,
+    ) -> io::Result<Self> {
+        let mut nock_context = ContextBuilder::new()
+            .stack_size(stack_size)
+            .slogger(newt.slogger()?)
+            .hot_state(constant_hot_state)
+            .profile(profile)
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        nock_context.trace_info = trace_info;
+        interrupt_on_sigint(&nock_context.interrupt)?;
+
+        let arvo = D(0);
+        let mug = mug_u32(&mut nock_context.stack, arvo);
+
+        Ok(Context {
+            event_num: 0,
+            arvo,
+            mug,
+            newt,
+            nock_context,
+        })
+    }
+
+    //
+    // Setters
+    //
+
+    /** Install the kernel produced by an event and bump the event number */
+    pub fn event_update(&mut self, new_event_num: u64, new_arvo: Noun) {
+        //  XX: assert event numbers are continuous
+        self.arvo = new_arvo;
+        self.event_num = new_event_num;
+
+        self.nock_context.cache.clear(&mut self.nock_context.stack);
+        self.nock_context.scry_stack = D(0);
+
+        self.mug = mug_u32(&mut self.nock_context.stack, self.arvo);
+    }
+
+    //
+    // Newt functions
+    //
+
+    pub fn next(&mut self) -> Option<Noun> {
+        self.newt.next(&mut self.nock_context.stack)
+    }
+
+    pub fn ripe(&mut self) {
+        self.newt.ripe(
+            &mut self.nock_context.stack, self.event_num, self.mug as u64,
+        );
+    }
+
+    /** Interrupt the current writ once its timeout (in milliseconds, 0 for none) expires */
+    pub fn deadline(&self, mil: Noun) -> Option<Deadline> {
+        match mil.as_direct() {
+            Ok(mil) if mil.data() != 0 => Some(interrupt_at(
+                &self.nock_context.interrupt,
+                Instant::now() + Duration::from_millis(mil.data()),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn live(&mut self) {
+        self.newt.live(&mut self.nock_context.stack);
+    }
+
+    pub fn peek_done(&mut self, dat: Noun) {
+        self.newt.peek_done(&mut self.nock_context.stack, dat);
+    }
+
+    pub fn peek_bail(&mut self, dud: Noun) {
+        self.newt.peek_bail(&mut self.nock_context.stack, dud);
+    }
+
+    pub fn play_done(&mut self) {
+        self.newt
+            .play_done(&mut self.nock_context.stack, self.mug as u64);
+    }
+
+    pub fn play_bail(&mut self, dud: Noun) {
+        self.newt.play_bail(
+            &mut self.nock_context.stack,
+            self.event_num + 1,
+            self.mug as u64,
+            dud,
+        );
+    }
+
+    pub fn work_done(&mut self, fec: Noun) {
+        self.newt.work_done(
+            &mut self.nock_context.stack, self.event_num, self.mug as u64, fec,
+        );
+    }
+
+    pub fn work_swap(&mut self, job: Noun, fec: Noun) {
+        self.newt.work_swap(
+            &mut self.nock_context.stack, self.event_num, self.mug as u64, job, fec,
+        );
+    }
+
+    pub fn work_bail(&mut self, lud: Noun) {
+        self.newt.work_bail(&mut self.nock_context.stack, lud);
+    }
+}
+
+/**
+ * This is suitable for talking to the king process. To test, change the arg_c[0] line in
+ * u3_lord_init in vere to point at this binary and start vere like normal.
+ */
+pub fn serf(constant_hot_state: &[HotEntry]) -> io::Result<()> {
+    let pier_path_string = std::env::args()
+        .nth(2)
+        .ok_or(io::Error::new(io::ErrorKind::Other, "no pier path"))?;
+    let pier_path = PathBuf::from(pier_path_string);
+
+    let wag: u32 = std::env::args()
+        .nth(4)
+        .ok_or(io::Error::new(io::ErrorKind::Other, "no flag bitmap"))?
+        .parse()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "bad flag bitmap"))?;
+
+    // Loom bits are log2 of the loom size in bytes
+    let stack_size = match std::env::args().nth(6) {
+        Some(lom) => {
+            let lom: usize = lom
+                .parse()
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "bad loom bits"))?;
+            if !(3..64).contains(&lom) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "loom bits out of range",
+                ));
+            }
+            1 << (lom - 3)
+        }
+        None => DEFAULT_STACK_SIZE,
+    };
+
+    let mut trace_info = if wag & FLAG_TRACE != 0 {
+        create_trace_file(pier_path.clone()).ok()
+    } else {
+        None
+    };
+
+    if let Some(ref mut info) = trace_info.as_mut() {
+        if let Err(_e) = write_metadata(info) {
+            eprintln!("\rError initializing trace file: {:?}", _e);
+            trace_info = None;
+        }
+    }
+
+    let mut context = Context::new(
+        stack_size,
+        trace_info,
+        wag & FLAG_PROFILE != 0,
+        constant_hot_state,
+        Newt::new(),
+    )?;
+    let res = serve(&mut context);
+
+    if let Some(ref profile) = context.nock_context.profile {
+        match write_profile(&pier_path, profile) {
+            Ok(dir) => eprintln!("serf: wrote profile to {}", dir.display()),
+            Err(e) => eprintln!("serf: error writing profile: {:?}", e),
+        }
+    }
+    res
+}
+
+/** Answer writs until the king hangs up or tells us to exit */
+fn serve(context: &mut Context) -> io::Result<()> {
+    context.ripe();
+
+    // Can't use for loop because it borrows newt
+    while let Some(writ) = context.next() {
+        // Reset the local cache and scry handler stack
+        context
+            .nock_context
+            .cache
+            .clear(&mut context.nock_context.stack);
+        context.nock_context.scry_stack = D(0);
+        // Only interrupt the writ we are about to run, not one that has already finished
+        context
+            .nock_context
+            .interrupt
+            .store(false, Ordering::Relaxed);
+
+        let tag = slot(writ, 2)?.as_direct().unwrap();
+        match tag.data() {
+            tas!(b"live") => {
+                let inner = slot(writ, 6)?.as_direct().unwrap();
+                match inner.data() {
+                    tas!(b"cram") => eprintln!("cram not implemented"),
+                    tas!(b"exit") => {
+                        eprintln!("exit");
+                        return Ok(());
+                    }
+                    tas!(b"save") => eprintln!("save not implemented"),
+                    tas!(b"meld") => eprintln!("meld not implemented"),
+                    tas!(b"pack") => eprintln!("pack not implemented"),
+                    _ => eprintln!("unknown live"),
+                }
+                context.live();
+            }
+            tas!(b"peek") => {
+                let _deadline = context.deadline(slot(writ, 6)?);
+                let sam = slot(writ, 7)?;
+                peek(context, sam);
+            }
+            tas!(b"play") => {
+                let lit = slot(writ, 7)?;
+                if context.event_num == 0 {
+                    // apply lifecycle to first batch
+                    play_life(context, lit);
+                } else {
+                    play_list(context, lit);
+                };
+            }
+            tas!(b"work") => {
+                let _deadline = context.deadline(slot(writ, 6)?);
+                let job = slot(writ, 7)?;
+                work(context, job);
+            }
+            _ => panic!("got message with unknown tag {}", tag),
+        };
+
+        // Persist data that should survive between events
+        unsafe {
+            let stack = &mut context.nock_context.stack;
+            stack.preserve(&mut context.arvo);
+            stack.preserve(&mut context.nock_context.cold);
+            stack.preserve(&mut context.nock_context.warm);
+            stack.preserve(&mut context.nock_context.hot);
+            stack.flip_top_frame(0);
+        }
+    }
+
+    Ok(())
+}
+
+/** Slam the arm at the given axis of the Arvo core with a sample */
+fn slam(context: &mut Context, axis: u64, ovo: Noun) -> interpreter::Result {
+    let arvo = context.arvo;
+    let stack = &mut context.nock_context.stack;
+    let pul = T(stack, &[D(9), D(axis), D(0), D(2)]);
+    let sam = T(stack, &[D(6), D(0), D(7)]);
+    let fol = T(stack, &[D(8), pul, D(9), D(2), D(10), sam, D(0), D(2)]);
+    let sub = T(stack, &[arvo, ovo]);
+    interpret(&mut context.nock_context, sub, fol)
+}
+
+/** Render an interpreter error trace as a $goof */
+fn goof(context: &mut Context, mote: Mote, traces: Noun) -> Noun {
+    let trace = zing(&mut context.nock_context.stack, traces).unwrap();
+    let tone = Cell::new(&mut context.nock_context.stack, D(2), trace);
+    let tang = mook(&mut context.nock_context, tone, false)
+        .expect("serf: goof: +mook crashed on bail")
+        .tail();
+    T(&mut context.nock_context.stack, &[D(mote as u64), tang])
+}
+
+/** Run slam; process stack trace to tang if error.
+ *  Generate tracing events, if JSON tracing enabled.
+ */
+fn soft(
+    context: &mut Context,
+    axis: u64,
+    ovo: Noun,
+    trace_name: Option<String>,
+) -> Result<Noun, Noun> {
+    let slam_res = if context.nock_context.trace_info.is_some() {
+        let start = Instant::now();
+        let slam_res = slam(context, axis, ovo);
+        write_serf_trace_safe(
+            &mut context.nock_context,
+            trace_name.as_ref().unwrap(),
+            start,
+        );
+
+        slam_res
+    } else {
+        slam(context, axis, ovo)
+    };
+
+    match slam_res {
+        Ok(res) => Ok(res),
+        Err(error) => match error {
+            Error::Deterministic(mote, trace) | Error::NonDeterministic(mote, trace) => {
+                Err(goof(context, mote, trace))
+            }
+            Error::ScryBlocked(_) | Error::ScryCrashed(_) => {
+                panic!("serf: soft: .^ invalid outside of virtual Nock")
+            }
+        },
+    }
+}
+
+fn peek(context: &mut Context, sam: Noun) {
+    let trace_name = if context.nock_context.trace_info.is_some() {
+        Some("peek".to_string())
+    } else {
+        None
+    };
+
+    match soft(context, PEEK_AXIS, sam, trace_name) {
+        Ok(res) => context.peek_done(res),
+        Err(goof) => context.peek_bail(goof),
+    }
+}
+
+fn play_life(context: &mut Context, eve: Noun) {
+    let stack = &mut context.nock_context.stack;
+    let sub = T(stack, &[D(0), D(3)]);
+    let lyf = T(stack, &[D(2), sub, D(0), D(2)]);
+    let res = if context.nock_context.trace_info.is_some() {
+        let start = Instant::now();
+        let boot_res = interpret(&mut context.nock_context, eve, lyf);
+        write_serf_trace_safe(&mut context.nock_context, "boot", start);
+        boot_res
+    } else {
+        interpret(&mut context.nock_context, eve, lyf)
+    };
+
+    match res {
+        Ok(gat) => {
+            let eved = lent(eve).expect("serf: play: boot event number failure") as u64;
+            let arvo = slot(gat, 7).expect("serf: play: lifecycle didn't return initial Arvo");
+
+            context.event_update(eved, arvo);
+            context.play_done();
+        }
+        Err(error) => match error {
+            Error::Deterministic(mote, trace) | Error::NonDeterministic(mote, trace) => {
+                let goof = goof(context, mote, trace);
+                context.play_bail(goof);
+            }
+            Error::ScryBlocked(_) | Error::ScryCrashed(_) => {
+                panic!("serf: play: .^ invalid outside of virtual Nock")
+            }
+        },
+    }
+}
+
+fn play_list(context: &mut Context, mut lit: Noun) {
+    let mut eve = context.event_num;
+    while let Ok(cell) = lit.as_cell() {
+        let ovo = cell.head();
+        let trace_name = if context.nock_context.trace_info.is_some() {
+            Some(format!("play [{}]", eve))
+        } else {
+            None
+        };
+
+        match soft(context, POKE_AXIS, ovo, trace_name) {
+            Ok(res) => {
+                let arvo = res
+                    .as_cell()
+                    .expect("serf: play: +slam returned atom")
+                    .tail();
+                eve += 1;
+
+                context.event_update(eve, arvo);
+            }
+            Err(goof) => {
+                return context.play_bail(goof);
+            }
+        }
+        lit = cell.tail();
+    }
+    context.play_done();
+}
+
+fn work(context: &mut Context, job: Noun) {
+    let trace_name = if context.nock_context.trace_info.is_some() {
+        Some(format!("work [{}]", context.event_num + 1))
+    } else {
+        None
+    };
+
+    match soft(context, POKE_AXIS, job, trace_name) {
+        Ok(res) => {
+            let cell = res.as_cell().expect("serf: work: +slam returned atom");
+            let fec = cell.head();
+            let eve = context.event_num;
+
+            context.event_update(eve + 1, cell.tail());
+            context.work_done(fec);
+        }
+        Err(goof) => {
+            work_swap(context, job, goof);
+        }
+    }
+}
+
+fn work_swap(context: &mut Context, job: Noun, goof: Noun) {
+    //  TODO: on decryption failure in aes_siv, should bail as fast as
+    //  possible, without rendering stack trace or injecting crud event.  See
+    //  c3__evil in vere.
+
+    let stack = &mut context.nock_context.stack;
+    context.nock_context.cache.clear(stack);
+    //  crud ovo = [+(now) [%$ %arvo ~] [%crud goof ovo]]
+    let job_cell = job.as_cell().expect("serf: work: job not a cell");
+    let job_now = job_cell.head().as_atom().expect("serf: work: now not atom");
+    let now = inc(stack, job_now).as_noun();
+    let wire = T(stack, &[D(0), D(tas!(b"arvo")), D(0)]);
+    let crud = T(stack, &[now, wire, D(tas!(b"crud")), goof, job_cell.tail()]);
+    let trace_name = if context.nock_context.trace_info.is_some() {
+        Some(format!("crud [{}]", context.event_num + 1))
+    } else {
+        None
+    };
+
+    match soft(context, POKE_AXIS, crud, trace_name) {
+        Ok(res) => {
+            let cell = res.as_cell().expect("serf: work: crud +slam returned atom");
+            let fec = cell.head();
+            let eve = context.event_num;
+
+            context.event_update(eve + 1, cell.tail());
+            context.work_swap(crud, fec);
+        }
+        Err(goof_crud) => {
+            flog!(&mut context.nock_context, "\rserf: bail");
+            let stack = &mut context.nock_context.stack;
+            let lud = T(stack, &[goof_crud, goof, D(0)]);
+            context.work_bail(lud);
+        }
+    }
+}
+
+fn slot(noun: Noun, axis: u64) -> io::Result<Noun> {
+    noun.slot(axis)
+        .map_err(|_e| io::Error::new(io::ErrorKind::InvalidInput, "Bad axis"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::{assert_noun_eq, init_context};
+    use crate::mem::NockStack;
+    use std::fs::File;
+    use std::os::unix::io::FromRawFd;
+    use std::thread;
+
+    /// The read and write ends of a pipe
+    fn pipe() -> (File, File) {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) }
+    }
+
+    /// A kernel which has seen `count` events
+    ///
+    /// Its +poke gives back the job as its only effect and counts it, unless the job's card is
+    /// %boom, when it crashes. Its +peek gives back its sample in a unit.
+    fn kernel(stack: &mut NockStack, count: u64) -> Noun {
+        // [5 [0 54] [1 %boom]]: is the card %boom?
+        let card = T(stack, &[D(0), D(54)]);
+        let boom = T(stack, &[D(1), D(tas!(b"boom"))]);
+        let boom = T(stack, &[D(5), card, boom]);
+        let crash = T(stack, &[D(0), D(0)]);
+        // [[[0 6] [1 0]] [[0 14] [4 0 15]]]: [[job ~] [battery +(count)]]
+        let job = T(stack, &[D(0), D(6)]);
+        let nil = T(stack, &[D(1), D(0)]);
+        let fec = T(stack, &[job, nil]);
+        let battery = T(stack, &[D(0), D(14)]);
+        let count_up = T(stack, &[D(4), D(0), D(15)]);
+        let next = T(stack, &[battery, count_up]);
+        let done = T(stack, &[fec, next]);
+        let poke_gate = T(stack, &[D(6), boom, crash, done]);
+        // [[1 0] [0 6]]: [~ sample]
+        let sample = T(stack, &[D(0), D(6)]);
+        let peek_gate = T(stack, &[nil, sample]);
+        // [[1 gate] [[1 0] [0 1]]]: a gate with the kernel as its context
+        let kernel = T(stack, &[D(0), D(1)]);
+        let context = T(stack, &[nil, kernel]);
+        let poke_gate = T(stack, &[D(1), poke_gate]);
+        let poke = T(stack, &[poke_gate, context]);
+        let peek_gate = T(stack, &[D(1), peek_gate]);
+        let peek = T(stack, &[peek_gate, context]);
+        let battery = T(stack, &[D(0), D(0), peek, poke]);
+        T(stack, &[battery, D(count)])
+    }
+
+    fn job(stack: &mut NockStack, card: u64) -> Noun {
+        T(
+            stack,
+            &[D(0x8000_0000), D(0), D(tas!(b"card")), D(card), D(0)],
+        )
+    }
+
+    fn mug(stack: &mut NockStack, count: u64) -> Noun {
+        let kernel = kernel(stack, count);
+        D(mug_u32(stack, kernel) as u64)
+    }
+
+    #[test]
+    fn test_serf_with_fake_king() {
+        let (serf_in, king_out) = pipe();
+        let (king_in, serf_out) = pipe();
+        let serf = thread::spawn(move || {
+            let newt = Newt::from_files(serf_in, serf_out);
+            let mut context = Context::new(1 << 22, None, false, &[], newt)?;
+            serve(&mut context)
+        });
+
+        let mut c = init_context();
+        let stack = &mut c.stack;
+        let mut king = Newt::from_files(king_in, king_out);
+
+        let got = king.next(stack).unwrap();
+        let version = T(stack, &[D(1), D(139), D(4)]);
+        let mug_none = D(mug_u32(stack, D(0)) as u64);
+        let want = T(stack, &[D(tas!(b"ripe")), version, D(0), mug_none]);
+        assert_noun_eq(stack, got, want);
+
+        // Boot into the kernel: the first event is a formula run against the rest
+        let kernel_0 = kernel(stack, 0);
+        let boot = T(stack, &[D(1), D(0), D(0), kernel_0]);
+        let play = T(stack, &[D(tas!(b"play")), D(1), boot, D(0)]);
+        king.send(stack, play);
+        let got = king.next(stack).unwrap();
+        let mug_0 = mug(stack, 0);
+        let want = T(stack, &[D(tas!(b"play")), D(tas!(b"done")), mug_0]);
+        assert_noun_eq(stack, got, want);
+
+        // Replay an event through +poke
+        let job_1 = job(stack, 1);
+        let play = T(stack, &[D(tas!(b"play")), D(2), job_1, D(0)]);
+        king.send(stack, play);
+        let got = king.next(stack).unwrap();
+        let mug_1 = mug(stack, 1);
+        let want = T(stack, &[D(tas!(b"play")), D(tas!(b"done")), mug_1]);
+        assert_noun_eq(stack, got, want);
+
+        let job_2 = job(stack, 2);
+        let work = T(stack, &[D(tas!(b"work")), D(0), job_2]);
+        king.send(stack, work);
+        let got = king.next(stack).unwrap();
+        let mug_2 = mug(stack, 2);
+        let want = T(
+            stack,
+            &[D(tas!(b"work")), D(tas!(b"done")), D(3), mug_2, job_2, D(0)],
+        );
+        assert_noun_eq(stack, got, want);
+
+        let sam = T(stack, &[D(tas!(b"once")), D(tas!(b"x")), D(0)]);
+        let peek = T(stack, &[D(tas!(b"peek")), D(0), sam]);
+        king.send(stack, peek);
+        let got = king.next(stack).unwrap();
+        let want = T(stack, &[D(tas!(b"peek")), D(tas!(b"done")), D(0), sam]);
+        assert_noun_eq(stack, got, want);
+
+        // A crashing event is replaced by a %crud, which the kernel takes
+        let boom = T(stack, &[D(0x8000_0000), D(0), D(tas!(b"boom")), D(0)]);
+        let work = T(stack, &[D(tas!(b"work")), D(0), boom]);
+        king.send(stack, work);
+        let got = king.next(stack).unwrap();
+        let mug_3 = mug(stack, 3);
+        let want = [(2, D(tas!(b"work"))), (6, D(tas!(b"swap"))), (14, D(4)), (30, mug_3)];
+        for (axis, want) in want {
+            assert_noun_eq(stack, got.slot(axis).unwrap(), want);
+        }
+        let crud = got.slot(62).unwrap();
+        assert_noun_eq(stack, crud.slot(2).unwrap(), D(0x8000_0001));
+        assert_noun_eq(stack, crud.slot(14).unwrap(), D(tas!(b"crud")));
+        let fec = T(stack, &[crud, D(0)]);
+        assert_noun_eq(stack, got.slot(63).unwrap(), fec);
+
+        let exit = T(stack, &[D(tas!(b"live")), D(tas!(b"exit")), D(0)]);
+        king.send(stack, exit);
+        serf.join().unwrap().unwrap();
+        assert!(king.next(stack).is_none());
+    }
+}
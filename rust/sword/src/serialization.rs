@@ -1,13 +1,21 @@
 use crate::hamt::MutHamt;
 use crate::interpreter::Error::{self, *};
 use crate::interpreter::Mote::*;
-use crate::mem::NockStack;
-use crate::noun::{Atom, Cell, DirectAtom, IndirectAtom, Noun, D};
+use crate::mem::{NockStack, Preserve};
+use crate::noun::{Atom, Cell, CellMemory, DirectAtom, IndirectAtom, Noun, D};
+use bitvec::field::BitField;
 use bitvec::prelude::{BitSlice, Lsb0};
 use either::Either::{Left, Right};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::mem::size_of;
+use std::slice::from_raw_parts_mut;
 
 crate::gdb!();
 
+/// Size in words of a cell's memory, for [CueLimits::max_words]
+const CELL_WORDS: usize = size_of::<CellMemory>() >> 3;
+
 /// Calculate the number of bits needed to represent an atom
 pub fn met0_usize(atom: Atom) -> usize {
     let atom_bitslice = atom.as_bitslice();
@@ -59,10 +67,315 @@ pub fn rest_bits(cursor: usize, slice: &BitSlice<u64, Lsb0>) -> &BitSlice<u64, L
 
 #[derive(Copy, Clone)]
 enum CueStackEntry {
-    DestinationPointer(*mut Noun),
+    DestinationPointer(*mut Noun, usize),
     BackRef(u64, *const Noun),
 }
 
+/// Resource limits for cueing untrusted input
+///
+/// Each limit is checked before the cell or indirect atom it guards is allocated, so a hostile jam
+/// is rejected without first building an oversized noun. The limits cover only the result: direct
+/// atoms take no memory of their own, and cue's bookkeeping is not counted against `max_words`.
+/// That bookkeeping, freed when cue returns, is a backreference map entry for every noun decoded,
+/// so it grows with the length of the input, and a work stack which grows with `max_depth`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CueLimits {
+    /// Maximum number of 64-bit words of cells and indirect atoms in the result
+    pub max_words: usize,
+    /// Maximum size in bits of any one atom
+    pub max_atom_bits: usize,
+    /// Maximum nesting depth of cells
+    pub max_depth: usize,
+    /// Maximum number of backreferences
+    pub max_backrefs: usize,
+}
+
+impl CueLimits {
+    /// No limits beyond those of the stack, as for [cue]
+    pub const UNLIMITED: CueLimits = CueLimits {
+        max_words: usize::MAX,
+        max_atom_bits: usize::MAX,
+        max_depth: usize::MAX,
+        max_backrefs: usize::MAX,
+    };
+}
+
+/// The limit in [CueLimits] which an input exceeded
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CueLimit {
+    Words,
+    AtomBits,
+    Depth,
+    Backrefs,
+}
+
+impl fmt::Display for CueLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CueLimit::Words => write!(f, "output words"),
+            CueLimit::AtomBits => write!(f, "atom bits"),
+            CueLimit::Depth => write!(f, "depth"),
+            CueLimit::Backrefs => write!(f, "backreferences"),
+        }
+    }
+}
+
+/// Why a jam could not be cued. Bit offsets are from the start of the input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CueError {
+    #[error("malformed jam at bit {0}")]
+    Malformed(usize),
+    #[error("limit on {limit} exceeded at bit {at}")]
+    OverLimit { limit: CueLimit, at: usize },
+    #[error("backreference at bit {at} to bit {backref}, which is not the start of a noun")]
+    BadBackref { backref: u64, at: usize },
+    #[error("I/O error reading jam: {0}")]
+    Io(io::ErrorKind),
+}
+
+impl Preserve for CueError {
+    unsafe fn preserve(&mut self, _: &mut NockStack) {}
+
+    unsafe fn assert_in_stack(&self, _: &NockStack) {}
+}
+
+impl From<CueError> for Error {
+    fn from(err: CueError) -> Self {
+        match err {
+            CueError::Io(_) => NonDeterministic(Fail, D(0)),
+            _ => Deterministic(Exit, D(0)),
+        }
+    }
+}
+
+/// A jammed bit stream, read front to back by [cue_source]
+///
+/// Reads past the end of the input produce 0 bits, as they would from the high bits of an atom.
+trait CueSource {
+    /// Offset in bits of the next bit to be read, which is what backreferences refer to
+    fn cursor(&self) -> usize;
+
+    /// Read the next bit
+    fn next_bit(&mut self) -> Result<bool, CueError>;
+
+    /// Read the next `n` bits, for `n` at most 64, as the low bits of a word
+    fn next_word(&mut self, n: usize) -> Result<u64, CueError>;
+
+    /// Skip zero bits up to and including the next 1 bit, returning the number of zeros skipped
+    ///
+    /// Fails if the input ends before a 1 bit is found.
+    fn next_one(&mut self) -> Result<usize, CueError>;
+
+    /// Read the next `n` bits into the low bits of `dest`, which must be zeroed
+    fn next_bits(&mut self, dest: &mut [u64], n: usize) -> Result<(), CueError> {
+        let words = (n + 63) >> 6;
+        for (i, word) in dest[..words].iter_mut().enumerate() {
+            *word = self.next_word(std::cmp::min(64, n - (i << 6)))?;
+        }
+        Ok(())
+    }
+}
+
+/// A [CueSource] over the bits of an atom
+struct BitSliceSource<'a> {
+    cursor: usize,
+    slice: &'a BitSlice<u64, Lsb0>,
+}
+
+impl CueSource for BitSliceSource<'_> {
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn next_bit(&mut self) -> Result<bool, CueError> {
+        Ok(next_bit(&mut self.cursor, self.slice))
+    }
+
+    fn next_word(&mut self, n: usize) -> Result<u64, CueError> {
+        let mut word: u64 = 0;
+        let bits = next_up_to_n_bits(&mut self.cursor, self.slice, n);
+        BitSlice::from_element_mut(&mut word)[0..bits.len()].copy_from_bitslice(bits);
+        Ok(word)
+    }
+
+    fn next_one(&mut self) -> Result<usize, CueError> {
+        let zeros = rest_bits(self.cursor, self.slice)
+            .first_one()
+            .ok_or(CueError::Malformed(self.cursor))?;
+        self.cursor += zeros + 1;
+        Ok(zeros)
+    }
+
+    fn next_bits(&mut self, dest: &mut [u64], n: usize) -> Result<(), CueError> {
+        let bits = next_up_to_n_bits(&mut self.cursor, self.slice, n);
+        BitSlice::<u64, Lsb0>::from_slice_mut(dest)[0..bits.len()].copy_from_bitslice(bits);
+        Ok(())
+    }
+}
+
+/// Read `n` bits, for `n` at most 64, starting at bit `pos` of a little-endian byte buffer
+fn bits_at(bytes: &[u8], pos: usize, n: usize) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let byte = pos >> 3;
+    let shift = pos & 7;
+    let mut buf = [0u8; 9];
+    if byte < bytes.len() {
+        let end = std::cmp::min(bytes.len(), byte + 9);
+        buf[..end - byte].copy_from_slice(&bytes[byte..end]);
+    }
+    let mut word =
+        u64::from_le_bytes([buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7]]);
+    if shift != 0 {
+        word = (word >> shift) | ((buf[8] as u64) << (64 - shift));
+    }
+    if n < 64 {
+        word & ((1 << n) - 1)
+    } else {
+        word
+    }
+}
+
+/// Count the zero bits of a little-endian byte buffer from bit `pos` up to the next 1 bit, or
+/// `None` if there are no more 1 bits in the buffer
+fn zeros_at(bytes: &[u8], pos: usize) -> Option<usize> {
+    let mut at = pos;
+    while at < bytes.len() << 3 {
+        let word = bits_at(bytes, at, 64);
+        if word != 0 {
+            return Some(at + word.trailing_zeros() as usize - pos);
+        }
+        at += 64;
+    }
+    None
+}
+
+/// A [CueSource] over a byte buffer, such as a memory-mapped pill
+struct ByteSource<'a> {
+    cursor: usize,
+    bytes: &'a [u8],
+}
+
+impl CueSource for ByteSource<'_> {
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn next_bit(&mut self) -> Result<bool, CueError> {
+        let byte = self.cursor >> 3;
+        let res = byte < self.bytes.len() && (self.bytes[byte] >> (self.cursor & 7)) & 1 == 1;
+        self.cursor += 1;
+        Ok(res)
+    }
+
+    fn next_word(&mut self, n: usize) -> Result<u64, CueError> {
+        let word = bits_at(self.bytes, self.cursor, n);
+        self.cursor += n;
+        Ok(word)
+    }
+
+    fn next_one(&mut self) -> Result<usize, CueError> {
+        let zeros = zeros_at(self.bytes, self.cursor).ok_or(CueError::Malformed(self.cursor))?;
+        self.cursor += zeros + 1;
+        Ok(zeros)
+    }
+}
+
+/// Minimum number of bytes to request from the reader at once
+const READ_CHUNK: usize = 1 << 16;
+
+/// A [CueSource] which reads incrementally from an [io::Read], keeping only the bytes it has not
+/// yet decoded
+struct ReaderSource<R: Read> {
+    reader: R,
+    /// Bytes read but not yet consumed
+    buffer: Vec<u8>,
+    /// Offset in bytes of the start of `buffer` in the stream
+    base: usize,
+    cursor: usize,
+    eof: bool,
+}
+
+impl<R: Read> ReaderSource<R> {
+    fn new(reader: R) -> Self {
+        ReaderSource {
+            reader,
+            buffer: Vec::new(),
+            base: 0,
+            cursor: 0,
+            eof: false,
+        }
+    }
+
+    /// Position of the cursor relative to the start of the buffer
+    fn offset(&self) -> usize {
+        self.cursor - (self.base << 3)
+    }
+
+    /// Make sure the buffer holds every byte up to bit `end` of the stream, unless it ends first
+    fn fill(&mut self, end: usize) -> Result<(), CueError> {
+        let want = (end + 7) >> 3;
+        if self.eof || self.base + self.buffer.len() >= want {
+            return Ok(());
+        }
+        let consumed = std::cmp::min(self.buffer.len(), (self.cursor >> 3) - self.base);
+        self.buffer.drain(..consumed);
+        self.base += consumed;
+        while self.base + self.buffer.len() < want {
+            let len = self.buffer.len();
+            let grow = std::cmp::max(want - self.base - len, READ_CHUNK);
+            self.buffer.resize(len + grow, 0);
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(0) => {
+                    self.buffer.truncate(len);
+                    self.eof = true;
+                    break;
+                }
+                Ok(read) => self.buffer.truncate(len + read),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => self.buffer.truncate(len),
+                Err(err) => {
+                    self.buffer.truncate(len);
+                    return Err(CueError::Io(err.kind()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> CueSource for ReaderSource<R> {
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn next_bit(&mut self) -> Result<bool, CueError> {
+        self.next_word(1).map(|bit| bit == 1)
+    }
+
+    fn next_word(&mut self, n: usize) -> Result<u64, CueError> {
+        self.fill(self.cursor + n)?;
+        let word = bits_at(&self.buffer, self.offset(), n);
+        self.cursor += n;
+        Ok(word)
+    }
+
+    fn next_one(&mut self) -> Result<usize, CueError> {
+        let start = self.cursor;
+        loop {
+            self.fill(self.cursor + 64)?;
+            if let Some(zeros) = zeros_at(&self.buffer, self.offset()) {
+                self.cursor += zeros + 1;
+                break Ok(self.cursor - start - 1);
+            } else if self.eof {
+                break Err(CueError::Malformed(start));
+            }
+            // Everything buffered past the cursor is zeros
+            self.cursor = (self.base + self.buffer.len()) << 3;
+        }
+    }
+}
+
 /// Deserialize a noun from a BitSlice
 ///
 /// This function implements the inverse of jam, unpacking a serialized noun.
@@ -103,14 +416,32 @@ enum CueStackEntry {
 /// # Returns
 /// A Result containing either the deserialized Noun or an Error
 pub fn cue_bitslice(stack: &mut NockStack, buffer: &BitSlice<u64, Lsb0>) -> Result<Noun, Error> {
+    let mut source = BitSliceSource {
+        cursor: 0,
+        slice: buffer,
+    };
+    Ok(cue_source(stack, &mut source, &CueLimits::UNLIMITED)?)
+}
+
+/// Deserialize a noun from a [CueSource] within `limits`. See [cue_bitslice].
+///
+/// A cell is entered in the backreference map only once both its head and tail are decoded, as
+/// in the hoon, so a backreference cannot make a cycle.
+fn cue_source<S: CueSource>(
+    stack: &mut NockStack,
+    source: &mut S,
+    limits: &CueLimits,
+) -> Result<Noun, CueError> {
     let backref_map = MutHamt::<Noun>::new(stack);
     let mut result = D(0);
-    let mut cursor = 0;
+    let mut words: usize = 0;
+    let mut backrefs: usize = 0;
+    let over = |limit, at| CueError::OverLimit { limit, at };
 
     unsafe {
         stack.with_frame(0, |stack: &mut NockStack| {
             *(stack.push::<CueStackEntry>()) =
-                CueStackEntry::DestinationPointer(&mut result as *mut Noun);
+                CueStackEntry::DestinationPointer(&mut result as *mut Noun, 0);
             loop {
                 if stack.stack_is_empty() {
                     break Ok(result);
@@ -119,37 +450,57 @@ pub fn cue_bitslice(stack: &mut NockStack, buffer: &BitSlice<u64, Lsb0>) -> Resu
                 stack.pop::<CueStackEntry>();
                 // Capture the destination pointer and pop it off the stack
                 match stack_entry {
-                    CueStackEntry::DestinationPointer(dest_ptr) => {
+                    CueStackEntry::DestinationPointer(dest_ptr, depth) => {
+                        let at = source.cursor();
                         // 1 bit
-                        if next_bit(&mut cursor, buffer) {
+                        if source.next_bit()? {
                             // 11 tag: backref
-                            if next_bit(&mut cursor, buffer) {
-                                let mut backref_noun =
-                                    Atom::new(stack, rub_backref(&mut cursor, buffer)?).as_noun();
+                            if source.next_bit()? {
+                                backrefs += 1;
+                                if backrefs > limits.max_backrefs {
+                                    break Err(over(CueLimit::Backrefs, at));
+                                }
+                                let backref = rub_backref(source)?;
+                                let mut backref_noun = Atom::new(stack, backref).as_noun();
                                 *dest_ptr = backref_map
                                     .lookup(stack, &mut backref_noun)
-                                    .ok_or(Deterministic(Exit, D(0)))?;
+                                    .ok_or(CueError::BadBackref { backref, at })?;
                             } else {
                                 // 10 tag: cell
+                                if depth >= limits.max_depth {
+                                    break Err(over(CueLimit::Depth, at));
+                                }
+                                words += CELL_WORDS;
+                                if words > limits.max_words {
+                                    break Err(over(CueLimit::Words, at));
+                                }
                                 let (cell, cell_mem_ptr) = Cell::new_raw_mut(stack);
                                 *dest_ptr = cell.as_noun();
-                                let mut backref_atom =
-                                    Atom::new(stack, (cursor - 2) as u64).as_noun();
-                                backref_map.insert(stack, &mut backref_atom, *dest_ptr);
-                                *(stack.push()) = CueStackEntry::BackRef(
-                                    cursor as u64 - 2,
-                                    dest_ptr as *const Noun,
-                                );
-                                *(stack.push()) =
-                                    CueStackEntry::DestinationPointer(&mut (*cell_mem_ptr).tail);
                                 *(stack.push()) =
-                                    CueStackEntry::DestinationPointer(&mut (*cell_mem_ptr).head);
+                                    CueStackEntry::BackRef(at as u64, dest_ptr as *const Noun);
+                                *(stack.push()) = CueStackEntry::DestinationPointer(
+                                    &mut (*cell_mem_ptr).tail,
+                                    depth + 1,
+                                );
+                                *(stack.push()) = CueStackEntry::DestinationPointer(
+                                    &mut (*cell_mem_ptr).head,
+                                    depth + 1,
+                                );
                             }
                         } else {
                             // 0 tag: atom
-                            let backref: u64 = (cursor - 1) as u64;
-                            *dest_ptr = rub_atom(stack, &mut cursor, buffer)?.as_noun();
-                            let mut backref_atom = Atom::new(stack, backref).as_noun();
+                            let size = get_size(source)?;
+                            if size > limits.max_atom_bits {
+                                break Err(over(CueLimit::AtomBits, at));
+                            }
+                            if size >= 64 {
+                                words += ((size + 63) >> 6) + 2;
+                                if words > limits.max_words {
+                                    break Err(over(CueLimit::Words, at));
+                                }
+                            }
+                            *dest_ptr = rub_atom(stack, source, size)?.as_noun();
+                            let mut backref_atom = Atom::new(stack, at as u64).as_noun();
                             backref_map.insert(stack, &mut backref_atom, *dest_ptr);
                         }
                     }
@@ -178,28 +529,285 @@ pub fn cue(stack: &mut NockStack, buffer: Atom) -> Result<Noun, Error> {
     cue_bitslice(stack, buffer_bitslice)
 }
 
+/// Deserialize a noun from an Atom of untrusted origin, within `limits`
+pub fn cue_limited(
+    stack: &mut NockStack,
+    buffer: Atom,
+    limits: &CueLimits,
+) -> Result<Noun, CueError> {
+    let mut source = BitSliceSource {
+        cursor: 0,
+        slice: buffer.as_bitslice(),
+    };
+    cue_source(stack, &mut source, limits)
+}
+
+/// Deserialize a noun from a byte buffer
+///
+/// The bytes are the little-endian bytes of the jammed atom, as in a pill file, so a
+/// memory-mapped file can be decoded without first copying it into an atom. Produces the same
+/// noun as [cue_bitslice].
+pub fn cue_bytes(stack: &mut NockStack, bytes: &[u8]) -> Result<Noun, Error> {
+    Ok(cue_bytes_limited(stack, bytes, &CueLimits::UNLIMITED)?)
+}
+
+/// Deserialize a noun from a byte buffer of untrusted origin, such as a network packet, within
+/// `limits`. See [cue_bytes].
+pub fn cue_bytes_limited(
+    stack: &mut NockStack,
+    bytes: &[u8],
+    limits: &CueLimits,
+) -> Result<Noun, CueError> {
+    let mut source = ByteSource { cursor: 0, bytes };
+    cue_source(stack, &mut source, limits)
+}
+
+/// Deserialize a noun from a stream of the little-endian bytes of a jammed atom
+///
+/// Decodes incrementally, buffering only the part of the stream not yet decoded. The reader may
+/// be read past the end of the jammed noun, so wrap it in [Read::take] if anything follows.
+/// Fails with a nondeterministic error if the reader does.
+pub fn cue_reader<R: Read>(stack: &mut NockStack, reader: R) -> Result<Noun, Error> {
+    Ok(cue_reader_limited(stack, reader, &CueLimits::UNLIMITED)?)
+}
+
+/// Deserialize a noun from a stream of untrusted origin, such as a socket, within `limits`. See
+/// [cue_reader].
+pub fn cue_reader_limited<R: Read>(
+    stack: &mut NockStack,
+    reader: R,
+    limits: &CueLimits,
+) -> Result<Noun, CueError> {
+    let mut source = ReaderSource::new(reader);
+    cue_source(stack, &mut source, limits)
+}
+
+/// A bit cursor over a little-endian byte buffer which reads a word at a time, for [cue_fast]
+struct WordReader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl WordReader<'_> {
+    /// The 64 bits from the cursor, zero past the end of the input
+    #[inline]
+    fn peek(&self) -> u64 {
+        let byte = self.cursor >> 3;
+        if byte + 9 <= self.bytes.len() {
+            let shift = self.cursor & 7;
+            let mut lo = [0u8; 8];
+            lo.copy_from_slice(&self.bytes[byte..byte + 8]);
+            let word = u64::from_le_bytes(lo);
+            if shift == 0 {
+                word
+            } else {
+                (word >> shift) | ((self.bytes[byte + 8] as u64) << (64 - shift))
+            }
+        } else {
+            bits_at(self.bytes, self.cursor, 64)
+        }
+    }
+
+    /// Read `n` bits, for `n` less than 64
+    #[inline]
+    fn read(&mut self, n: usize) -> u64 {
+        let word = self.peek() & ((1 << n) - 1);
+        self.cursor += n;
+        word
+    }
+
+    /// Read the size prefix of an atom or backref, as [get_size] does
+    ///
+    /// The prefix of a size which does not fit in a word is rejected without scanning the zeros.
+    #[inline]
+    fn size(&mut self) -> Result<usize, CueError> {
+        let at = self.cursor;
+        let word = self.peek();
+        if word == 0 {
+            return Err(CueError::Malformed(at));
+        }
+        let bitsize = word.trailing_zeros() as usize;
+        self.cursor += bitsize + 1;
+        if bitsize == 0 {
+            Ok(0)
+        } else {
+            Ok((self.read(bitsize - 1) as usize) + (1 << (bitsize - 1)))
+        }
+    }
+
+    /// Read `n` bits into the low bits of `dest`
+    fn read_words(&mut self, dest: &mut [u64], n: usize) {
+        if self.cursor & 7 == 0 {
+            // Byte-aligned, so copy the bytes directly
+            let src = self.bytes.get(self.cursor >> 3..).unwrap_or(&[]);
+            let len = std::cmp::min((n + 7) >> 3, src.len());
+            unsafe {
+                std::ptr::copy_nonoverlapping(src.as_ptr(), dest.as_mut_ptr() as *mut u8, len);
+            }
+        } else {
+            let mut words = WordReader {
+                bytes: self.bytes,
+                cursor: self.cursor,
+            };
+            for word in dest[..(n + 63) >> 6].iter_mut() {
+                *word = words.peek();
+                words.cursor += 64;
+            }
+        }
+        if n & 63 != 0 {
+            dest[(n - 1) >> 6] &= (1 << (n & 63)) - 1;
+        }
+        self.cursor += n;
+    }
+}
+
+#[derive(Copy, Clone)]
+enum FastCueEntry {
+    DestinationPointer(*mut Noun),
+    /// Fill in a cell's entry in the backreference table, now that its head and tail are decoded
+    Complete(usize, Noun),
+}
+
+/// Backreferences resolved by [cue_fast_bytes], sorted by bit offset
+///
+/// Nouns start in increasing bit order, so entries are pushed in order and found by binary search.
+/// A cell's entry is pushed when it starts, marked incomplete by the low bit of its key until its
+/// tail is decoded, so that a backreference to an enclosing cell is rejected as [cue] rejects it.
+struct BackrefTable {
+    /// (bit offset << 1 | incomplete, noun)
+    nouns: Vec<(u64, Noun)>,
+}
+
+impl BackrefTable {
+    fn insert(&mut self, offset: usize, noun: Noun) {
+        self.nouns.push(((offset as u64) << 1, noun));
+    }
+
+    /// Push an incomplete entry for a cell, and return its index for [BackrefTable::complete]
+    fn open(&mut self, offset: usize) -> usize {
+        self.nouns.push(((offset as u64) << 1 | 1, D(0)));
+        self.nouns.len() - 1
+    }
+
+    fn complete(&mut self, index: usize, cell: Noun) {
+        let (key, _) = self.nouns[index];
+        self.nouns[index] = (key & !1, cell);
+    }
+
+    fn get(&self, offset: u64) -> Option<Noun> {
+        let index = self
+            .nouns
+            .binary_search_by_key(&offset, |(key, _)| key >> 1)
+            .ok()?;
+        let (key, noun) = self.nouns[index];
+        (key & 1 == 0).then_some(noun)
+    }
+}
+
+/// Deserialize a noun from an Atom, faster than [cue] and producing the same noun
+///
+/// See [cue_fast_bytes].
+pub fn cue_fast(stack: &mut NockStack, buffer: Atom) -> Result<Noun, Error> {
+    cue_fast_bytes(stack, buffer.as_bytes())
+}
+
+/// Deserialize a noun from a byte buffer, faster than [cue_bytes] and producing the same noun
+///
+/// Tags and size prefixes are read a word at a time rather than a bit at a time, and
+/// backreferences are resolved by binary search on a sorted table of bit offsets instead of in a
+/// [MutHamt] keyed by atoms. The table is on the heap and grows by 16 bytes for every noun decoded,
+/// counted against no limit, so this is for trusted input such as pills, not for use within jets.
+/// [cue] remains the reference this is tested against.
+pub fn cue_fast_bytes(stack: &mut NockStack, bytes: &[u8]) -> Result<Noun, Error> {
+    let mut reader = WordReader { bytes, cursor: 0 };
+    let mut table = BackrefTable { nouns: Vec::new() };
+    let mut result = D(0);
+
+    let res = unsafe {
+        stack.with_frame(0, |stack: &mut NockStack| {
+            *(stack.push::<FastCueEntry>()) =
+                FastCueEntry::DestinationPointer(&mut result as *mut Noun);
+            loop {
+                if stack.stack_is_empty() {
+                    break Ok(result);
+                }
+                let entry = *stack.top::<FastCueEntry>();
+                stack.pop::<FastCueEntry>();
+                match entry {
+                    FastCueEntry::DestinationPointer(dest_ptr) => {
+                        let at = reader.cursor;
+                        let tag = reader.peek();
+                        if tag & 1 == 0 {
+                            // 0 tag: atom
+                            reader.cursor += 1;
+                            let size = reader.size()?;
+                            let atom = if size < 64 {
+                                DirectAtom::new_unchecked(reader.read(size)).as_atom()
+                            } else {
+                                let wordsize = (size + 63) >> 6;
+                                let (mut atom, buffer) =
+                                    IndirectAtom::new_raw_mut_zeroed(stack, wordsize);
+                                reader.read_words(from_raw_parts_mut(buffer, wordsize), size);
+                                atom.normalize_as_atom()
+                            };
+                            *dest_ptr = atom.as_noun();
+                            table.insert(at, *dest_ptr);
+                        } else if tag & 2 == 0 {
+                            // 10 tag: cell
+                            reader.cursor += 2;
+                            let (cell, cell_mem_ptr) = Cell::new_raw_mut(stack);
+                            *dest_ptr = cell.as_noun();
+                            *(stack.push()) = FastCueEntry::Complete(table.open(at), *dest_ptr);
+                            *(stack.push()) =
+                                FastCueEntry::DestinationPointer(&mut (*cell_mem_ptr).tail);
+                            *(stack.push()) =
+                                FastCueEntry::DestinationPointer(&mut (*cell_mem_ptr).head);
+                        } else {
+                            // 11 tag: backref
+                            reader.cursor += 2;
+                            let size = reader.size()?;
+                            let backref = if size == 0 {
+                                0
+                            } else if size < 64 {
+                                reader.read(size)
+                            } else if size == 64 {
+                                let low = reader.read(32);
+                                (reader.read(32) << 32) | low
+                            } else {
+                                break Err(CueError::Malformed(at));
+                            };
+                            *dest_ptr = table
+                                .get(backref)
+                                .ok_or(CueError::BadBackref { backref, at })?;
+                        }
+                    }
+                    FastCueEntry::Complete(index, cell) => {
+                        table.complete(index, cell);
+                    }
+                }
+            }
+        })
+    };
+    Ok(res?)
+}
+
 /// Get the size in bits of an encoded atom or backref
-/// TODO: use first_zero() on a slice of the buffer
-fn get_size(cursor: &mut usize, buffer: &BitSlice<u64, Lsb0>) -> Result<usize, Error> {
-    let buff_at_cursor = rest_bits(*cursor, buffer);
-    let bitsize = buff_at_cursor
-        .first_one()
-        .ok_or(Deterministic(Exit, D(0)))?;
+fn get_size<S: CueSource>(source: &mut S) -> Result<usize, CueError> {
+    let at = source.cursor();
+    let bitsize = source.next_one()?;
     if bitsize == 0 {
-        *cursor += 1;
         Ok(0)
+    } else if bitsize > 64 {
+        Err(CueError::Malformed(at))
     } else {
-        let mut size: u64 = 0;
-        *cursor += bitsize + 1;
-        let size_bits = next_up_to_n_bits(cursor, buffer, bitsize - 1);
-        BitSlice::from_element_mut(&mut size)[0..bitsize - 1].copy_from_bitslice(size_bits);
+        let size = source.next_word(bitsize - 1)?;
         Ok((size as usize) + (1 << (bitsize - 1)))
     }
 }
 
-/// Length-decode an atom from the buffer
+/// Length-decode an atom of `size` bits from the buffer, after its size
 ///
-/// Corresponds to `++rub` in the hoon stdlib.
+/// Together with [get_size], corresponds to `++rub` in the hoon stdlib.
 ///
 /// ```hoon
 /// ++  rub                                                 ::  length-decode
@@ -218,48 +826,52 @@ fn get_size(cursor: &mut usize, buffer: &BitSlice<u64, Lsb0>) -> Result<usize, E
 ///   =+  e=(add (bex (dec c)) (cut 0 [d (dec c)] b))
 ///   [(add (add c c) e) (cut 0 [(add d (dec c)) e] b)]
 /// ```
-fn rub_atom(
+fn rub_atom<S: CueSource>(
     stack: &mut NockStack,
-    cursor: &mut usize,
-    buffer: &BitSlice<u64, Lsb0>,
-) -> Result<Atom, Error> {
-    let size = get_size(cursor, buffer)?;
-    let bits = next_up_to_n_bits(cursor, buffer, size);
+    source: &mut S,
+    size: usize,
+) -> Result<Atom, CueError> {
     if size == 0 {
         unsafe { Ok(DirectAtom::new_unchecked(0).as_atom()) }
     } else if size < 64 {
         // Fits in a direct atom
-        let mut direct_raw = 0;
-        BitSlice::from_element_mut(&mut direct_raw)[0..bits.len()].copy_from_bitslice(bits);
+        let direct_raw = source.next_word(size)?;
         unsafe { Ok(DirectAtom::new_unchecked(direct_raw).as_atom()) }
     } else {
         // Need an indirect atom
         let wordsize = (size + 63) >> 6;
-        let (mut atom, slice) = unsafe { IndirectAtom::new_raw_mut_bitslice(stack, wordsize) };
-        slice[0..bits.len()].copy_from_bitslice(bits);
+        let (mut atom, buffer) = unsafe { IndirectAtom::new_raw_mut_zeroed(stack, wordsize) };
+        source.next_bits(unsafe { from_raw_parts_mut(buffer, wordsize) }, size)?;
         debug_assert!(atom.size() > 0);
         unsafe { Ok(atom.normalize_as_atom()) }
     }
 }
 
 /// Deserialize a backreference from the buffer
-fn rub_backref(cursor: &mut usize, buffer: &BitSlice<u64, Lsb0>) -> Result<u64, Error> {
-    // TODO: What's size here usually?
-    let size = get_size(cursor, buffer)?;
+fn rub_backref<S: CueSource>(source: &mut S) -> Result<u64, CueError> {
+    let at = source.cursor();
+    let size = get_size(source)?;
     if size == 0 {
         Ok(0)
     } else if size <= 64 {
-        // TODO: Size <= 64, so we can fit the backref in a direct atom?
-        let mut backref: u64 = 0;
-        BitSlice::from_element_mut(&mut backref)[0..size]
-            .copy_from_bitslice(&buffer[*cursor..*cursor + size]);
-        *cursor += size;
-        Ok(backref)
+        source.next_word(size)
     } else {
-        Err(NonDeterministic(Fail, D(0)))
+        Err(CueError::Malformed(at))
     }
 }
 
+/// A bit stream for [jam_sink] to write into, front to back
+trait JamSink {
+    /// Offset in bits of the next bit to be written, which is what backreferences refer to
+    fn cursor(&self) -> usize;
+
+    /// Write the low `n` bits of `word`, for `n` at most 64
+    fn write_word(&mut self, stack: &mut NockStack, word: u64, n: usize);
+
+    /// Write all of `bits`
+    fn write_bits(&mut self, stack: &mut NockStack, bits: &BitSlice<u64, Lsb0>);
+}
+
 struct JamState<'a> {
     cursor: usize,
     size: usize,
@@ -267,6 +879,116 @@ struct JamState<'a> {
     slice: &'a mut BitSlice<u64, Lsb0>,
 }
 
+impl JamState<'_> {
+    /// Double the size of the atom until `n` more bits fit
+    fn reserve(&mut self, stack: &mut NockStack, n: usize) {
+        while self.cursor + n > self.slice.len() {
+            double_atom_size(stack, self);
+        }
+    }
+}
+
+impl JamSink for JamState<'_> {
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn write_word(&mut self, stack: &mut NockStack, word: u64, n: usize) {
+        self.reserve(stack, n);
+        self.slice[self.cursor..self.cursor + n]
+            .copy_from_bitslice(&BitSlice::<u64, Lsb0>::from_element(&word)[0..n]);
+        self.cursor += n;
+    }
+
+    fn write_bits(&mut self, stack: &mut NockStack, bits: &BitSlice<u64, Lsb0>) {
+        self.reserve(stack, bits.len());
+        self.slice[self.cursor..self.cursor + bits.len()].copy_from_bitslice(bits);
+        self.cursor += bits.len();
+    }
+}
+
+/// Number of buffered bytes at which [ByteSink] hands them to its writer
+const WRITE_CHUNK: usize = 1 << 16;
+
+/// A [JamSink] which packs bits into bytes, handing them to a writer in chunks
+///
+/// Like the adapter behind [Write::write_fmt], it keeps the first error from the writer and
+/// drops everything written after it, so that the traversal need not check every write.
+struct ByteSink<W: Write> {
+    writer: W,
+    /// Whole bytes not yet handed to the writer
+    buffer: Vec<u8>,
+    /// Buffer length at which to write to the writer, or `usize::MAX` to keep everything
+    flush_at: usize,
+    /// Bits written past the last whole word
+    word: u64,
+    cursor: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> ByteSink<W> {
+    fn new(writer: W, flush_at: usize) -> Self {
+        ByteSink {
+            writer,
+            buffer: Vec::new(),
+            flush_at,
+            word: 0,
+            cursor: 0,
+            error: None,
+        }
+    }
+
+    fn push_word(&mut self, word: u64) {
+        self.buffer.extend_from_slice(&word.to_le_bytes());
+        if self.buffer.len() >= self.flush_at {
+            self.flush_buffer();
+        }
+    }
+
+    fn flush_buffer(&mut self) {
+        if self.error.is_none() {
+            if let Err(err) = self.writer.write_all(&self.buffer) {
+                self.error = Some(err);
+            }
+        }
+        self.buffer.clear();
+    }
+
+    /// Buffer the last partial word, trimmed to whole bytes
+    fn finish(&mut self) {
+        let rest = ((self.cursor & 63) + 7) >> 3;
+        self.buffer
+            .extend_from_slice(&self.word.to_le_bytes()[..rest]);
+        self.word = 0;
+    }
+}
+
+impl<W: Write> JamSink for ByteSink<W> {
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn write_word(&mut self, _stack: &mut NockStack, word: u64, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let word = if n < 64 { word & ((1 << n) - 1) } else { word };
+        let used = self.cursor & 63;
+        self.word |= word << used;
+        if used + n >= 64 {
+            self.push_word(self.word);
+            self.word = if used == 0 { 0 } else { word >> (64 - used) };
+        }
+        self.cursor += n;
+    }
+
+    fn write_bits(&mut self, stack: &mut NockStack, bits: &BitSlice<u64, Lsb0>) {
+        for chunk in bits.chunks(64) {
+            self.write_word(stack, chunk.load_le::<u64>(), chunk.len());
+        }
+    }
+}
+
 /// Serialize a noun into an atom
 ///
 /// Corresponds to ++jam in the hoon stdlib.
@@ -283,6 +1005,59 @@ pub fn jam(stack: &mut NockStack, noun: Noun) -> Atom {
         slice,
     };
     stack.frame_push(0);
+    jam_sink(stack, backref_map, &mut state, noun);
+    unsafe {
+        let mut result = state.atom.normalize_as_atom();
+        stack.preserve(&mut result);
+        stack.frame_pop();
+        result
+    }
+}
+
+/// Serialize a noun into a byte vector
+///
+/// The bytes are those of the atom [jam] would produce, without building that atom on the stack.
+pub fn jam_to_vec(stack: &mut NockStack, noun: Noun) -> Vec<u8> {
+    let mut sink = ByteSink::new(io::sink(), usize::MAX);
+    jam_bytes(stack, &mut sink, noun);
+    sink.buffer
+}
+
+/// Serialize a noun into a writer
+///
+/// Writes the bytes of the atom [jam] would produce, a chunk at a time, without building that
+/// atom on the stack. Returns the number of bytes written.
+pub fn jam_to_writer<W: Write>(stack: &mut NockStack, noun: Noun, writer: W) -> io::Result<usize> {
+    let mut sink = ByteSink::new(writer, WRITE_CHUNK);
+    jam_bytes(stack, &mut sink, noun);
+    sink.flush_buffer();
+    match sink.error {
+        Some(err) => Err(err),
+        None => {
+            sink.writer.flush()?;
+            Ok((sink.cursor + 7) >> 3)
+        }
+    }
+}
+
+/// Serialize a noun into a [ByteSink], in a frame of its own
+fn jam_bytes<W: Write>(stack: &mut NockStack, sink: &mut ByteSink<W>, noun: Noun) {
+    stack.frame_push(0);
+    let backref_map = MutHamt::new(stack);
+    jam_sink(stack, backref_map, sink, noun);
+    unsafe { stack.frame_pop() };
+    sink.finish();
+}
+
+/// Serialize a noun into a [JamSink]
+///
+/// Uses the top of the current frame as its work stack.
+fn jam_sink<S: JamSink>(
+    stack: &mut NockStack,
+    backref_map: MutHamt<u64>,
+    sink: &mut S,
+    noun: Noun,
+) {
     unsafe {
         *(stack.push::<Noun>()) = noun;
     };
@@ -297,13 +1072,13 @@ pub fn jam(stack: &mut NockStack, noun: Noun) -> Atom {
                         let atom_size = met0_usize(atom);
                         let backref_size = met0_u64_to_usize(backref);
                         if atom_size <= backref_size {
-                            jam_atom(stack, &mut state, atom);
+                            jam_atom(stack, sink, atom);
                         } else {
-                            jam_backref(stack, &mut state, backref);
+                            jam_backref(stack, sink, backref);
                         }
                     }
                     Right(_cell) => {
-                        jam_backref(stack, &mut state, backref);
+                        jam_backref(stack, sink, backref);
                     }
                 }
                 unsafe {
@@ -311,17 +1086,17 @@ pub fn jam(stack: &mut NockStack, noun: Noun) -> Atom {
                 };
                 continue 'jam;
             };
-            backref_map.insert(stack, &mut noun, state.cursor as u64);
+            backref_map.insert(stack, &mut noun, sink.cursor() as u64);
             match noun.as_either_atom_cell() {
                 Left(atom) => {
-                    jam_atom(stack, &mut state, atom);
+                    jam_atom(stack, sink, atom);
                     unsafe {
                         stack.pop::<Noun>();
                     };
                     continue;
                 }
                 Right(cell) => {
-                    jam_cell(stack, &mut state);
+                    jam_cell(stack, sink);
                     unsafe {
                         stack.pop::<Noun>();
                         *(stack.push::<Noun>()) = cell.tail();
@@ -332,68 +1107,24 @@ pub fn jam(stack: &mut NockStack, noun: Noun) -> Atom {
             }
         }
     }
-    unsafe {
-        let mut result = state.atom.normalize_as_atom();
-        stack.preserve(&mut result);
-        stack.frame_pop();
-        result
-    }
 }
 
-/// Serialize an atom into the jam state
-fn jam_atom(traversal: &mut NockStack, state: &mut JamState, atom: Atom) {
-    loop {
-        if state.cursor + 1 > state.slice.len() {
-            double_atom_size(traversal, state);
-        } else {
-            break;
-        }
-    }
-    state.slice.set(state.cursor, false); // 0 tag for atom
-    state.cursor += 1;
-    loop {
-        if let Ok(()) = mat(traversal, state, atom) {
-            break;
-        } else {
-            double_atom_size(traversal, state);
-        }
-    }
+/// Serialize an atom into the jam sink
+fn jam_atom<S: JamSink>(traversal: &mut NockStack, sink: &mut S, atom: Atom) {
+    sink.write_word(traversal, 0, 1); // 0 tag for atom
+    mat(traversal, sink, atom);
 }
 
-/// Serialize a cell into the jam state
-fn jam_cell(traversal: &mut NockStack, state: &mut JamState) {
-    loop {
-        if state.cursor + 2 > state.slice.len() {
-            double_atom_size(traversal, state);
-        } else {
-            break;
-        }
-    }
-    state.slice.set(state.cursor, true); // 1 bit
-    state.slice.set(state.cursor + 1, false); // 0 bit, forming 10 tag for cell
-    state.cursor += 2;
+/// Serialize a cell into the jam sink
+fn jam_cell<S: JamSink>(traversal: &mut NockStack, sink: &mut S) {
+    sink.write_word(traversal, 0b01, 2); // 1 bit then 0 bit, forming 10 tag for cell
 }
 
-/// Serialize a backreference into the jam state
-fn jam_backref(traversal: &mut NockStack, state: &mut JamState, backref: u64) {
-    loop {
-        if state.cursor + 2 > state.slice.len() {
-            double_atom_size(traversal, state);
-        } else {
-            break;
-        }
-    }
-    state.slice.set(state.cursor, true); // 1 bit
-    state.slice.set(state.cursor + 1, true); // 1 bit, forming 11 tag for backref
-    state.cursor += 2;
+/// Serialize a backreference into the jam sink
+fn jam_backref<S: JamSink>(traversal: &mut NockStack, sink: &mut S, backref: u64) {
+    sink.write_word(traversal, 0b11, 2); // 11 tag for backref
     let backref_atom = Atom::new(traversal, backref);
-    loop {
-        if let Ok(()) = mat(traversal, state, backref_atom) {
-            break;
-        } else {
-            double_atom_size(traversal, state);
-        }
-    }
+    mat(traversal, sink, backref_atom);
 }
 
 /// Double the size of the atom in the jam state
@@ -406,35 +1137,23 @@ fn double_atom_size(traversal: &mut NockStack, state: &mut JamState) {
     state.slice = new_slice;
 }
 
-/// Encode an atom's size and value into the jam state
+/// Encode an atom's size and value into the jam sink
 ///
-/// INVARIANT: mat must not modify state.cursor unless it will also return `Ok(())`
-fn mat(traversal: &mut NockStack, state: &mut JamState, atom: Atom) -> Result<(), ()> {
+/// Corresponds to `++mat` in the hoon stdlib.
+fn mat<S: JamSink>(traversal: &mut NockStack, sink: &mut S, atom: Atom) {
     let b_atom_size = met0_usize(atom);
-    let b_atom_size_atom = Atom::new(traversal, b_atom_size as u64);
     if b_atom_size == 0 {
-        if state.cursor + 1 > state.slice.len() {
-            Err(())
-        } else {
-            state.slice.set(state.cursor, true);
-            state.cursor += 1;
-            Ok(())
-        }
+        sink.write_word(traversal, 1, 1);
     } else {
-        let c_b_size = met0_usize(b_atom_size_atom);
-        if state.cursor + c_b_size + c_b_size + b_atom_size > state.slice.len() {
-            Err(())
-        } else {
-            state.slice[state.cursor..state.cursor + c_b_size].fill(false); // a 0 bit for each bit in the atom size
-            state.slice.set(state.cursor + c_b_size, true); // a terminating 1 bit
-            state.slice[state.cursor + c_b_size + 1..state.cursor + c_b_size + c_b_size]
-                .copy_from_bitslice(&b_atom_size_atom.as_bitslice()[0..c_b_size - 1]); // the atom size excepting the most significant 1 (since we know where that is from the size-of-the-size)
-            state.slice[state.cursor + c_b_size + c_b_size
-                ..state.cursor + c_b_size + c_b_size + b_atom_size]
-                .copy_from_bitslice(&atom.as_bitslice()[0..b_atom_size]);
-            state.cursor += c_b_size + c_b_size + b_atom_size;
-            Ok(())
-        }
+        let c_b_size = met0_u64_to_usize(b_atom_size as u64);
+        // a 0 bit for each bit in the atom size
+        sink.write_word(traversal, 0, c_b_size);
+        // a terminating 1 bit
+        sink.write_word(traversal, 1, 1);
+        // the atom size excepting the most significant 1 (since we know where that is from the
+        // size-of-the-size)
+        sink.write_word(traversal, b_atom_size as u64, c_b_size - 1);
+        sink.write_bits(traversal, &atom.as_bitslice()[0..b_atom_size]);
     }
 }
 
@@ -448,7 +1167,8 @@ mod tests {
     use super::*;
     use crate::jets::util::test::assert_noun_eq;
     use crate::mem::NockStack;
-    use crate::noun::{Atom, Cell, CellMemory, Noun};
+    use crate::mug::met3_usize;
+    use crate::noun::{Atom, Cell, CellMemory, Noun, T};
     fn setup_stack() -> NockStack {
         NockStack::new(1 << 30, 0)
     }
@@ -535,6 +1255,198 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// A reader which hands out a few bytes at a time, to exercise refilling
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = std::cmp::min(3, std::cmp::min(buf.len(), self.0.len()));
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_cue_bytes_and_reader() {
+        let mut stack = setup_stack();
+        let mut rng = StdRng::seed_from_u64(2);
+        let (original, _) = generate_deeply_nested_noun(&mut stack, 6, &mut rng);
+        let big = Atom::new(&mut stack, u64::MAX).as_noun();
+        let original = Cell::new(&mut stack, big, original).as_noun();
+        let jammed = jam(&mut stack, original);
+        let bytes = &jammed.as_bytes()[..met3_usize(jammed)];
+
+        let from_bytes = cue_bytes(&mut stack, bytes).unwrap();
+        assert_noun_eq(&mut stack, from_bytes, original);
+        let from_reader = cue_reader(&mut stack, Trickle(bytes)).unwrap();
+        assert_noun_eq(&mut stack, from_reader, original);
+    }
+
+    #[test]
+    fn test_cue_bytes_truncated() {
+        let mut stack = setup_stack();
+        assert!(cue_bytes(&mut stack, &[]).is_err());
+        assert!(cue_reader(&mut stack, Trickle(&[0b11])).is_err());
+    }
+
+    #[test]
+    fn test_jam_to_vec_and_writer() {
+        let mut stack = setup_stack();
+        let mut rng = StdRng::seed_from_u64(3);
+        let (original, _) = generate_deeply_nested_noun(&mut stack, 6, &mut rng);
+        let jammed = jam(&mut stack, original);
+        let bytes = &jammed.as_bytes()[..met3_usize(jammed)];
+
+        let vec = jam_to_vec(&mut stack, original);
+        assert_eq!(&vec[..], bytes);
+        let mut written = Vec::new();
+        let len = jam_to_writer(&mut stack, original, &mut written).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(&written[..], bytes);
+
+        assert_eq!(jam_to_vec(&mut stack, D(0)), vec![0b10]);
+        let cued = cue_bytes(&mut stack, &vec).unwrap();
+        assert_noun_eq(&mut stack, cued, original);
+    }
+
+    #[test]
+    fn test_cue_limited() {
+        let mut stack = setup_stack();
+        let inner = T(&mut stack, &[D(1), D(2), D(3), D(4)]);
+        let jammed = jam(&mut stack, inner);
+        let limits = CueLimits {
+            max_words: 3 * CELL_WORDS,
+            max_atom_bits: 63,
+            max_depth: 3,
+            max_backrefs: 0,
+        };
+        let cued = cue_limited(&mut stack, jammed, &limits).unwrap();
+        assert_noun_eq(&mut stack, cued, inner);
+
+        let shallow = CueLimits {
+            max_depth: 2,
+            ..limits
+        };
+        let err = cue_limited(&mut stack, jammed, &shallow).unwrap_err();
+        assert!(matches!(
+            err,
+            CueError::OverLimit {
+                limit: CueLimit::Depth,
+                ..
+            }
+        ));
+        let small = CueLimits {
+            max_words: 3 * CELL_WORDS - 1,
+            ..limits
+        };
+        let err = cue_limited(&mut stack, jammed, &small).unwrap_err();
+        assert!(matches!(
+            err,
+            CueError::OverLimit {
+                limit: CueLimit::Words,
+                ..
+            }
+        ));
+
+        let big = Atom::new(&mut stack, u64::MAX).as_noun();
+        let jammed = jam(&mut stack, big);
+        let err = cue_limited(&mut stack, jammed, &limits).unwrap_err();
+        assert!(matches!(
+            err,
+            CueError::OverLimit {
+                limit: CueLimit::AtomBits,
+                ..
+            }
+        ));
+
+        let pair = T(&mut stack, &[D(1), D(2)]);
+        let shared = T(&mut stack, &[pair, pair]);
+        let jammed = jam(&mut stack, shared);
+        let err = cue_limited(&mut stack, jammed, &limits).unwrap_err();
+        assert!(matches!(
+            err,
+            CueError::OverLimit {
+                limit: CueLimit::Backrefs,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cue_reader_limited() {
+        let mut stack = setup_stack();
+        let inner = T(&mut stack, &[D(1), D(2), D(3), D(4)]);
+        let jammed = jam(&mut stack, inner);
+        let bytes = &jammed.as_bytes()[..met3_usize(jammed)];
+        let limits = CueLimits {
+            max_words: 3 * CELL_WORDS,
+            max_atom_bits: 63,
+            max_depth: 3,
+            max_backrefs: 0,
+        };
+        let cued = cue_reader_limited(&mut stack, Trickle(bytes), &limits).unwrap();
+        assert_noun_eq(&mut stack, cued, inner);
+
+        let shallow = CueLimits {
+            max_depth: 2,
+            ..limits
+        };
+        let err = cue_reader_limited(&mut stack, Trickle(bytes), &shallow).unwrap_err();
+        assert!(matches!(
+            err,
+            CueError::OverLimit {
+                limit: CueLimit::Depth,
+                ..
+            }
+        ));
+
+        let err =
+            cue_reader_limited(&mut stack, Trickle(&[0b11]), &CueLimits::UNLIMITED).unwrap_err();
+        assert_eq!(err, CueError::Malformed(2));
+    }
+
+    #[test]
+    fn test_cue_backref_to_enclosing_cell() {
+        let mut stack = setup_stack();
+        // A cell whose head is a backreference to the cell itself
+        let cyclic = Atom::new(&mut stack, 0b1011101);
+        let err = cue_limited(&mut stack, cyclic, &CueLimits::UNLIMITED).unwrap_err();
+        assert_eq!(err, CueError::BadBackref { backref: 0, at: 2 });
+        assert!(cue(&mut stack, cyclic).is_err());
+    }
+
+    #[test]
+    fn test_cue_fast_matches_cue() {
+        let mut stack = setup_stack();
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..8 {
+            let (noun, _) = generate_deeply_nested_noun(&mut stack, 5, &mut rng);
+            let big = Atom::new(&mut stack, u64::MAX).as_noun();
+            let noun = T(&mut stack, &[noun, big, noun]);
+            let jammed = jam(&mut stack, noun);
+            let reference = cue(&mut stack, jammed).unwrap();
+            let fast = cue_fast(&mut stack, jammed).unwrap();
+            assert_noun_eq(&mut stack, fast, reference);
+        }
+
+        let invalid = Atom::new(&mut stack, 0b11);
+        assert!(cue_fast(&mut stack, invalid).is_err());
+        let cyclic = Atom::new(&mut stack, 0b1011101);
+        assert!(cue_fast(&mut stack, cyclic).is_err());
+
+        // [0 0] with the tail a backreference to the head at bit 2, and then to bit 3, which
+        // shares its slot in the table
+        let shared = Atom::new(&mut stack, 0b100100111001);
+        let fast = cue_fast(&mut stack, shared).unwrap();
+        let pair = T(&mut stack, &[D(0), D(0)]);
+        assert_noun_eq(&mut stack, fast, pair);
+        let odd = Atom::new(&mut stack, 0b110100111001);
+        assert!(cue_fast(&mut stack, odd).is_err());
+        let err = cue_limited(&mut stack, odd, &CueLimits::UNLIMITED).unwrap_err();
+        assert_eq!(err, CueError::BadBackref { backref: 3, at: 4 });
+    }
+
     #[test]
     fn test_jam_cue_roundtrip_property() {
         let rng = StdRng::seed_from_u64(1);
@@ -0,0 +1,278 @@
+/** Snapshots: a kernel and the interpreter state for it, saved to disk
+ *
+ * A snapshot lets a host restart without replaying every event. The file is a header followed by
+ * one jammed noun:
+ *
+ * | bytes | contents                                         |
+ * |-------|--------------------------------------------------|
+ * | 8     | magic, "swrdsnap"                                |
+ * | 4     | format version, little-endian                    |
+ * | 4     | murmur3 checksum of the jammed noun              |
+ * | 8     | byte length of the jammed noun, little-endian    |
+ * | ...   | jam of [kernel cold cache=(unit (list [* *]))]   |
+ *
+ * The file is written beside the old one and renamed over it, so a crash mid-write leaves the
+ * previous snapshot intact. Memo cache entries are listed oldest first.
+ *
+ * The warm state is not saved: it is rebuilt from the cold state and the hot state of the
+ * restoring context, which may have different jets than the one which took the snapshot.
+ */
+use crate::interpreter::{Context, Error};
+use crate::jets::cold::{Cold, FromNounError, Nounable};
+use crate::jets::warm::Warm;
+use crate::noun::{self, IndirectAtom, Noun, Slots, D, T};
+use crate::serialization::{cue, jam_to_vec};
+use murmur3::murmur3_32_of_slice;
+use std::convert::TryInto;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use thiserror::Error;
+
+crate::gdb!();
+
+const MAGIC: &[u8; 8] = b"swrdsnap";
+const VERSION: u32 = 1;
+const HEADER_SIZE: usize = 24;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("not a snapshot file")]
+    Magic,
+    #[error("unsupported snapshot version {0}")]
+    Version(u32),
+    #[error("snapshot checksum mismatch")]
+    Checksum,
+    #[error("snapshot length does not match its header")]
+    Length,
+    #[error("could not cue snapshot")]
+    Cue,
+    #[error("malformed snapshot: {0}")]
+    Format(#[from] FromNounError),
+}
+
+impl From<noun::Error> for SnapshotError {
+    fn from(err: noun::Error) -> Self {
+        SnapshotError::Format(err.into())
+    }
+}
+
+impl From<Error> for SnapshotError {
+    fn from(_: Error) -> Self {
+        SnapshotError::Cue
+    }
+}
+
+impl Context {
+    /** Save `kernel`, the cold state, and if `with_cache` the memo cache to a snapshot file */
+    pub fn snapshot<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        kernel: Noun,
+        with_cache: bool,
+    ) -> Result<(), SnapshotError> {
+        let stack = &mut self.stack;
+        stack.frame_push(0);
+        let cold = self.cold.into_noun(stack);
+        let cache = if with_cache {
+            let mut newest = D(0);
+            for (key, value) in self.cache.entries() {
+                let entry = T(stack, &[key, value]);
+                newest = T(stack, &[entry, newest]);
+            }
+            let mut list = D(0);
+            while let Ok(cell) = newest.as_cell() {
+                list = T(stack, &[cell.head(), list]);
+                newest = cell.tail();
+            }
+            T(stack, &[D(0), list])
+        } else {
+            D(0)
+        };
+        let snapshot = T(stack, &[kernel, cold, cache]);
+        let bytes = jam_to_vec(stack, snapshot);
+        let res = write_snapshot(path.as_ref(), &bytes);
+        unsafe { stack.frame_pop() };
+        Ok(res?)
+    }
+
+    /** Load a snapshot file, replacing the cold, warm, and memo cache state. Returns the kernel.
+     *
+     * The kernel is allocated in the current frame of the stack.
+     */
+    pub fn restore_from<P: AsRef<Path>>(&mut self, path: P) -> Result<Noun, SnapshotError> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut file = BufReader::new(file);
+        let mut header = [0u8; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        if &header[0..8] != MAGIC {
+            return Err(SnapshotError::Magic);
+        }
+        let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        if version != VERSION {
+            return Err(SnapshotError::Version(version));
+        }
+        let checksum = u32::from_le_bytes(header[12..16].try_into().unwrap());
+        let size = u64::from_le_bytes(header[16..24].try_into().unwrap());
+        if len.checked_sub(HEADER_SIZE as u64) != Some(size) {
+            return Err(SnapshotError::Length);
+        }
+        if size == 0 {
+            return Err(SnapshotError::Cue);
+        }
+        let size = size as usize;
+
+        let stack = &mut self.stack;
+        let atom = unsafe {
+            let (mut atom, body) = IndirectAtom::new_raw_mut_bytes(stack, size);
+            file.read_exact(body)?;
+            if murmur3_32_of_slice(body, 0) != checksum {
+                return Err(SnapshotError::Checksum);
+            }
+            atom.normalize_as_atom()
+        };
+        let snapshot = cue(stack, atom)?;
+
+        let kernel = snapshot.slot(2)?;
+        let (battery_to_paths, root_to_paths, path_to_batteries) =
+            Cold::from_noun(stack, &snapshot.slot(6)?)?;
+        let cache = snapshot.slot(7)?;
+
+        let mut cold = Cold::from_vecs(stack, battery_to_paths, root_to_paths, path_to_batteries);
+        self.warm = Warm::init(stack, &mut cold, &self.hot);
+        self.cold = cold;
+        self.cache.clear(stack);
+        if let Ok(cache) = cache.as_cell() {
+            let mut list = cache.tail();
+            while let Ok(cell) = list.as_cell() {
+                let entry = cell.head().as_cell()?;
+                self.cache.insert(stack, &mut entry.head(), entry.tail());
+                list = cell.tail();
+            }
+        }
+        Ok(kernel)
+    }
+}
+
+fn write_snapshot(path: &Path, body: &[u8]) -> io::Result<()> {
+    let mut header = [0u8; HEADER_SIZE];
+    header[0..8].copy_from_slice(MAGIC);
+    header[8..12].copy_from_slice(&VERSION.to_le_bytes());
+    header[12..16].copy_from_slice(&murmur3_32_of_slice(body, 0).to_le_bytes());
+    header[16..24].copy_from_slice(&(body.len() as u64).to_le_bytes());
+
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    let temp = path.with_file_name(name);
+    let mut file = BufWriter::new(File::create(&temp)?);
+    file.write_all(&header)?;
+    file.write_all(body)?;
+    file.into_inner()?.sync_all()?;
+    fs::rename(&temp, path)?;
+
+    // Make the rename durable too
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jets::util::test::{assert_noun_eq, init_context};
+    use crate::memo::MemoCache;
+    use crate::noun::Atom;
+    use std::fs;
+    use sword_macros::tas;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let path = std::env::temp_dir().join(format!("sword-snapshot-{}", std::process::id()));
+
+        let mut c = init_context();
+        let stack = &mut c.stack;
+        // A root, and a core under it with the root as its payload
+        let root = T(stack, &[D(7), D(8)]);
+        let battery = T(stack, &[D(0), D(1)]);
+        let core = T(stack, &[battery, root]);
+        let zero = Atom::new(stack, 0);
+        let axis = Atom::new(stack, 3);
+        assert!(c.cold.register(stack, root, zero, D(tas!(b"root"))).is_ok());
+        assert!(c.cold.register(stack, core, axis, D(tas!(b"core"))).is_ok());
+        let mut key = T(stack, &[D(1), D(2)]);
+        c.cache.insert(stack, &mut key, D(3));
+        let kernel = T(stack, &[D(4), D(5), D(6)]);
+        c.snapshot(&path, kernel, true).unwrap();
+
+        // Nouns from the first context's stack can't be compared on the second's
+        let mut d = init_context();
+        let kernel = d.restore_from(&path).unwrap();
+        let stack = &mut d.stack;
+        let expected = T(stack, &[D(4), D(5), D(6)]);
+        assert_noun_eq(stack, kernel, expected);
+        let root = T(stack, &[D(7), D(8)]);
+        let battery = T(stack, &[D(0), D(1)]);
+        let mut core = T(stack, &[battery, root]);
+        assert!(d.cold.matches(stack, &mut core).is_some());
+        let mut key = T(stack, &[D(1), D(2)]);
+        let hit = d.cache.lookup(stack, &mut key).unwrap();
+        assert_noun_eq(stack, hit, D(3));
+
+        // Flip a bit in the body
+        let mut bytes = fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            d.restore_from(&path),
+            Err(SnapshotError::Checksum)
+        ));
+
+        // A header claiming more than the file holds is refused before allocating for it
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(d.restore_from(&path), Err(SnapshotError::Length)));
+        bytes.truncate(HEADER_SIZE - 1);
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(d.restore_from(&path), Err(SnapshotError::Io(_))));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_keeps_cache_order() {
+        let path =
+            std::env::temp_dir().join(format!("sword-snapshot-order-{}", std::process::id()));
+
+        // A small cache, so that the order decides which generation an entry lands in
+        let mut c = init_context();
+        let stack = &mut c.stack;
+        c.cache = MemoCache::new(stack, 4);
+        for n in 0..6 {
+            c.cache.insert(stack, &mut D(n), D(n + 100));
+        }
+        let kernel = D(0);
+        c.snapshot(&path, kernel, true).unwrap();
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        assert!(!Path::new(&temp).exists());
+
+        let mut d = init_context();
+        d.cache = MemoCache::new(&mut d.stack, 4);
+        d.restore_from(&path).unwrap();
+        let keys = |c: &Context| -> Vec<u64> {
+            c.cache
+                .entries()
+                .map(|(key, _)| key.as_direct().unwrap().data())
+                .collect()
+        };
+        assert_eq!(keys(&d), keys(&c));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
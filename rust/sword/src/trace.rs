@@ -4,30 +4,151 @@ use crate::jets::bits::util::rap;
 use crate::jets::form::util::scow;
 use crate::mem::NockStack;
 use crate::mug::met3_usize;
-use crate::noun::{Atom, DirectAtom, IndirectAtom, Noun};
+use crate::noun::{Atom, DirectAtom, IndirectAtom, Noun, Slots};
+use crate::profile::ProfileNode;
 use either::Either::*;
-use json::object;
+use json::{object, JsonValue};
 use std::fs::{create_dir_all, File};
 use std::io::{Error, Write};
 use std::path::PathBuf;
 use std::result::Result;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use sword_macros::tas;
 
 crate::gdb!();
 
+/// What to write to a trace
+#[derive(Clone, Copy, Debug)]
+pub struct TraceConfig {
+    /// Don't write out Nock traces shorter than this
+    pub min_duration: Duration,
+    /// Trace calls into cores registered in the cold state
+    pub jets: bool,
+    /// Trace %spot-hinted source locations
+    pub spots: bool,
+    /// Trace events processed by the serf
+    pub events: bool,
+}
+
+impl TraceConfig {
+    /// Whether to write out Nock trace entries of this category
+    pub fn includes(&self, category: TraceCategory) -> bool {
+        match category {
+            TraceCategory::Jet => self.jets,
+            TraceCategory::Spot => self.spots,
+        }
+    }
+}
+
+impl Default for TraceConfig {
+    fn default() -> Self {
+        TraceConfig {
+            // same threshhold used in vere
+            min_duration: Duration::from_micros(33),
+            jets: true,
+            spots: false,
+            events: true,
+        }
+    }
+}
+
+/// Kind of Nock trace entry, written as the Chrome trace category
+#[derive(Clone, Copy, Debug)]
+pub enum TraceCategory {
+    Jet,
+    Spot,
+}
+
+impl TraceCategory {
+    fn name(self) -> &'static str {
+        match self {
+            TraceCategory::Jet => "jet",
+            TraceCategory::Spot => "spot",
+        }
+    }
+}
+
+/// A Chrome trace (JSON array format) being written to a sink
+///
+/// The closing bracket is written when this is dropped, so that the trace parses.
 pub struct TraceInfo {
-    pub file: File,
+    pub sink: Box<dyn Write>,
     pub pid: u32,
     pub process_start: Instant,
+    pub config: TraceConfig,
+    entries: usize,
+}
+
+impl TraceInfo {
+    pub fn new<W: Write + 'static>(sink: W, config: TraceConfig) -> TraceInfo {
+        TraceInfo {
+            sink: Box::new(sink),
+            pid: std::process::id(),
+            process_start: Instant::now(),
+            config,
+            entries: 0,
+        }
+    }
+
+    /// Write one entry to the trace array
+    fn write_entry(&mut self, obj: JsonValue) -> Result<(), Error> {
+        let sep = if self.entries == 0 { "[\n" } else { ",\n" };
+        self.sink.write_all(sep.as_bytes())?;
+        assert_no_alloc::permit_alloc(|| obj.write(&mut self.sink))?;
+        self.entries += 1;
+        Ok(())
+    }
+}
+
+impl Drop for TraceInfo {
+    fn drop(&mut self) {
+        let end = if self.entries == 0 { "[]\n" } else { "\n]\n" };
+        let _ = self.sink.write_all(end.as_bytes());
+        let _ = self.sink.flush();
+    }
 }
 
 pub struct TraceStack {
     pub start: Instant,
+    /// Cold state path for a jet, or the whole [path pint] clue for a %spot
     pub path: Noun,
+    pub category: TraceCategory,
+    /// Words the stack had allocated when the entry was pushed, for profiling
+    pub allocated: u64,
+    /// Profile node to return to when the entry is popped
+    pub profile_parent: ProfileNode,
     pub next: *const TraceStack,
 }
 
+/// Render the name of a trace entry: the path, and for a %spot the line it starts on
+pub fn trace_name(stack: &mut NockStack, path: Noun, category: TraceCategory) -> String {
+    let (path, line) = match category {
+        TraceCategory::Jet => (path, None),
+        TraceCategory::Spot => match path.as_cell() {
+            Ok(spot) => (
+                spot.head(),
+                spot.tail()
+                    .slot(4)
+                    .ok()
+                    .and_then(|row| row.as_direct().ok())
+                    .map(|row| row.data()),
+            ),
+            Err(_) => (path, None),
+        },
+    };
+
+    let pc = path_to_cord(stack, path);
+    let pc_len = met3_usize(pc);
+    let pc_bytes = &pc.as_bytes()[0..pc_len];
+    assert_no_alloc::permit_alloc(|| {
+        let pc_str = String::from_utf8_lossy(pc_bytes);
+        match line {
+            Some(line) => format!("{}:{}", pc_str, line),
+            None => pc_str.into_owned(),
+        }
+    })
+}
+
 pub fn create_trace_file(pier_path: PathBuf) -> Result<TraceInfo, Error> {
     let mut trace_dir_path = pier_path.clone();
     trace_dir_path.push(".urb");
@@ -49,49 +170,41 @@ pub fn create_trace_file(pier_path: PathBuf) -> Result<TraceInfo, Error> {
         }
     }
 
-    let file = File::create(trace_path)?;
-    let process_start = Instant::now();
-    let pid = std::process::id();
+    create_trace_file_at(trace_path)
+}
 
-    Ok(TraceInfo {
-        file,
-        pid,
-        process_start,
-    })
+/// Create a trace file at an arbitrary path
+pub fn create_trace_file_at(trace_path: PathBuf) -> Result<TraceInfo, Error> {
+    let file = File::create(trace_path)?;
+    Ok(TraceInfo::new(file, TraceConfig::default()))
 }
 
 /// Write metadata to trace file
 pub fn write_metadata(info: &mut TraceInfo) -> Result<(), Error> {
-    info.file.write_all("[ ".as_bytes())?;
+    let pid = info.pid;
 
-    (object! {
+    info.write_entry(object! {
         name: "process_name",
         ph: "M",
-        pid: info.pid,
+        pid: pid,
         args: object! { name: "urbit", },
-    })
-    .write(&mut info.file)?;
-    info.file.write_all(",\n".as_bytes())?;
+    })?;
 
-    (object! {
+    info.write_entry(object! {
         name: "thread_name",
         ph: "M",
-        pid: info.pid,
+        pid: pid,
         tid: 1,
         args: object!{ name: "Event Processing", },
-    })
-    .write(&mut info.file)?;
-    info.file.write_all(",\n".as_bytes())?;
+    })?;
 
-    (object! {
+    info.write_entry(object! {
         name: "thread_sort_index",
         ph: "M",
-        pid: info.pid,
+        pid: pid,
         tid: 1,
         args: object!{ sort_index: 1, },
-    })
-    .write(&mut info.file)?;
-    info.file.write_all(",\n".as_bytes())?;
+    })?;
 
     Ok(())
 }
@@ -108,13 +221,17 @@ pub fn write_serf_trace_safe(context: &mut Context, name: &str, start: Instant)
 }
 
 pub fn write_serf_trace(info: &mut TraceInfo, name: &str, start: Instant) -> Result<(), Error> {
+    if !info.config.events {
+        return Ok(());
+    }
+
     let ts = start
         .saturating_duration_since(info.process_start)
         .as_micros() as f64;
     let dur = Instant::now().saturating_duration_since(start).as_micros() as f64;
 
-    assert_no_alloc::permit_alloc(|| {
-        let obj = object! {
+    let obj = assert_no_alloc::permit_alloc(|| {
+        object! {
             cat: "event",
             name: name,
             ph: "X",
@@ -122,12 +239,9 @@ pub fn write_serf_trace(info: &mut TraceInfo, name: &str, start: Instant) -> Res
             tid: 1,
             ts: ts,
             dur: dur,
-        };
-        obj.write(&mut info.file)
-    })?;
-    info.file.write_all(",\n".as_bytes())?;
-
-    Ok(())
+        }
+    });
+    info.write_entry(obj)
 }
 
 pub unsafe fn write_nock_trace(
@@ -142,41 +256,33 @@ pub unsafe fn write_nock_trace(
             .start
             .saturating_duration_since(info.process_start)
             .as_micros() as f64;
-        let dur = now
-            .saturating_duration_since((*trace_stack).start)
-            .as_micros() as f64;
+        let dur = now.saturating_duration_since((*trace_stack).start);
 
-        // Don't write out traces less than 33us
-        // (same threshhold used in vere)
-        if dur < 33.0 {
+        if dur < info.config.min_duration {
             trace_stack = (*trace_stack).next;
             continue;
         }
+        let dur = dur.as_micros() as f64;
 
-        let pc = path_to_cord(stack, (*trace_stack).path);
-        let pc_len = met3_usize(pc);
-        let pc_bytes = &pc.as_bytes()[0..pc_len];
-        let pc_str = match std::str::from_utf8(pc_bytes) {
-            Ok(valid) => valid,
-            Err(error) => {
-                let (valid, _) = pc_bytes.split_at(error.valid_up_to());
-                unsafe { std::str::from_utf8_unchecked(valid) }
-            }
-        };
+        let category = (*trace_stack).category;
+        if !info.config.includes(category) {
+            trace_stack = (*trace_stack).next;
+            continue;
+        }
 
-        assert_no_alloc::permit_alloc(|| {
-            let obj = object! {
-                cat: "nock",
-                name: pc_str,
+        let name = trace_name(stack, (*trace_stack).path, category);
+        let obj = assert_no_alloc::permit_alloc(|| {
+            object! {
+                cat: category.name(),
+                name: name,
                 ph: "X",
                 pid: info.pid,
                 tid: 1,
                 ts: ts,
                 dur: dur,
-            };
-            obj.write(&mut info.file)
-        })?;
-        info.file.write_all(",\n".as_bytes())?;
+            }
+        });
+        info.write_entry(obj)?;
 
         trace_stack = (*trace_stack).next;
     }
@@ -256,3 +362,58 @@ pub fn path_to_cord(stack: &mut NockStack, path: Noun) -> Atom {
 
     unsafe { deres.normalize_as_atom() }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn parse(buf: &SharedBuf) -> JsonValue {
+        json::parse(std::str::from_utf8(&buf.0.borrow()).unwrap()).expect("trace is not JSON")
+    }
+
+    #[test]
+    fn test_trace_is_valid_json() {
+        let buf = SharedBuf::default();
+        drop(TraceInfo::new(buf.clone(), TraceConfig::default()));
+        assert_eq!(parse(&buf).len(), 0);
+
+        let buf = SharedBuf::default();
+        let mut info = TraceInfo::new(buf.clone(), TraceConfig::default());
+        write_metadata(&mut info).unwrap();
+        write_serf_trace(&mut info, "work", Instant::now()).unwrap();
+        drop(info);
+        let trace = parse(&buf);
+        assert_eq!(trace.len(), 4);
+        assert_eq!(trace[3]["cat"], "event");
+        assert_eq!(trace[3]["name"], "work");
+    }
+
+    #[test]
+    fn test_trace_filters_events() {
+        let buf = SharedBuf::default();
+        let config = TraceConfig {
+            events: false,
+            ..TraceConfig::default()
+        };
+        let mut info = TraceInfo::new(buf.clone(), config);
+        write_metadata(&mut info).unwrap();
+        write_serf_trace(&mut info, "work", Instant::now()).unwrap();
+        drop(info);
+        assert_eq!(parse(&buf).len(), 3);
+    }
+}